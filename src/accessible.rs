@@ -0,0 +1,205 @@
+/*!
+Writing a tree as descriptive plain text, without tree lines, for screen readers; the lines and
+corners of a tree drawing are read aloud as a series of symbols, or skipped entirely, so that the
+shape of the tree is lost.
+
+Each node is written on its own line with its level, where the root is at level `1`, its label,
+and a description of its place in the tree: whether it is the last child of its parent, the
+number of children written below it, and whether it is collapsed. Alternatively, each of these
+fields may be written on its own line, with an empty line between nodes. The nodes, and their
+labels, are those written by [`write_with_format`](struct.TreeNode.html#method.write_with_format)
+with the same format settings.
+
+# Example
+
+```rust
+use text_trees::{AccessibleOptions, StringTreeNode, TreeFormatting};
+
+let tree = StringTreeNode::with_child_nodes(
+    "root".to_string(),
+    vec![
+        StringTreeNode::with_children(
+            "Child 1".to_string(),
+            vec!["Grandchild".to_string()].into_iter(),
+        ),
+        "Child 2".into(),
+    ]
+    .into_iter(),
+);
+
+let result = tree
+    .to_string_accessible(&TreeFormatting::default(), &AccessibleOptions::default())
+    .unwrap();
+assert_eq!(
+    result,
+    r#"level 1: root (2 children)
+level 2: Child 1 (1 child)
+level 3: Grandchild (last)
+level 2: Child 2 (last)
+"#
+);
+```
+*/
+
+use crate::{collect_rows, ClipLines, IoWrite, TreeFormatting, TreeNode};
+use std::fmt::{self, Display, Write as _};
+use std::io::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The options controlling how a tree is written by
+/// [`write_accessible`](struct.TreeNode.html#method.write_accessible).
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessibleOptions {
+    ///
+    /// If `true` each field of a node is written on its own line, as `level: 2`, `label: Child`,
+    /// `last: yes`, `children: 3`, and `collapsed: yes`, with an empty line between nodes; the
+    /// `last`, `children`, and `collapsed` fields are only written where they apply.
+    ///
+    pub fields_per_line: bool,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return a string containing a description of each node, without tree lines, for screen
+    /// readers; the labels are formatted according to the provided format settings.
+    ///
+    pub fn to_string_accessible(
+        &self,
+        format: &TreeFormatting,
+        options: &AccessibleOptions,
+    ) -> Result<String> {
+        self.render_to_string(format, |mut w| {
+            write_tree_accessible(self, &mut w, format, options)
+        })
+    }
+
+    ///
+    /// Write a description of each node, without tree lines, for screen readers, to the provided
+    /// implementation of `std::io::Write`; the labels are formatted according to the provided
+    /// format settings.
+    ///
+    /// _Note_: the orientation, anchor, and characters of the format are not used as no tree
+    /// lines are written.
+    ///
+    pub fn write_accessible(
+        &self,
+        to_writer: &mut impl Write,
+        format: &TreeFormatting,
+        options: &AccessibleOptions,
+    ) -> Result<()> {
+        self.render_to(&mut IoWrite(to_writer), format, |mut w| {
+            write_tree_accessible(self, &mut w, format, options)
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A description of a node's place in the tree, written after its label.
+///
+#[derive(Clone, Copy, Debug)]
+enum Detail {
+    Last,
+    Children(usize),
+    Collapsed,
+}
+
+impl Display for Detail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Detail::Last => write!(f, "last"),
+            Detail::Children(1) => write!(f, "1 child"),
+            Detail::Children(count) => write!(f, "{} children", count),
+            Detail::Collapsed => write!(f, "collapsed"),
+        }
+    }
+}
+
+impl Detail {
+    fn field(&self) -> String {
+        match self {
+            Detail::Last => "last: yes".to_string(),
+            Detail::Children(count) => format!("children: {}", count),
+            Detail::Collapsed => "collapsed: yes".to_string(),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn write_tree_accessible<T>(
+    node: &TreeNode<T>,
+    w: &mut impl fmt::Write,
+    format: &TreeFormatting,
+    options: &AccessibleOptions,
+) -> fmt::Result
+where
+    T: Display,
+{
+    let mut w = ClipLines::new(w, format);
+
+    let rows = collect_rows(node, format);
+    // Whether each row is the last child of its parent, and the number of children written
+    // below it, found in a single pass from the end where `counts` records, for each depth,
+    // the number of nodes passed since the last node at a lower depth.
+    let mut is_last = vec![true; rows.len()];
+    let mut children = vec![0; rows.len()];
+    let mut counts: Vec<usize> = Vec::new();
+    for (index, (row, _)) in rows.iter().enumerate().rev() {
+        children[index] = counts.get(row.depth + 1).copied().unwrap_or_default();
+        counts.resize(row.depth + 1, 0);
+        is_last[index] = counts[row.depth] == 0;
+        counts[row.depth] += row.repeats;
+    }
+
+    for (index, (row, node)) in rows.iter().enumerate() {
+        let level = row.depth + 1;
+        let label = row.repeated_label(format.node_label(node));
+        let mut details: Vec<Detail> = Vec::new();
+        if row.depth > 0 && is_last[index] {
+            details.push(Detail::Last);
+        }
+        if children[index] > 0 {
+            details.push(Detail::Children(children[index]));
+        }
+        if node.is_collapsed() && node.has_children() {
+            details.push(Detail::Collapsed);
+        }
+
+        if options.fields_per_line {
+            if index > 0 {
+                writeln!(w)?;
+            }
+            writeln!(w, "level: {}", level)?;
+            writeln!(w, "label: {}", label)?;
+            for detail in details {
+                writeln!(w, "{}", detail.field())?;
+            }
+        } else {
+            let details: Vec<String> = details.iter().map(ToString::to_string).collect();
+            if details.is_empty() {
+                writeln!(w, "level {}: {}", level, label)?;
+            } else {
+                writeln!(w, "level {}: {} ({})", level, label, details.join(", "))?;
+            }
+        }
+    }
+    w.finish()
+}
@@ -0,0 +1,359 @@
+/*!
+The `ttree` command, available with the `cli` feature, reads a tree from standard input and
+writes it, formatted, to standard output.
+
+The input is either indented text, where each line is a node and its indentation denotes its
+parent, or a JSON document. JSON is detected when the input is a valid JSON object, or array, or
+may be requested with `--json`; so that text such as `[section]` is read as indented text.
+
+```bash
+$ printf 'root\n  a\n    a1\n  b\n' | ttree --charset box
+root
+├── a
+│   └── a1
+└── b
+```
+*/
+
+use std::io::{self, Read, Write};
+use std::process::exit;
+use text_trees::{
+    AnchorPosition, FormatCharacters, StringTreeNode, TreeFormatting, ValueTreeOptions,
+};
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum InputFormat {
+    Detect,
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SortOrder {
+    None,
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug)]
+struct Arguments {
+    input: InputFormat,
+    format: TreeFormatting,
+    max_depth: Option<usize>,
+    sort: SortOrder,
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const TAB_WIDTH: usize = 4;
+
+const USAGE: &str = r#"Usage: ttree [OPTIONS]
+
+Reads indented text, or JSON, from standard input and writes it as a tree.
+
+Options:
+  -t, --text              Read indented text
+  -j, --json              Read a JSON document
+  -c, --charset <NAME>    The line characters, "ascii" (default) or "box"
+  -a, --anchor <NAME>     The connector anchor, "below" (default) or "left"
+  -p, --prefix <STRING>   A string written before every line
+  -L, --max-depth <N>     Collapse nodes below depth N, the root has depth 0
+  -s, --sort <ORDER>      Sort children by label, "none" (default), "asc", or "desc"
+  -h, --help              Print this help and exit
+  -V, --version           Print the version and exit
+"#;
+
+// ------------------------------------------------------------------------------------------------
+// Main
+// ------------------------------------------------------------------------------------------------
+
+fn main() {
+    let arguments = match parse_arguments(std::env::args().skip(1)) {
+        Ok(arguments) => arguments,
+        Err(message) => {
+            eprintln!("ttree: {}\n\n{}", message, USAGE);
+            exit(2);
+        }
+    };
+
+    let mut input = String::new();
+    if let Err(e) = io::stdin().read_to_string(&mut input) {
+        eprintln!("ttree: could not read standard input: {}", e);
+        exit(1);
+    }
+
+    let mut tree = match read_tree(&input, arguments.input) {
+        Ok(Some(tree)) => tree,
+        Ok(None) => exit(0),
+        Err(message) => {
+            eprintln!("ttree: {}", message);
+            exit(1);
+        }
+    };
+    arrange_tree(&mut tree, arguments.sort, arguments.max_depth);
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    if let Err(e) = tree
+        .write_with_format(&mut stdout, &arguments.format)
+        .and_then(|_| stdout.flush())
+    {
+        if e.kind() != io::ErrorKind::BrokenPipe {
+            eprintln!("ttree: could not write tree: {}", e);
+            exit(1);
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn parse_arguments(args: impl Iterator<Item = String>) -> Result<Arguments, String> {
+    let mut input = InputFormat::Detect;
+    let mut chars = FormatCharacters::ascii();
+    let mut anchor = AnchorPosition::Below;
+    let mut prefix = String::new();
+    let mut max_depth = None;
+    let mut sort = SortOrder::None;
+
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value)),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| -> Result<String, String> {
+            match inline_value {
+                Some(value) => Ok(value.to_string()),
+                None => args
+                    .next()
+                    .ok_or_else(|| format!("option '{}' requires a value", name)),
+            }
+        };
+        match flag.as_str() {
+            "-t" | "--text" => input = InputFormat::Text,
+            "-j" | "--json" => input = InputFormat::Json,
+            "-c" | "--charset" => {
+                chars = match value(&flag)?.as_str() {
+                    "ascii" => FormatCharacters::ascii(),
+                    "box" => FormatCharacters::box_chars(),
+                    other => return Err(format!("unknown charset '{}'", other)),
+                }
+            }
+            "-a" | "--anchor" => {
+                anchor = match value(&flag)?.as_str() {
+                    "below" => AnchorPosition::Below,
+                    "left" => AnchorPosition::Left,
+                    other => return Err(format!("unknown anchor '{}'", other)),
+                }
+            }
+            "-p" | "--prefix" => prefix = value(&flag)?,
+            "-L" | "--max-depth" => {
+                let depth = value(&flag)?;
+                max_depth = Some(
+                    depth
+                        .parse::<usize>()
+                        .map_err(|_| format!("invalid depth '{}'", depth))?,
+                );
+            }
+            "-s" | "--sort" => {
+                sort = match value(&flag)?.as_str() {
+                    "none" => SortOrder::None,
+                    "asc" => SortOrder::Ascending,
+                    "desc" => SortOrder::Descending,
+                    other => return Err(format!("unknown sort order '{}'", other)),
+                }
+            }
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                exit(0);
+            }
+            "-V" | "--version" => {
+                println!("ttree {}", env!("CARGO_PKG_VERSION"));
+                exit(0);
+            }
+            other => return Err(format!("unknown option '{}'", other)),
+        }
+    }
+
+    let format = match anchor {
+        AnchorPosition::Below => TreeFormatting::dir_tree_with_prefix(chars, prefix),
+        AnchorPosition::Left => TreeFormatting::dir_tree_left_with_prefix(chars, prefix),
+    };
+    Ok(Arguments {
+        input,
+        format,
+        max_depth,
+        sort,
+    })
+}
+
+fn read_tree(input: &str, format: InputFormat) -> Result<Option<StringTreeNode>, String> {
+    let value: Option<serde_json::Value> = match format {
+        InputFormat::Detect if input.trim_start().starts_with(['{', '[']) => {
+            serde_json::from_str(input).ok()
+        }
+        InputFormat::Detect | InputFormat::Text => None,
+        InputFormat::Json => {
+            Some(serde_json::from_str(input).map_err(|e| format!("invalid JSON input: {}", e))?)
+        }
+    };
+    match value {
+        Some(value) => Ok(Some(text_trees::json_tree(
+            &value,
+            &ValueTreeOptions::default(),
+        ))),
+        None => Ok(parse_indented(input)),
+    }
+}
+
+///
+/// Parse indented text, where a line's parent is the closest preceding line with less
+/// indentation, tabs count as `TAB_WIDTH` spaces. If the input has more than one unindented
+/// line a root labeled `"."` is added.
+///
+fn parse_indented(input: &str) -> Option<StringTreeNode> {
+    let mut roots: Vec<StringTreeNode> = Vec::new();
+    // each entry is the indentation of an open node and the node itself.
+    let mut stack: Vec<(usize, StringTreeNode)> = Vec::new();
+
+    for line in input.lines() {
+        let label = line.trim_start();
+        if label.is_empty() {
+            continue;
+        }
+        let indent = line[..line.len() - label.len()]
+            .chars()
+            .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+            .sum::<usize>();
+        close_nodes(&mut stack, &mut roots, |open| open >= indent);
+        stack.push((indent, StringTreeNode::new(label.trim_end().to_string())));
+    }
+    close_nodes(&mut stack, &mut roots, |_| true);
+
+    match roots.len() {
+        0 => None,
+        1 => roots.pop(),
+        _ => Some(StringTreeNode::with_child_nodes(
+            ".".to_string(),
+            roots.into_iter(),
+        )),
+    }
+}
+
+fn close_nodes(
+    stack: &mut Vec<(usize, StringTreeNode)>,
+    roots: &mut Vec<StringTreeNode>,
+    should_close: impl Fn(usize) -> bool,
+) {
+    while let Some((indent, _)) = stack.last() {
+        if !should_close(*indent) {
+            break;
+        }
+        let (_, node) = stack.pop().unwrap();
+        match stack.last_mut() {
+            Some((_, parent)) => parent.push_node(node),
+            None => roots.push(node),
+        }
+    }
+}
+
+///
+/// Sort the children of every node by label, and collapse the nodes at `max_depth` that have
+/// children; neither step recurses.
+///
+fn arrange_tree(tree: &mut StringTreeNode, sort: SortOrder, max_depth: Option<usize>) {
+    match sort {
+        SortOrder::None => {}
+        SortOrder::Ascending => tree.sort_tree_by(|a, b| a.cmp(b)),
+        SortOrder::Descending => tree.sort_tree_by(|a, b| b.cmp(a)),
+    }
+    if let Some(max_depth) = max_depth {
+        let mut work: Vec<(&mut StringTreeNode, usize)> = vec![(tree, 0)];
+        while let Some((node, depth)) = work.pop() {
+            if depth < max_depth {
+                work.extend(
+                    node.children_vec_mut()
+                        .iter_mut()
+                        .map(|child| (child, depth + 1)),
+                );
+            } else if node.has_children() {
+                node.set_collapsed(true);
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(input: &str, sort: SortOrder, max_depth: Option<usize>) -> String {
+        let mut tree = parse_indented(input).unwrap();
+        arrange_tree(&mut tree, sort, max_depth);
+        tree.to_string()
+    }
+
+    #[test]
+    fn test_parse_indented() {
+        let result = render("root\n  b\n    b1\n\n  a\n\ta2\n", SortOrder::None, None);
+        assert_eq!(
+            result,
+            "root\n+-- b\n|   '-- b1\n'-- a\n    '-- a2\n".to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_multiple_roots() {
+        let result = render("one\ntwo\n  three\n", SortOrder::None, None);
+        assert_eq!(result, ".\n+-- one\n'-- two\n    '-- three\n".to_string());
+    }
+
+    #[test]
+    fn test_sort_and_depth() {
+        let result = render("root\n  b\n    b1\n  a\n", SortOrder::Ascending, Some(1));
+        assert_eq!(result, "root\n+-- a\n'-- b [+1]\n".to_string());
+    }
+
+    #[test]
+    fn test_detect_json() {
+        let tree = read_tree("[section]\n  key\n", InputFormat::Detect)
+            .unwrap()
+            .unwrap();
+        assert_eq!(tree.to_string(), "[section]\n'-- key\n".to_string());
+
+        let tree = read_tree(r#"{"key": "value"}"#, InputFormat::Detect)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            tree,
+            text_trees::json_tree(
+                &serde_json::json!({"key": "value"}),
+                &ValueTreeOptions::default()
+            )
+        );
+
+        assert!(read_tree("[section]\n", InputFormat::Json).is_err());
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        let args = ["--charset=box", "-L", "2", "--sort", "desc", "-j"];
+        let arguments = parse_arguments(args.iter().map(|s| s.to_string())).unwrap();
+        assert_eq!(arguments.input, InputFormat::Json);
+        assert_eq!(arguments.max_depth, Some(2));
+        assert_eq!(arguments.sort, SortOrder::Descending);
+
+        let args = ["--charset", "fancy"];
+        assert!(parse_arguments(args.iter().map(|s| s.to_string())).is_err());
+    }
+}
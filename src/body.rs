@@ -0,0 +1,148 @@
+/*!
+Writing a multi-line body, such as a code snippet or an error's backtrace, beneath the label of
+a node; each line of the body is indented below the label and preceded by the tree lines that
+continue past the node, in the style of error reports that nest causes and their details.
+
+As with annotations, the body of each node is provided when the tree is written, by a function
+of the node's data, so that a body need not be stored in the tree. Text below a node with
+children is preceded by the vertical line that connects those children, as written by an
+[`IndentWriter`](struct.IndentWriter.html).
+
+# Example
+
+```rust
+use text_trees::{StringTreeNode, TreeFormatting};
+
+let tree = StringTreeNode::with_children(
+    "error: build failed".to_string(),
+    vec!["lib.rs".to_string(), "main.rs".to_string()].into_iter(),
+);
+
+let result = tree
+    .to_string_with_bodies(&TreeFormatting::default(), |label| {
+        if label == "lib.rs" {
+            Some("3 | let x = ;\n  |         ^ expected expression".to_string())
+        } else {
+            None
+        }
+    })
+    .unwrap();
+assert_eq!(
+    result,
+    r#"error: build failed
++-- lib.rs
+|   3 | let x = ;
+|     |         ^ expected expression
+'-- main.rs
+"#
+);
+```
+*/
+
+use crate::{
+    classic, collect_rows, ClipLines, CompiledFormat, IoWrite, TreeFormatting, TreeNode,
+    TreeOrientation,
+};
+use std::fmt::{self, Display, Write as _};
+use std::io::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return a string containing the generated tree text formatted according to the provided
+    /// format settings, where any body returned by `bodies` is written beneath the node's label.
+    ///
+    pub fn to_string_with_bodies(
+        &self,
+        format: &TreeFormatting,
+        bodies: impl Fn(&T) -> Option<String>,
+    ) -> Result<String> {
+        self.render_to_string(format, |mut w| {
+            write_tree_with_bodies(self, &mut w, format, bodies)
+        })
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` with the provided
+    /// format settings, where any body returned by `bodies` is written beneath the node's
+    /// label; each line of the body is preceded by the tree lines for the node's position, and
+    /// empty lines are written without trailing spaces.
+    ///
+    /// _Note_: the `Classic` and `OrgChart` orientations do not have a line per node, and so no
+    /// bodies are written.
+    ///
+    pub fn write_with_bodies(
+        &self,
+        to_writer: &mut impl Write,
+        format: &TreeFormatting,
+        bodies: impl Fn(&T) -> Option<String>,
+    ) -> Result<()> {
+        self.render_to(&mut IoWrite(to_writer), format, |mut w| {
+            write_tree_with_bodies(self, &mut w, format, bodies)
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn write_tree_with_bodies<T>(
+    node: &TreeNode<T>,
+    w: &mut impl fmt::Write,
+    format: &TreeFormatting,
+    bodies: impl Fn(&T) -> Option<String>,
+) -> fmt::Result
+where
+    T: Display,
+{
+    let mut w = ClipLines::new(w, format);
+
+    if format.orientation != TreeOrientation::TopDown {
+        classic::write_tree(node, &mut w, format, &|node| format.node_label(node))?;
+        return w.finish();
+    }
+
+    let segments = CompiledFormat::new(format);
+    let rows = collect_rows(node, format);
+    // Whether each row is the last child of its parent, found in a single pass from the end
+    // where `seen` records, for each depth, whether a later sibling has been passed.
+    let mut is_last = vec![true; rows.len()];
+    let mut seen: Vec<bool> = Vec::new();
+    for (index, (row, _)) in rows.iter().enumerate().rev() {
+        seen.resize(row.depth + 1, false);
+        is_last[index] = !seen[row.depth];
+        seen[row.depth] = true;
+    }
+    // Whether each node, from a child of the root down to the current node, is the last child
+    // of its parent.
+    let mut position: Vec<bool> = Vec::new();
+    for (index, (row, node)) in rows.iter().enumerate() {
+        if row.depth > 0 {
+            position.truncate(row.depth - 1);
+            position.push(is_last[index]);
+        }
+
+        writeln!(w, "{}{}", row, row.repeated_label(format.node_label(node)))?;
+        if let Some(body) = bodies(node.data()) {
+            let has_children = rows
+                .get(index + 1)
+                .is_some_and(|(next, _)| next.depth > row.depth);
+            let indent = segments.indent(&position, has_children);
+            for line in body.lines() {
+                if line.is_empty() {
+                    writeln!(w, "{}", indent.trim_end())?;
+                } else {
+                    writeln!(w, "{}{}", indent, line)?;
+                }
+            }
+        }
+    }
+    w.finish()
+}
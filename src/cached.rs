@@ -0,0 +1,110 @@
+/*!
+Caching the label of each node, so that an expensive `Display` implementation is called once
+for each node rather than once for each time the tree is written.
+
+Labels are computed when the tree is converted by
+[`with_cached_labels`](struct.TreeNode.html#method.with_cached_labels), and every later write
+of the converted tree uses the same labels; so a tree whose labels read changing state, such as
+the file system, is written consistently.
+
+# Example
+
+```rust
+use std::cell::Cell;
+use std::fmt::{self, Display, Formatter};
+use text_trees::{TreeFormatting, TreeNode};
+
+struct Expensive<'a>(&'a str, &'a Cell<usize>);
+
+impl Display for Expensive<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.1.set(self.1.get() + 1);
+        write!(f, "{}", self.0)
+    }
+}
+
+let calls = Cell::new(0);
+let tree = TreeNode::with_children(
+    Expensive("root", &calls),
+    vec![Expensive("child", &calls)].into_iter(),
+)
+.with_cached_labels();
+assert_eq!(calls.get(), 2);
+
+let _ = tree.to_string();
+let _ = tree.to_string_with_format(&TreeFormatting::default()).unwrap();
+assert_eq!(calls.get(), 2);
+assert_eq!(tree.data().data().0, "root");
+```
+*/
+
+use crate::{map_tree, TreeNode};
+use std::fmt::{self, Display, Formatter};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The data of each node of a tree with cached labels, the original data and the label
+/// written by its `Display` implementation when the tree was converted.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachedLabel<T> {
+    data: T,
+    label: String,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T> Display for CachedLabel<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+impl<T> CachedLabel<T>
+where
+    T: Display,
+{
+    /// Construct a new value, computing the label of the provided data.
+    pub fn new(data: T) -> Self {
+        let label = data.to_string();
+        Self { data, label }
+    }
+}
+
+impl<T> CachedLabel<T> {
+    /// Return a reference to the original data.
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    /// Return the cached label.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Return the original data, discarding the cached label.
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return this tree where the label of every node has been computed once, and is reused
+    /// each time the tree is written. The collapsed state, and visibility, of each node is
+    /// retained. The tree is converted without recursion.
+    ///
+    pub fn with_cached_labels(self) -> TreeNode<CachedLabel<T>> {
+        map_tree(self, CachedLabel::new)
+    }
+}
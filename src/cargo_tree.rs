@@ -0,0 +1,274 @@
+/*!
+Construction of dependency trees from `cargo metadata` output, available with the
+`cargo-metadata` feature.
+
+The [`cargo_tree`](fn.cargo_tree.html) function uses the dependency graph resolved by Cargo,
+read using the [cargo_metadata](https://docs.rs/cargo_metadata) crate, to construct a tree of
+packages labeled `name vX.Y.Z`, much like `cargo tree`. As a package may be a dependency of many
+others its dependencies are, by default, only included the first time it appears and later
+occurrences are marked with `(*)`.
+
+# Example
+
+```rust,no_run
+use cargo_metadata::MetadataCommand;
+use text_trees::{cargo_tree, CargoTreeOptions};
+
+let metadata = MetadataCommand::new().exec().unwrap();
+let tree = cargo_tree(&metadata, &CargoTreeOptions::default()).unwrap();
+println!("{}", tree);
+```
+
+```text
+text_trees v0.2.0
++-- unicode-width v0.2.0
+'-- pretty_assertions v1.4.1 (dev)
+    +-- diff v0.1.13
+    '-- yansi v1.0.1
+```
+*/
+
+use crate::{StringTreeNode, TreeNode};
+use cargo_metadata::{DependencyKind, Metadata, Node, Package, PackageId};
+use std::collections::{HashMap, HashSet};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The options that control the construction of a dependency tree.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct CargoTreeOptions {
+    /// If `true` the dependencies of a package are only included the first time it appears in
+    /// the tree; later occurrences are labeled with a `(*)` suffix.
+    pub dedup: bool,
+    /// If set, the maximum depth of dependencies to include; the root has depth `0`.
+    pub max_depth: Option<usize>,
+    /// If `true` build dependencies are included, labeled with a `(build)` suffix.
+    pub build_dependencies: bool,
+    /// If `true` development dependencies are included, labeled with a `(dev)` suffix. Cargo only
+    /// resolves development dependencies for workspace members.
+    pub dev_dependencies: bool,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return the dependency tree of the root package of the metadata, or if there is no root
+/// package, as in a virtual workspace, a tree labeled with the workspace root whose children are
+/// the dependency trees of each workspace member.
+///
+/// This returns `None` if the metadata does not include the resolved dependency graph, as is the
+/// case when it was created with the `--no-deps` option.
+///
+pub fn cargo_tree(metadata: &Metadata, options: &CargoTreeOptions) -> Option<StringTreeNode> {
+    let resolve = metadata.resolve.as_ref()?;
+    let packages = index_packages(metadata)?;
+    match &resolve.root {
+        Some(root) => make_tree(&packages, root, options, &mut HashSet::new()),
+        None => {
+            let mut visited = HashSet::new();
+            let mut members: Vec<StringTreeNode> = Vec::new();
+            for member in &metadata.workspace_members {
+                members.push(make_tree(&packages, member, options, &mut visited)?);
+            }
+            Some(TreeNode::with_child_nodes(
+                metadata.workspace_root.to_string(),
+                members.into_iter(),
+            ))
+        }
+    }
+}
+
+///
+/// Return the dependency tree of the identified package. This returns `None` if the metadata
+/// does not include the resolved dependency graph, or does not include the package.
+///
+pub fn cargo_tree_for(
+    metadata: &Metadata,
+    package: &PackageId,
+    options: &CargoTreeOptions,
+) -> Option<StringTreeNode> {
+    make_tree(
+        &index_packages(metadata)?,
+        package,
+        options,
+        &mut HashSet::new(),
+    )
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Default for CargoTreeOptions {
+    fn default() -> Self {
+        Self {
+            dedup: true,
+            max_depth: None,
+            build_dependencies: true,
+            dev_dependencies: true,
+        }
+    }
+}
+
+impl CargoTreeOptions {
+    fn includes(&self, kind: DependencyKind) -> bool {
+        match kind {
+            DependencyKind::Build => self.build_dependencies,
+            DependencyKind::Development => self.dev_dependencies,
+            _ => true,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+/// A dependency to include in the tree, with its kind if it is not a normal dependency.
+type Dependency<'a> = (&'a PackageId, Option<DependencyKind>);
+
+/// Each package, and its node in the resolved dependency graph, by package identifier.
+type Packages<'a> = HashMap<&'a PackageId, (&'a Package, &'a Node)>;
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return the dependency tree of the identified package, built with an explicit stack of the
+/// packages being expanded, rather than by recursion, as dependency chains may be long.
+///
+fn make_tree<'a>(
+    packages: &Packages<'a>,
+    id: &'a PackageId,
+    options: &CargoTreeOptions,
+    visited: &mut HashSet<&'a PackageId>,
+) -> Option<StringTreeNode> {
+    let (label, dependencies) = expand(packages, id, None, 0, options, visited, false)?;
+    // Each open node, the package it is for, and its dependencies yet to be visited.
+    let mut stack = vec![(TreeNode::new(label), id, dependencies.into_iter())];
+    loop {
+        let next = stack.last_mut()?.2.next();
+        match next {
+            Some((id, kind)) => {
+                let is_cycle = stack.iter().any(|(_, ancestor, _)| *ancestor == id);
+                let depth = stack.len();
+                if let Some((label, dependencies)) =
+                    expand(packages, id, kind, depth, options, visited, is_cycle)
+                {
+                    stack.push((TreeNode::new(label), id, dependencies.into_iter()));
+                }
+            }
+            None => {
+                let (node, _, _) = stack.pop()?;
+                match stack.last_mut() {
+                    Some((parent, _, _)) => parent.push_node(node),
+                    None => return Some(node),
+                }
+            }
+        }
+    }
+}
+
+///
+/// Return the label of the identified package, and the dependencies to be included below it
+/// with the kind of each, or `None` if the metadata does not include the package.
+///
+fn expand<'a>(
+    packages: &Packages<'a>,
+    id: &'a PackageId,
+    kind: Option<DependencyKind>,
+    depth: usize,
+    options: &CargoTreeOptions,
+    visited: &mut HashSet<&'a PackageId>,
+    is_cycle: bool,
+) -> Option<(String, Vec<Dependency<'a>>)> {
+    let (package, node) = *packages.get(id)?;
+
+    let mut label = format!("{} v{}", package.name, package.version);
+    match kind {
+        Some(DependencyKind::Build) => label.push_str(" (build)"),
+        Some(DependencyKind::Development) => label.push_str(" (dev)"),
+        _ => {}
+    }
+
+    if is_cycle {
+        label.push_str(" (cycle)");
+        return Some((label, Vec::new()));
+    }
+    let first_visit = visited.insert(id);
+    if options.dedup && !first_visit && !node.deps.is_empty() {
+        label.push_str(" (*)");
+        return Some((label, Vec::new()));
+    }
+    if options
+        .max_depth
+        .map(|max| depth >= max)
+        .unwrap_or_default()
+    {
+        return Some((label, Vec::new()));
+    }
+
+    // a dependency may have more than one kind; it is listed once, with its most general kind.
+    let mut dependencies: Vec<(&'a PackageId, DependencyKind)> = node
+        .deps
+        .iter()
+        .filter_map(|dep| {
+            dep.dep_kinds
+                .iter()
+                .map(|info| info.kind)
+                .filter(|kind| options.includes(*kind))
+                .min_by_key(|kind| kind_order(*kind))
+                .map(|kind| (&dep.pkg, kind))
+        })
+        .collect();
+    dependencies.sort_by_key(|(_, kind)| kind_order(*kind));
+
+    let dependencies = dependencies
+        .into_iter()
+        .map(|(dep, kind)| {
+            (
+                dep,
+                Some(kind).filter(|kind| *kind != DependencyKind::Normal),
+            )
+        })
+        .collect();
+    Some((label, dependencies))
+}
+
+///
+/// Return each package with its node in the resolved dependency graph, so that each is found
+/// once rather than by searching the metadata for every occurrence in the tree; or `None` if the
+/// metadata does not include the resolved dependency graph.
+///
+fn index_packages(metadata: &Metadata) -> Option<Packages<'_>> {
+    let nodes: HashMap<&PackageId, &Node> = metadata
+        .resolve
+        .as_ref()?
+        .nodes
+        .iter()
+        .map(|node| (&node.id, node))
+        .collect();
+    Some(
+        metadata
+            .packages
+            .iter()
+            .filter_map(|package| Some((&package.id, (package, *nodes.get(&package.id)?))))
+            .collect(),
+    )
+}
+
+fn kind_order(kind: DependencyKind) -> usize {
+    match kind {
+        DependencyKind::Normal => 0,
+        DependencyKind::Build => 1,
+        DependencyKind::Development => 2,
+        _ => 3,
+    }
+}
@@ -0,0 +1,281 @@
+/*!
+The layout engine for the [`Classic`](../enum.TreeOrientation.html#variant.Classic) and
+[`OrgChart`](../enum.TreeOrientation.html#variant.OrgChart) orientations.
+
+Each node's label is centered above the span of its children, and a single row of
+`down_left_diagonal`, `vertical_line`, and `down_right_diagonal` characters connects a parent
+to each child, as in the tree diagrams common in textbooks.
+
+```text
+      root
+     /     \
+Uncle      Parent
+            /    \
+       Child 1  Child 2
+```
+
+In the org chart orientation the same layout is used, but the row below each parent is a rail
+spanning the centers of its children, which the parent joins from above.
+
+```text
+      root
+  +-----+-----+
+Uncle       Parent
+         +----+----+
+      Child 1   Child 2
+```
+
+The layout is computed in two passes; the first determines the width of each subtree, and the
+second places labels and connectors into rows which are then written in order. Neither pass
+recurses, so that very deep trees do not overflow the stack.
+*/
+
+use crate::{display_width, TreeFormatting, TreeNode, TreeOrientation};
+use std::fmt::Display;
+use std::fmt::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+/// The number of columns between adjacent sibling subtrees.
+const SIBLING_GAP: usize = 2;
+
+#[derive(Debug)]
+struct Layout {
+    label: String,
+    /// The width of the box containing this node and all its descendants.
+    width: usize,
+    /// The column, relative to the box, of the first character of the label.
+    label_start: usize,
+    /// The column, relative to the box, of the center of the label.
+    center: usize,
+    /// The column, relative to the box, of the first child's box.
+    children_start: usize,
+    /// The indices of this node's children in the list of layouts.
+    children: Vec<usize>,
+}
+
+/// The text segments, with their starting columns, for each output row.
+type Rows = Vec<Vec<(usize, String)>>;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Write the tree in the classic layout, the `label_fn` closure provides the complete label text
+/// for each node.
+///
+pub(crate) fn write_tree<T>(
+    node: &TreeNode<T>,
+    w: &mut impl Write,
+    format: &TreeFormatting,
+    label_fn: &impl Fn(&TreeNode<T>) -> String,
+) -> Result
+where
+    T: Display,
+{
+    let layouts = Layout::all(node, format, label_fn);
+    let mut rows: Rows = Default::default();
+    Layout::place(&layouts, format, &mut rows);
+
+    for segments in rows {
+        if let Some(prefix_str) = &format.prefix_str {
+            write!(w, "{}", prefix_str)?;
+        }
+        let mut column = 0;
+        for (start, text) in segments {
+            write!(w, "{}{}", " ".repeat(start.saturating_sub(column)), text)?;
+            column = start.max(column) + display_width(&text);
+        }
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Layout {
+    ///
+    /// Return the layout of every node written, in pre-order so that the root is first and each
+    /// node precedes its descendants.
+    ///
+    fn all<T>(
+        node: &TreeNode<T>,
+        format: &TreeFormatting,
+        label_fn: &impl Fn(&TreeNode<T>) -> String,
+    ) -> Vec<Self>
+    where
+        T: Display,
+    {
+        let mut layouts: Vec<Self> = Vec::new();
+        let mut work: Vec<(&TreeNode<T>, usize, Option<usize>)> = vec![(node, 0, None)];
+        while let Some((node, depth, parent)) = work.pop() {
+            let index = layouts.len();
+            let label = format.clip_label(label_fn(node), depth);
+            let width = display_width(&label);
+            layouts.push(Self {
+                label,
+                width,
+                label_start: 0,
+                center: width / 2,
+                children_start: 0,
+                children: Vec::new(),
+            });
+            if let Some(parent) = parent {
+                layouts[parent].children.push(index);
+            }
+            // Schedule any children, in reverse so the first is visited next
+            let start = work.len();
+            work.extend(
+                node.visible_children()
+                    .map(|child| (child, depth + 1, Some(index))),
+            );
+            work[start..].reverse();
+        }
+
+        // Descendants follow their ancestors, so working backwards each node's children are
+        // sized before the node itself.
+        for index in (0..layouts.len()).rev() {
+            let children: Vec<(usize, usize)> = layouts[index]
+                .children
+                .iter()
+                .map(|child| (layouts[*child].width, layouts[*child].center))
+                .collect();
+            if !children.is_empty() {
+                layouts[index].arrange(&children);
+            }
+        }
+        layouts
+    }
+
+    ///
+    /// Size the box of this node, and position its label and children within it, given the
+    /// width and center of each child's box.
+    ///
+    fn arrange(&mut self, children: &[(usize, usize)]) {
+        let label_width = self.width;
+        let children_width = children.iter().map(|(width, _)| width).sum::<usize>()
+            + (SIBLING_GAP * (children.len() - 1));
+        let width = label_width.max(children_width);
+
+        // the center of the span from the first child's label to the last child's label.
+        let (last_width, last_center) = children[children.len() - 1];
+        let span_center = (children[0].1 + (children_width - last_width + last_center)) / 2;
+
+        let (label_start, children_start) = if label_width >= children_width {
+            // center the children below the label.
+            let center = label_width / 2;
+            (
+                0,
+                center
+                    .saturating_sub(span_center)
+                    .min(width - children_width),
+            )
+        } else {
+            // center the label above the span of the children.
+            (
+                span_center
+                    .saturating_sub(label_width / 2)
+                    .min(width - label_width),
+                0,
+            )
+        };
+
+        self.width = width;
+        self.label_start = label_start;
+        self.center = label_start + label_width / 2;
+        self.children_start = children_start;
+    }
+
+    ///
+    /// Place the labels and connectors of every node into rows; nodes are placed in pre-order,
+    /// so the segments of each row are added from left to right.
+    ///
+    fn place(layouts: &[Self], format: &TreeFormatting, rows: &mut Rows) {
+        // Each item is the index of a layout, the column of its box, and its depth.
+        let mut work: Vec<(usize, usize, usize)> = vec![(0, 0, 0)];
+        while let Some((index, offset, depth)) = work.pop() {
+            let layout = &layouts[index];
+            let label_row = depth * 2;
+            if rows.len() <= label_row {
+                rows.resize_with(label_row + 1, Default::default);
+            }
+            rows[label_row].push((offset + layout.label_start, layout.label.clone()));
+
+            if layout.children.is_empty() {
+                continue;
+            }
+            let connector_row = label_row + 1;
+            if rows.len() <= connector_row {
+                rows.resize_with(connector_row + 1, Default::default);
+            }
+
+            let parent_center = offset + layout.center;
+            let mut child_offset = offset + layout.children_start;
+            let mut child_centers = Vec::with_capacity(layout.children.len());
+            let start = work.len();
+            for child in &layout.children {
+                let child_center = child_offset + layouts[*child].center;
+                child_centers.push(child_center);
+                if format.orientation != TreeOrientation::OrgChart {
+                    let connector = if child_center < parent_center {
+                        (
+                            (child_center + parent_center) / 2,
+                            format.chars.down_left_diagonal,
+                        )
+                    } else if child_center > parent_center {
+                        (
+                            (child_center + parent_center).div_ceil(2),
+                            format.chars.down_right_diagonal,
+                        )
+                    } else {
+                        (parent_center, format.chars.vertical_line)
+                    };
+                    rows[connector_row].push((connector.0, connector.1.to_string()));
+                }
+                work.push((*child, child_offset, depth + 1));
+                child_offset += layouts[*child].width + SIBLING_GAP;
+            }
+            if format.orientation == TreeOrientation::OrgChart {
+                rows[connector_row].push(rail(parent_center, &child_centers, format));
+            }
+            // in reverse so the first child is placed next
+            work[start..].reverse();
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return the starting column and text of the rail connecting a parent to its children in the
+/// org chart orientation. The rail spans the children, and the parent joins it at the column
+/// nearest its own center.
+///
+fn rail(parent: usize, children: &[usize], format: &TreeFormatting) -> (usize, String) {
+    let chars = &format.chars;
+    let (first, last) = (children[0], children[children.len() - 1]);
+    if first == last {
+        return (first, chars.vertical_line.to_string());
+    }
+    let parent = parent.clamp(first, last);
+    let rail = (first..=last)
+        .map(
+            |column| match (column == parent, children.contains(&column)) {
+                (true, true) => chars.cross,
+                (true, false) => chars.up_facing_tee,
+                (false, true) if column == first => chars.down_facing_angle,
+                (false, true) if column == last => chars.down_left_angle,
+                (false, true) => chars.down_facing_tee,
+                (false, false) => chars.horizontal_line,
+            },
+        )
+        .collect();
+    (first, rail)
+}
@@ -0,0 +1,80 @@
+/*!
+Comparators that sort labels by the collation rules of a locale, available with the `icu`
+feature; so that labels with accented, or non-Latin, characters sort as a reader of the locale
+expects, rather than by the values of their bytes.
+
+Each function returns a comparator which may be passed to
+[`sort_children_by`](struct.TreeNode.html#method.sort_children_by) or
+[`sort_tree_by`](struct.TreeNode.html#method.sort_tree_by). As with the other comparators,
+labels that collate as equal are then compared by their bytes so that each is a total order.
+
+# Example
+
+```rust
+use text_trees::{locale_order, StringTreeNode};
+
+let mut tree = StringTreeNode::with_children(
+    "words".to_string(),
+    vec!["zebra".to_string(), "Öl".to_string(), "orange".to_string()].into_iter(),
+);
+tree.sort_tree_by(locale_order("de").unwrap());
+assert_eq!(tree.to_string(), "words\n+-- Öl\n+-- orange\n'-- zebra\n");
+
+tree.sort_tree_by(locale_order("sv").unwrap());
+assert_eq!(tree.to_string(), "words\n+-- orange\n+-- zebra\n'-- Öl\n");
+```
+*/
+
+use icu_collator::{Collator, CollatorOptions, Strength};
+use icu_locid::Locale;
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::io::{Error, ErrorKind, Result};
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return a comparator of the labels of two nodes by the collation rules of the locale, named
+/// by a BCP 47 language tag such as `"en"` or `"sv-SE"`; an error of kind `InvalidInput` is
+/// returned if the tag is not valid.
+///
+pub fn locale_order<T>(locale: &str) -> Result<impl Fn(&T, &T) -> Ordering>
+where
+    T: Display,
+{
+    collation_order(locale, Strength::Tertiary)
+}
+
+///
+/// Return a comparator of the labels of two nodes by the collation rules of the locale, as
+/// [`locale_order`](fn.locale_order.html) does, ignoring case.
+///
+pub fn locale_case_insensitive_order<T>(locale: &str) -> Result<impl Fn(&T, &T) -> Ordering>
+where
+    T: Display,
+{
+    collation_order(locale, Strength::Secondary)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn collation_order<T>(locale: &str, strength: Strength) -> Result<impl Fn(&T, &T) -> Ordering>
+where
+    T: Display,
+{
+    let locale: Locale = locale
+        .parse()
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, format!("{}", e)))?;
+    let mut options = CollatorOptions::new();
+    options.strength = Some(strength);
+    let collator = Collator::try_new(&(&locale).into(), options)
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, format!("{}", e)))?;
+    Ok(move |a: &T, b: &T| {
+        let (a, b) = (a.to_string(), b.to_string());
+        collator.compare(&a, &b).then_with(|| a.cmp(&b))
+    })
+}
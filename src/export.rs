@@ -0,0 +1,226 @@
+/*!
+Export of trees as data, rather than drawings, for consumption by other tools.
+
+Each export writes one record per node, in the same order the nodes appear when the tree is
+written. Each record identifies the node's position by its depth, where the root has depth `0`,
+and its _index path_, the index of each child from the root down to the node; the root's index
+path is empty.
+*/
+
+use crate::TreeNode;
+use std::fmt::Display;
+use std::io::{BufWriter, Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The options that control the output of
+/// [`write_csv_with_options`](struct.TreeNode.html#method.write_csv_with_options).
+///
+#[derive(Clone, Debug)]
+pub struct CsvOptions {
+    /// The character separating fields in a record, `','` for CSV or `'\t'` for TSV.
+    pub delimiter: char,
+    /// The character separating the indices in the `index_path` field.
+    pub path_separator: char,
+    /// If `true`, the first record is a header naming each field.
+    pub header: bool,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self::csv()
+    }
+}
+
+impl CsvOptions {
+    /// Options for comma-separated values, with a header record.
+    pub fn csv() -> Self {
+        Self {
+            delimiter: ',',
+            path_separator: '.',
+            header: true,
+        }
+    }
+
+    /// Options for tab-separated values, with a header record.
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: '\t',
+            ..Self::csv()
+        }
+    }
+
+    fn field(&self, value: &str) -> String {
+        if value
+            .chars()
+            .any(|c| c == self.delimiter || c == '"' || c == '\n' || c == '\r')
+        {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` as comma-separated
+    /// values, with a header, and the fields `depth`, `index_path`, and `label`.
+    ///
+    pub fn write_csv(&self, to_writer: &mut impl Write) -> Result<()> {
+        self.write_csv_with_options(to_writer, &Default::default())
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` as delimited values,
+    /// with the fields `depth`, `index_path`, and `label`, according to the provided options.
+    /// Fields containing the delimiter, quotes, or line breaks are quoted.
+    ///
+    pub fn write_csv_with_options(
+        &self,
+        to_writer: &mut impl Write,
+        options: &CsvOptions,
+    ) -> Result<()> {
+        let mut to_writer = BufWriter::new(to_writer);
+        if options.header {
+            writeln!(
+                to_writer,
+                "depth{}index_path{}label",
+                options.delimiter, options.delimiter
+            )?;
+        }
+        visit_with_paths(self, |node, path| {
+            let index_path: Vec<String> = path.iter().map(ToString::to_string).collect();
+            writeln!(
+                to_writer,
+                "{}{}{}{}{}",
+                path.len(),
+                options.delimiter,
+                options.field(&index_path.join(&options.path_separator.to_string())),
+                options.delimiter,
+                options.field(&node.label())
+            )
+        })?;
+        to_writer.flush()
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` as
+    /// [JSON Lines](https://jsonlines.org/), one object per node with the fields `path`, `depth`,
+    /// and `label`. As each line is complete in itself this format is suited to streaming very
+    /// large trees to tools such as `jq`.
+    ///
+    /// # Example
+    ///
+    /// ```json
+    /// {"path":[],"depth":0,"label":"root"}
+    /// {"path":[0],"depth":1,"label":"child"}
+    /// ```
+    ///
+    pub fn write_json_lines(&self, to_writer: &mut impl Write) -> Result<()> {
+        let mut to_writer = BufWriter::new(to_writer);
+        visit_with_paths(self, |node, path| {
+            let index_path: Vec<String> = path.iter().map(ToString::to_string).collect();
+            writeln!(
+                to_writer,
+                "{{\"path\":[{}],\"depth\":{},\"label\":{}}}",
+                index_path.join(","),
+                path.len(),
+                json_string(&node.label())
+            )
+        })?;
+        to_writer.flush()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+///
+/// Call `visitor` on each node, and its index path, in the order the nodes are written; with an
+/// explicit stack of the open nodes, rather than by recursion, so that very deep trees do not
+/// overflow the stack.
+///
+fn visit_with_paths<T, F>(node: &TreeNode<T>, mut visitor: F) -> Result<()>
+where
+    T: Display,
+    F: FnMut(&TreeNode<T>, &[usize]) -> Result<()>,
+{
+    let mut path: Vec<usize> = Vec::new();
+    // Each open node, and the index of its next child to visit.
+    let mut stack: Vec<(&TreeNode<T>, usize)> = vec![(node, 0)];
+    visitor(node, &path)?;
+    while let Some((node, next)) = stack.last_mut() {
+        let node: &TreeNode<T> = node;
+        match node.children_slice().get(*next) {
+            Some(child) => {
+                path.push(*next);
+                *next += 1;
+                visitor(child, &path)?;
+                stack.push((child, 0));
+            }
+            None => {
+                let _ = stack.pop();
+                let _ = path.pop();
+            }
+        }
+    }
+    Ok(())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_quoting() {
+        let options = CsvOptions::csv();
+        assert_eq!(options.field("simple"), "simple");
+        assert_eq!(options.field("a, b"), "\"a, b\"");
+        assert_eq!(options.field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(CsvOptions::tsv().field("a, b"), "a, b");
+    }
+
+    #[test]
+    fn test_json_string_escaping() {
+        assert_eq!(json_string("simple"), r#""simple""#);
+        assert_eq!(json_string("say \"hi\""), r#""say \"hi\"""#);
+        assert_eq!(json_string("a\\b\nc"), r#""a\\b\nc""#);
+        assert_eq!(json_string("\u{1b}"), r#""\u001b""#);
+    }
+}
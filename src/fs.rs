@@ -0,0 +1,558 @@
+/*!
+Construction of trees from the file system, available with the `fs` feature.
+
+The [`fs_tree`](fn.fs_tree.html) function walks a directory hierarchy and returns a tree of
+[`FsEntry`](struct.FsEntry.html) values, the [`FsOptions`](struct.FsOptions.html) structure
+controls which entries are included, how they are sorted, and how they are labeled.
+
+# Example
+
+```rust,no_run
+use text_trees::fs::{fs_tree, FsOptions, FsSort};
+
+let options = FsOptions {
+    max_depth: Some(2),
+    exclude: vec!["target".to_string()],
+    sort: FsSort::DirectoriesFirst,
+    ..Default::default()
+};
+let tree = fs_tree(".", &options).unwrap();
+println!("{}", tree);
+```
+
+```text
+📁 .
++-- 📁 examples
+|   '-- 📄 tls.rs
++-- 📁 src
+|   '-- 📄 lib.rs
++-- 📄 Cargo.toml
+'-- 📄 README.md
+```
+
+The [`write_gnu_tree`](fn.write_gnu_tree.html) function writes a tree as the GNU `tree` command
+does, with the [`gnu_tree`](struct.FsOptions.html#method.gnu_tree) options, for scripts that
+expect its output.
+
+```rust,no_run
+use text_trees::fs::{fs_tree, write_gnu_tree, FsOptions};
+
+let options = FsOptions::gnu_tree();
+let tree = fs_tree(".", &options).unwrap();
+write_gnu_tree(&tree, &options, &mut std::io::stdout()).unwrap();
+```
+
+```text
+.
+├── Cargo.toml
+└── src
+    └── lib.rs
+
+1 directory, 2 files
+```
+
+To list the icons used in a legend after the tree, construct the tree without `icons` and write
+it with the [`FsIcons`](struct.FsIcons.html) badges and the
+[`badge_legend`](../struct.TreeFormatting.html#structfield.badge_legend) formatting option.
+
+```rust,no_run
+use text_trees::fs::{fs_tree, FsIcons, FsOptions};
+use text_trees::{BadgePosition, TreeFormatting};
+
+let options = FsOptions {
+    icons: false,
+    ..Default::default()
+};
+let tree = fs_tree(".", &options).unwrap();
+let format = TreeFormatting {
+    badge_position: BadgePosition::BeforeLabel,
+    badge_legend: true,
+    ..Default::default()
+};
+tree.write_with_badges(&mut std::io::stdout(), &format, &FsIcons)
+    .unwrap();
+```
+
+```text
+📁 .
++-- 📁 src
+|   '-- 📄 lib.rs
+'-- 📄 Cargo.toml
+
+📁 directory
+📄 file
+```
+
+Errors reading the root path are returned, however an error reading a directory below the root
+is recorded in that directory's label, as `[error: ...]`, so that one unreadable directory does
+not prevent the rest of the tree from being written.
+*/
+
+use crate::selector::glob_match;
+use crate::{Badge, TreeFormatting, TreeNode};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::{Result, Write};
+use std::path::{Path, PathBuf};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Denotes the kind of a file system entry.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FsEntryKind {
+    /// A directory.
+    Directory,
+    /// A regular file.
+    File,
+    /// A symbolic link that is not followed, or whose target does not exist.
+    Symlink,
+    /// Any other kind of entry, such as a device or socket.
+    Other,
+}
+
+///
+/// A single file system entry, the data for each node in the tree returned by
+/// [`fs_tree`](fn.fs_tree.html). The label of the entry is computed when the tree is
+/// constructed, so that writing the tree does not access the file system.
+///
+#[derive(Clone, Debug)]
+pub struct FsEntry {
+    path: PathBuf,
+    kind: FsEntryKind,
+    size: Option<u64>,
+    link_target: Option<PathBuf>,
+    label: String,
+}
+
+///
+/// A tree of file system entries.
+///
+pub type FsTreeNode = TreeNode<FsEntry>;
+
+///
+/// Denotes how symbolic links are handled.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SymlinkHandling {
+    /// Symbolic links are shown, with their target, but not followed.
+    Show,
+    /// Symbolic links are followed, a link to a directory includes the directory's content.
+    /// Directories already visited are not followed again, so cycles are safe.
+    Follow,
+    /// Symbolic links are not included in the tree.
+    Skip,
+}
+
+///
+/// Denotes the order of the children of each directory.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FsSort {
+    /// Entries are in the order returned by the operating system.
+    Unsorted,
+    /// Entries are sorted by name.
+    Name,
+    /// Directories are listed before other entries, each group sorted by name.
+    DirectoriesFirst,
+    /// Entries are sorted by size, largest first; directories have no size and are listed last.
+    Size,
+}
+
+///
+/// The options that control the construction of a file system tree.
+///
+#[derive(Clone, Debug)]
+pub struct FsOptions {
+    /// If `true` entries whose name starts with `'.'` are included.
+    pub show_hidden: bool,
+    /// How symbolic links are handled.
+    pub symlinks: SymlinkHandling,
+    /// If set, the maximum depth of directories to read; the root has depth `0`.
+    pub max_depth: Option<usize>,
+    /// If not empty, only files whose name matches one of these glob patterns are included;
+    /// directories are always included. Patterns may contain `'*'` and `'?'`.
+    pub include: Vec<String>,
+    /// Entries, files or directories, whose name matches one of these glob patterns are excluded.
+    pub exclude: Vec<String>,
+    /// The order of the children of each directory.
+    pub sort: FsSort,
+    /// If `true` labels are prefixed with an icon denoting the kind of entry.
+    pub icons: bool,
+    /// If `true` the labels of files include their size.
+    pub sizes: bool,
+    /// If `true` only directories are included.
+    pub directories_only: bool,
+}
+
+///
+/// Provides the icon denoting the kind of each entry as a [`Badge`](../trait.Badge.html), with a
+/// description of each icon for the legend; these are the icons written by the `icons` option.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FsIcons;
+
+///
+/// The number of directories, and of other entries, below the root of a file system tree; this is
+/// written as the summary of the GNU `tree` command, such as `"1 directory, 2 files"`.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FsSummary {
+    /// The number of directories, not including the root.
+    pub directories: usize,
+    /// The number of entries that are not directories.
+    pub files: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return a tree of the entries in the file system below, and including, `path`, according to
+/// the provided options.
+///
+pub fn fs_tree(path: impl AsRef<Path>, options: &FsOptions) -> Result<FsTreeNode> {
+    let path = path.as_ref();
+    let metadata = fs::symlink_metadata(path)?;
+    let mut visited: HashSet<PathBuf> = Default::default();
+    let root = FsEntry::new(path, path.display().to_string(), &metadata, options);
+    if root.kind == FsEntryKind::Directory {
+        let _ = fs::read_dir(path)?;
+    }
+    Ok(make_tree(root, options, &mut visited))
+}
+
+///
+/// Write the tree with the [`gnu_tree`](../struct.TreeFormatting.html#method.gnu_tree) format,
+/// followed by an empty line and the summary line of the GNU `tree` command; the summary omits the
+/// number of files if the options include only directories.
+///
+pub fn write_gnu_tree(
+    tree: &FsTreeNode,
+    options: &FsOptions,
+    to_writer: &mut impl Write,
+) -> Result<()> {
+    tree.write_with_format(to_writer, &TreeFormatting::gnu_tree())?;
+    let summary = FsSummary::of(tree);
+    if options.directories_only {
+        writeln!(
+            to_writer,
+            "\n{}",
+            plural(summary.directories, "directory", "directories")
+        )
+    } else {
+        writeln!(to_writer, "\n{}", summary)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+const ICON_FOLDER: &str = "📁";
+const ICON_FILE: &str = "📄";
+const ICON_LINK: &str = "🔗";
+const ICON_OTHER: &str = "☠️";
+
+impl Display for FsEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+impl FsEntry {
+    /// Return the path of this entry.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Return the kind of this entry.
+    pub fn kind(&self) -> FsEntryKind {
+        self.kind
+    }
+
+    /// Return the size, in bytes, of this entry if it is a file.
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    /// Return the target of this entry if it is a symbolic link.
+    pub fn link_target(&self) -> Option<&Path> {
+        self.link_target.as_deref()
+    }
+
+    fn new(path: &Path, name: String, metadata: &fs::Metadata, options: &FsOptions) -> Self {
+        let file_type = metadata.file_type();
+        let (kind, link_target, metadata) = if file_type.is_symlink() {
+            let link_target = fs::read_link(path).ok();
+            match (options.symlinks, fs::metadata(path)) {
+                (SymlinkHandling::Follow, Ok(target_metadata)) => {
+                    (kind_of(&target_metadata), link_target, target_metadata)
+                }
+                _ => (FsEntryKind::Symlink, link_target, metadata.clone()),
+            }
+        } else {
+            (kind_of(metadata), None, metadata.clone())
+        };
+        let size = if kind == FsEntryKind::File {
+            Some(metadata.len())
+        } else {
+            None
+        };
+
+        let mut label = String::new();
+        if options.icons {
+            label.push_str(icon_of(kind).0);
+            label.push(' ');
+        }
+        label.push_str(&name);
+        if let (FsEntryKind::Symlink, Some(target)) = (kind, &link_target) {
+            label.push_str(&format!(" -> {}", target.display()));
+        }
+        if let (true, Some(size)) = (options.sizes, size) {
+            label.push_str(&format!(" ({})", human_size(size)));
+        }
+
+        Self {
+            path: path.to_path_buf(),
+            kind,
+            size,
+            link_target,
+            label,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Default for FsOptions {
+    fn default() -> Self {
+        Self {
+            show_hidden: false,
+            symlinks: SymlinkHandling::Show,
+            max_depth: None,
+            include: Default::default(),
+            exclude: Default::default(),
+            sort: FsSort::Name,
+            icons: true,
+            sizes: false,
+            directories_only: false,
+        }
+    }
+}
+
+impl FsOptions {
+    ///
+    /// Construct the options that match the defaults of the GNU `tree` command, entries sorted
+    /// by name and labeled without icons or sizes. The command's `-a` option corresponds to
+    /// `show_hidden`, `-d` to `directories_only`, `-L` to `max_depth`, `-P` to `include`, and
+    /// `-I` to `exclude`.
+    ///
+    pub fn gnu_tree() -> Self {
+        Self {
+            icons: false,
+            ..Default::default()
+        }
+    }
+
+    fn is_included(&self, name: &str, kind: FsEntryKind) -> bool {
+        let hidden = !self.show_hidden && name.starts_with('.');
+        let skipped = kind == FsEntryKind::Symlink && self.symlinks == SymlinkHandling::Skip;
+        let excluded = self.exclude.iter().any(|pattern| glob_match(pattern, name));
+        let not_directory = self.directories_only && kind != FsEntryKind::Directory;
+        !(hidden || skipped || excluded || not_directory)
+            && (kind == FsEntryKind::Directory
+                || self.include.is_empty()
+                || self.include.iter().any(|pattern| glob_match(pattern, name)))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Badge<FsEntry> for FsIcons {
+    fn badge(&self, data: &FsEntry) -> Option<String> {
+        Some(icon_of(data.kind).0.to_string())
+    }
+
+    fn description(&self, badge: &str) -> Option<String> {
+        [
+            FsEntryKind::Directory,
+            FsEntryKind::File,
+            FsEntryKind::Symlink,
+            FsEntryKind::Other,
+        ]
+        .iter()
+        .map(|kind| icon_of(*kind))
+        .find(|(icon, _)| *icon == badge)
+        .map(|(_, description)| description.to_string())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for FsSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}, {}",
+            plural(self.directories, "directory", "directories"),
+            plural(self.files, "file", "files")
+        )
+    }
+}
+
+impl FsSummary {
+    /// Return the number of directories, and of other entries, below the root of the tree.
+    pub fn of(tree: &FsTreeNode) -> Self {
+        let mut summary = Self::default();
+        for (depth, entry) in tree.iter_with_depth() {
+            match (depth, entry.kind) {
+                (0, _) => {}
+                (_, FsEntryKind::Directory) => summary.directories += 1,
+                _ => summary.files += 1,
+            }
+        }
+        summary
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return the tree below `root`, built with [`unfold`](../struct.TreeNode.html#method.unfold) so
+/// that very deep directory hierarchies do not overflow the stack.
+///
+fn make_tree(root: FsEntry, options: &FsOptions, visited: &mut HashSet<PathBuf>) -> FsTreeNode {
+    TreeNode::unfold((root, 0), |(entry, depth)| {
+        let (entry, children) = read_children(entry, depth, options, visited);
+        (
+            entry,
+            children
+                .into_iter()
+                .map(|child| (child, depth + 1))
+                .collect(),
+        )
+    })
+}
+
+///
+/// Return the entry, with any error reading it appended to its label, and the entries of its
+/// children, sorted, if it is a directory that is to be expanded.
+///
+fn read_children(
+    entry: FsEntry,
+    depth: usize,
+    options: &FsOptions,
+    visited: &mut HashSet<PathBuf>,
+) -> (FsEntry, Vec<FsEntry>) {
+    if entry.kind != FsEntryKind::Directory
+        || options
+            .max_depth
+            .map(|max| depth >= max)
+            .unwrap_or_default()
+    {
+        return (entry, Vec::new());
+    }
+    if let Ok(canonical) = entry.path.canonicalize() {
+        if !visited.insert(canonical) {
+            return (entry, Vec::new());
+        }
+    }
+
+    let mut children: Vec<FsEntry> = Vec::new();
+    let mut entry = entry;
+    match fs::read_dir(&entry.path) {
+        Err(e) => entry.label.push_str(&format!(" [error: {}]", e)),
+        Ok(dir_entries) => {
+            for dir_entry in dir_entries.flatten() {
+                let name = dir_entry.file_name().to_string_lossy().to_string();
+                if let Ok(metadata) = fs::symlink_metadata(dir_entry.path()) {
+                    let child = FsEntry::new(&dir_entry.path(), name.clone(), &metadata, options);
+                    if options.is_included(&name, child.kind) {
+                        children.push(child);
+                    }
+                }
+            }
+        }
+    }
+
+    match options.sort {
+        FsSort::Unsorted => {}
+        FsSort::Name => children.sort_by(|a, b| a.path.file_name().cmp(&b.path.file_name())),
+        FsSort::DirectoriesFirst => children.sort_by(|a, b| {
+            (a.kind != FsEntryKind::Directory)
+                .cmp(&(b.kind != FsEntryKind::Directory))
+                .then_with(|| a.path.file_name().cmp(&b.path.file_name()))
+        }),
+        FsSort::Size => children.sort_by_key(|entry| std::cmp::Reverse(entry.size)),
+    }
+
+    (entry, children)
+}
+
+fn kind_of(metadata: &fs::Metadata) -> FsEntryKind {
+    let file_type = metadata.file_type();
+    if file_type.is_dir() {
+        FsEntryKind::Directory
+    } else if file_type.is_file() {
+        FsEntryKind::File
+    } else if file_type.is_symlink() {
+        FsEntryKind::Symlink
+    } else {
+        FsEntryKind::Other
+    }
+}
+
+///
+/// Return the icon denoting the kind of entry, and its description.
+///
+fn icon_of(kind: FsEntryKind) -> (&'static str, &'static str) {
+    match kind {
+        FsEntryKind::Directory => (ICON_FOLDER, "directory"),
+        FsEntryKind::File => (ICON_FILE, "file"),
+        FsEntryKind::Symlink => (ICON_LINK, "symbolic link"),
+        FsEntryKind::Other => (ICON_OTHER, "other"),
+    }
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    format!("{} {}", count, if count == 1 { one } else { many })
+}
+
+fn human_size(size: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+    if size < 1024 {
+        return format!("{} B", size);
+    }
+    let mut value = size as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_human_size() {
+        assert_eq!(human_size(0), "0 B");
+        assert_eq!(human_size(1023), "1023 B");
+        assert_eq!(human_size(1536), "1.5 KiB");
+        assert_eq!(human_size(5 * 1024 * 1024), "5.0 MiB");
+    }
+}
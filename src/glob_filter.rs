@@ -0,0 +1,87 @@
+/*!
+Matching node labels against glob patterns, such as `"*.rs"` or `"test_*"`, available with the
+`globset` feature.
+
+A [`GlobFilter`](struct.GlobFilter.html) compiles a set of patterns once, a label matches the
+filter if it matches any of them. The filter's
+[`predicate`](struct.GlobFilter.html#method.predicate) may be passed to any method that takes a
+predicate on node data, such as [`find_all`](struct.TreeNode.html#method.find_all), or to
+[`hide_unmatched`](struct.TreeNode.html#method.hide_unmatched) so that only the matching nodes,
+and their ancestors, are written. Patterns support `'*'`, `'?'`, character classes such as
+`"[a-z]"`, and alternatives such as `"*.{rs,toml}"`.
+
+# Example
+
+```rust
+use text_trees::{GlobFilter, StringTreeNode};
+
+let tree = StringTreeNode::with_children(
+    "src".to_string(),
+    vec!["lib.rs".to_string(), "test_lib.rs".to_string(), "README.md".to_string()].into_iter(),
+);
+let filter = GlobFilter::new(vec!["*.rs"]).unwrap();
+
+let paths: Vec<Vec<usize>> = tree
+    .find_all(filter.predicate())
+    .into_iter()
+    .map(|(path, _)| path)
+    .collect();
+assert_eq!(paths, vec![vec![0], vec![1]]);
+```
+*/
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::fmt::Display;
+use std::io::{Error, ErrorKind, Result};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A compiled set of glob patterns matched against the labels of nodes.
+///
+#[derive(Clone, Debug)]
+pub struct GlobFilter(GlobSet);
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl GlobFilter {
+    ///
+    /// Construct a new filter matching labels that match any of the provided patterns; an error
+    /// of kind `InvalidInput` is returned if any pattern is not valid.
+    ///
+    pub fn new<S>(patterns: impl IntoIterator<Item = S>) -> Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let _ = builder.add(Glob::new(pattern.as_ref()).map_err(invalid_input)?);
+        }
+        Ok(Self(builder.build().map_err(invalid_input)?))
+    }
+
+    /// Return `true` if the label matches any of this filter's patterns.
+    pub fn is_match(&self, label: &str) -> bool {
+        self.0.is_match(label)
+    }
+
+    /// Return a predicate which is `true` for node data whose label matches this filter.
+    pub fn predicate<T>(&self) -> impl Fn(&T) -> bool + '_
+    where
+        T: Display,
+    {
+        move |data| self.is_match(&data.to_string())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn invalid_input(e: globset::Error) -> Error {
+    Error::new(ErrorKind::InvalidInput, e)
+}
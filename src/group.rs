@@ -0,0 +1,264 @@
+/*!
+Grouping the children of each node, by a classifier, under header rows that are written as part
+of the tree but are not nodes of it.
+
+Rather than inserting header nodes into the tree, which would require the data type to represent
+them, the tree is written through a view in which each node's children are arranged under a
+header row for each group, in the order that each group is first seen among the children.
+
+# Example
+
+```rust
+use text_trees::{GroupOptions, StringTreeNode, TreeFormatting};
+
+let tree = StringTreeNode::with_children(
+    "src".to_string(),
+    vec!["bin/".to_string(), "lib.rs".to_string(), "main.rs".to_string()].into_iter(),
+);
+
+let result = tree
+    .to_string_grouped(&TreeFormatting::default(), &GroupOptions::default(), |name| {
+        if name.ends_with('/') { "directories" } else { "files" }.to_string()
+    })
+    .unwrap();
+assert_eq!(
+    result,
+    "src\n+-- [directories]\n|   '-- bin/\n'-- [files]\n    +-- lib.rs\n    '-- main.rs\n"
+);
+```
+*/
+
+use crate::{
+    collect_rows, write_tree_inner, AnchorPosition, BranchConnectors, ClipLines, IoWrite,
+    RowPrefix, Rows, TreeFormatting, TreeNode, TreeOrientation,
+};
+use std::fmt::{self, Display, Formatter, Write as _};
+use std::io::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The options controlling how group header rows are written by
+/// [`write_grouped`](struct.TreeNode.html#method.write_grouped).
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupOptions {
+    /// The string written before the name of each group, default is `"["`.
+    pub header_prefix: String,
+    /// The string written after the name of each group, default is `"]"`.
+    pub header_suffix: String,
+    /// If set, and lines are anchored below labels, header rows are connected with these
+    /// characters rather than those of the format, so that headers are distinct from nodes.
+    pub connectors: Option<BranchConnectors>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Default for GroupOptions {
+    fn default() -> Self {
+        Self {
+            header_prefix: "[".to_string(),
+            header_suffix: "]".to_string(),
+            connectors: None,
+        }
+    }
+}
+
+impl GroupOptions {
+    fn header(&self, name: &str) -> String {
+        format!("{}{}{}", self.header_prefix, name, self.header_suffix)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return a string containing the generated tree text formatted according to the provided
+    /// format settings, where the children of each node are written below a header row for
+    /// each group returned by `classifier`. The classifier is given the data of each node and
+    /// returns the name of its group.
+    ///
+    pub fn to_string_grouped(
+        &self,
+        format: &TreeFormatting,
+        options: &GroupOptions,
+        classifier: impl Fn(&T) -> String,
+    ) -> Result<String> {
+        self.render_to_string(format, |mut w| {
+            write_grouped_tree(&grouped_tree(self, classifier), &mut w, format, options)
+        })
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` with the provided
+    /// format settings, where the children of each node are written below a header row for
+    /// each group returned by `classifier`, as
+    /// [`to_string_grouped`](struct.TreeNode.html#method.to_string_grouped) describes.
+    ///
+    pub fn write_grouped(
+        &self,
+        to_writer: &mut impl Write,
+        format: &TreeFormatting,
+        options: &GroupOptions,
+        classifier: impl Fn(&T) -> String,
+    ) -> Result<()> {
+        self.render_to(&mut IoWrite(to_writer), format, |mut w| {
+            write_grouped_tree(&grouped_tree(self, classifier), &mut w, format, options)
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A row of the grouped view, either a node of the source tree or a group header.
+///
+#[derive(Debug)]
+enum Grouped<'a, T>
+where
+    T: Display,
+{
+    Node(&'a TreeNode<T>),
+    Header(String),
+}
+
+impl<T> Display for Grouped<'_, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Node(node) => write!(f, "{}", node.data),
+            Self::Header(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return the grouped view of the provided tree. The nodes are listed breadth first, with the
+/// index of each node's parent, and assembled into a tree at the end; neither step recurses.
+///
+fn grouped_tree<'a, T>(
+    node: &'a TreeNode<T>,
+    classifier: impl Fn(&T) -> String,
+) -> TreeNode<Grouped<'a, T>>
+where
+    T: Display,
+{
+    let mut grouped: Vec<(Grouped<'a, T>, usize)> = vec![(Grouped::Node(node), 0)];
+    let mut index = 0;
+    while index < grouped.len() {
+        if let (Grouped::Node(source), _) = &grouped[index] {
+            let mut groups: Vec<(String, Vec<&'a TreeNode<T>>)> = Vec::new();
+            for child in source.visible_children() {
+                let name = classifier(&child.data);
+                match groups.iter_mut().find(|(group, _)| *group == name) {
+                    Some((_, members)) => members.push(child),
+                    None => groups.push((name, vec![child])),
+                }
+            }
+            for (name, members) in groups {
+                grouped.push((Grouped::Header(name), index));
+                let header = grouped.len() - 1;
+                grouped.extend(
+                    members
+                        .into_iter()
+                        .map(|child| (Grouped::Node(child), header)),
+                );
+            }
+        }
+        index += 1;
+    }
+
+    let mut children: Vec<Vec<TreeNode<Grouped<'a, T>>>> =
+        grouped.iter().map(|_| Vec::new()).collect();
+    while let Some((data, parent)) = grouped.pop() {
+        let node = TreeNode::with_child_nodes(data, children.pop().unwrap().into_iter().rev());
+        if grouped.is_empty() {
+            return node;
+        }
+        children[parent].push(node);
+    }
+    unreachable!()
+}
+
+fn write_grouped_tree<T>(
+    tree: &TreeNode<Grouped<'_, T>>,
+    w: &mut impl fmt::Write,
+    format: &TreeFormatting,
+    options: &GroupOptions,
+) -> fmt::Result
+where
+    T: Display,
+{
+    let label_fn = |node: &TreeNode<Grouped<'_, T>>| match &node.data {
+        Grouped::Node(source) => format.node_label(source),
+        Grouped::Header(name) => options.header(name),
+    };
+    if format.orientation != TreeOrientation::TopDown {
+        return write_tree_inner(tree, w, format, &label_fn);
+    }
+
+    let connectors = options
+        .connectors
+        .as_ref()
+        .filter(|_| format.anchor == AnchorPosition::Below);
+    let mut w = ClipLines::new(w, format);
+    let mut write_row = |mut row: RowPrefix, node: &TreeNode<Grouped<'_, T>>| {
+        if let (Grouped::Header(_), Some(connectors)) = (&node.data, connectors) {
+            replace_connector(&mut row.lines, format, connectors);
+        }
+        writeln!(w, "{}{}", row, row.repeated_label(label_fn(node)))
+    };
+    if format.align_depths {
+        for (row, node) in collect_rows(tree, format) {
+            write_row(row, node)?;
+        }
+    } else {
+        for (row, node) in Rows::new(tree, format) {
+            write_row(row, node)?;
+        }
+    }
+    w.finish()
+}
+
+///
+/// Replace the connector character of a header row, which always has children, with the
+/// corresponding header connector. The connector is the last tee, or angle, in the row's lines
+/// as only vertical lines precede it and only horizontal lines follow it.
+///
+fn replace_connector(lines: &mut String, format: &TreeFormatting, connectors: &BranchConnectors) {
+    let (tee, angle) = match &format.branch_connectors {
+        Some(branch) => (branch.tee, branch.angle),
+        None => (
+            format.chars.right_facing_tee,
+            format.chars.right_facing_angle,
+        ),
+    };
+    if let Some((index, c)) = lines
+        .char_indices()
+        .rev()
+        .find(|(_, c)| *c == tee || *c == angle)
+    {
+        let replacement = if c == tee {
+            connectors.tee
+        } else {
+            connectors.angle
+        };
+        lines.replace_range(index..index + c.len_utf8(), &replacement.to_string());
+    }
+}
@@ -0,0 +1,209 @@
+/*!
+An extension point, the [`RenderHook`](trait.RenderHook.html) trait, that is called as each
+node, and each line, of a tree is written; allowing custom numbering, folding markers, or
+instrumentation without changes to the renderer.
+
+Each callback receives the path of the node, the position of the node among the children
+written below each of its ancestors, and its depth, which is the length of the path. Lines may
+be changed by the hook before they are written; the `max_line_width`, `elastic_tabs`, and
+`line_transforms` formatting options apply to the changed lines.
+
+# Example
+
+```rust
+use text_trees::{RenderHook, StringTreeNode, TreeFormatting};
+
+struct Numbering(usize);
+
+impl RenderHook<String> for Numbering {
+    fn on_line(&mut self, _path: &[usize], _depth: usize, line: &mut String) {
+        self.0 += 1;
+        line.insert_str(0, &format!("{:>2} ", self.0));
+    }
+}
+
+let tree = StringTreeNode::with_children(
+    "root".to_string(),
+    vec!["a".to_string(), "b".to_string()].into_iter(),
+);
+
+let result = tree
+    .to_string_with_hook(&TreeFormatting::default(), &mut Numbering(0))
+    .unwrap();
+assert_eq!(result, " 1 root\n 2 +-- a\n 3 '-- b\n");
+```
+*/
+
+use crate::{
+    classic, collect_rows, ClipLines, IoWrite, RowPrefix, Rows, TreeFormatting, TreeNode,
+    TreeOrientation,
+};
+use std::fmt::{self, Display, Write as _};
+use std::io::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Called by [`write_with_hook`](struct.TreeNode.html#method.write_with_hook) as a tree is
+/// written; each method has an empty default implementation so that a hook need only implement
+/// those it requires.
+///
+/// For each node `on_node_start` is called, then `on_line` for each line written for the node,
+/// and `on_node_end` is called once all of the node's descendants have been written.
+///
+pub trait RenderHook<T>
+where
+    T: Display,
+{
+    /// Called before any line is written for `node`.
+    fn on_node_start(&mut self, _path: &[usize], _depth: usize, _node: &TreeNode<T>) {}
+
+    /// Called with each line, without line ending, before it is written; the line may be
+    /// changed, and any line endings added to it are written.
+    fn on_line(&mut self, _path: &[usize], _depth: usize, _line: &mut String) {}
+
+    /// Called after the lines of `node`, and of all its descendants, have been written.
+    fn on_node_end(&mut self, _path: &[usize], _depth: usize, _node: &TreeNode<T>) {}
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return a string containing the generated tree text formatted according to the provided
+    /// format settings, calling the provided hook as each node, and line, is written.
+    ///
+    pub fn to_string_with_hook(
+        &self,
+        format: &TreeFormatting,
+        hook: &mut impl RenderHook<T>,
+    ) -> Result<String> {
+        self.render_to_string(format, |mut w| {
+            write_tree_with_hook(self, &mut w, format, hook)
+        })
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` with the provided
+    /// format settings, calling the provided hook as each node, and line, is written.
+    ///
+    /// _Note_: in the `Classic` and `OrgChart` orientations lines do not belong to a single
+    /// node, so `on_node_start` and `on_node_end` are called for every node, in depth-first
+    /// order, before any line is written; `on_line` is then called for each line with an empty
+    /// path and a depth of `0`.
+    ///
+    pub fn write_with_hook(
+        &self,
+        to_writer: &mut impl Write,
+        format: &TreeFormatting,
+        hook: &mut impl RenderHook<T>,
+    ) -> Result<()> {
+        self.render_to(&mut IoWrite(to_writer), format, |mut w| {
+            write_tree_with_hook(self, &mut w, format, hook)
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn write_tree_with_hook<T>(
+    node: &TreeNode<T>,
+    w: &mut impl fmt::Write,
+    format: &TreeFormatting,
+    hook: &mut impl RenderHook<T>,
+) -> fmt::Result
+where
+    T: Display,
+{
+    let mut w = ClipLines::new(w, format);
+
+    if format.orientation != TreeOrientation::TopDown {
+        visit_rendered(node, hook);
+        let mut text = String::new();
+        classic::write_tree(node, &mut text, format, &|node| format.node_label(node))?;
+        for line in text.lines() {
+            let mut line = line.to_string();
+            hook.on_line(&[], 0, &mut line);
+            writeln!(w, "{}", line)?;
+        }
+        return w.finish();
+    }
+
+    let rows: Box<dyn Iterator<Item = (RowPrefix, &TreeNode<T>)>> = if format.align_depths {
+        Box::new(collect_rows(node, format).into_iter())
+    } else {
+        Box::new(Rows::new(node, format))
+    };
+    // The position of each open node among its siblings, the root is at index `0`, and the
+    // open nodes themselves; both have one entry per level from the root to the current node.
+    let mut positions: Vec<usize> = Vec::new();
+    let mut open: Vec<&TreeNode<T>> = Vec::new();
+    for (mut row, node) in rows {
+        while open.len() > row.depth {
+            let ended = open.pop().unwrap();
+            hook.on_node_end(&positions[1..open.len() + 1], open.len(), ended);
+        }
+        positions.truncate(row.depth + 1);
+        if positions.len() == row.depth + 1 {
+            positions[row.depth] += 1;
+        } else {
+            positions.push(0);
+        }
+        let path = &positions[1..];
+
+        hook.on_node_start(path, row.depth, node);
+        if let Some(mut connector) = row.connector.take() {
+            hook.on_line(path, row.depth, &mut connector);
+            writeln!(w, "{}", connector)?;
+        }
+        let mut line = format!("{}{}", row, row.repeated_label(format.node_label(node)));
+        hook.on_line(path, row.depth, &mut line);
+        writeln!(w, "{}", line)?;
+        open.push(node);
+    }
+    while let Some(ended) = open.pop() {
+        hook.on_node_end(&positions[1..open.len() + 1], open.len(), ended);
+    }
+    w.finish()
+}
+
+///
+/// Call the start and end hooks for every node written, in depth-first order, without recursion.
+///
+fn visit_rendered<T>(node: &TreeNode<T>, hook: &mut impl RenderHook<T>)
+where
+    T: Display,
+{
+    let mut path: Vec<usize> = Vec::new();
+    hook.on_node_start(&path, 0, node);
+    let mut stack = vec![(node, node.visible_children())];
+    while let Some((parent, children)) = stack.last_mut() {
+        match children.next() {
+            Some(child) => {
+                let depth = stack.len();
+                if path.len() == depth {
+                    path[depth - 1] += 1;
+                } else {
+                    path.push(0);
+                }
+                hook.on_node_start(&path, depth, child);
+                stack.push((child, child.visible_children()));
+            }
+            None => {
+                let parent = *parent;
+                let _ = stack.pop();
+                path.truncate(stack.len());
+                hook.on_node_end(&path, stack.len(), parent);
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+/*!
+A writer, [`IndentWriter`](struct.IndentWriter.html), that prefixes each line of arbitrary text
+with the tree lines for a position in a tree; so that text, such as the output of a command, may
+be streamed below a node's label with the guides of the surrounding tree intact.
+
+The position of a node is given by whether each node, from a child of the root down to the node
+itself, is the last child of its parent; the root's position is empty. Text below a node with no
+children starts in the column of the node's label, text below a node with children is preceded
+by the vertical line that connects those children.
+
+# Example
+
+```rust
+use std::io::Write;
+use text_trees::{IndentWriter, TreeFormatting};
+
+let format = TreeFormatting::default();
+let mut output: Vec<u8> = Vec::new();
+writeln!(output, "jobs\n+-- build").unwrap();
+{
+    let mut body = IndentWriter::new(&mut output, &format, &[false], false);
+    write!(body, "compiling...\nfinished\n").unwrap();
+}
+writeln!(output, "'-- test").unwrap();
+
+assert_eq!(
+    String::from_utf8(output).unwrap(),
+    "jobs\n+-- build\n|   compiling...\n|   finished\n'-- test\n"
+);
+```
+*/
+
+use crate::{CompiledFormat, TreeFormatting};
+use std::io::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Wraps an implementation of `std::io::Write`, writing the tree lines for a position in a tree
+/// at the start of each line written to it. Empty lines are written without trailing spaces.
+///
+/// _Note_: the `max_line_width` and `line_transforms` formatting options are not applied to the
+/// lines written.
+///
+#[derive(Debug)]
+pub struct IndentWriter<W>
+where
+    W: Write,
+{
+    writer: W,
+    indent: String,
+    at_line_start: bool,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<W> IndentWriter<W>
+where
+    W: Write,
+{
+    ///
+    /// Construct a new writer for text below the label of the node at `position`, which does,
+    /// or does not, have children written below the text, with the provided format settings.
+    ///
+    pub fn new(writer: W, format: &TreeFormatting, position: &[bool], has_children: bool) -> Self {
+        Self {
+            writer,
+            indent: CompiledFormat::new(format).indent(position, has_children),
+            at_line_start: true,
+        }
+    }
+
+    /// Return the tree lines written at the start of each line.
+    pub fn indent(&self) -> &str {
+        &self.indent
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> Write for IndentWriter<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        for line in buf.split_inclusive(|b| *b == b'\n') {
+            if self.at_line_start {
+                if line == b"\n" {
+                    self.writer.write_all(self.indent.trim_end().as_bytes())?;
+                } else {
+                    self.writer.write_all(self.indent.as_bytes())?;
+                }
+            }
+            self.writer.write_all(line)?;
+            self.at_line_start = line.ends_with(b"\n");
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()
+    }
+}
@@ -0,0 +1,91 @@
+/*!
+Rendering of [serde_json](https://docs.rs/serde_json) values as trees, available with the
+`serde_json` feature.
+
+# Example
+
+```rust
+use serde_json::json;
+use text_trees::{json_tree, ValueTreeOptions};
+
+let value = json!({ "name": "text_trees", "keywords": ["tree", "text"] });
+let tree = json_tree(&value, &ValueTreeOptions::default());
+
+assert_eq!(
+    tree.to_string(),
+    r#"$
++-- keywords
+|   +-- [0]: "tree"
+|   '-- [1]: "text"
+'-- name: "text_trees"
+"#
+);
+```
+*/
+
+use crate::value_tree::{string_tree, write_value_tree, TreeValue, ValueShape};
+use crate::{StringTreeNode, TreeFormatting, ValueTreeOptions};
+use serde_json::Value;
+use std::io::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return a tree representing the provided JSON value, according to the provided options.
+///
+pub fn json_tree(value: &Value, options: &ValueTreeOptions) -> StringTreeNode {
+    string_tree(value, options)
+}
+
+///
+/// Write the tree representing the provided JSON value to the provided implementation of
+/// `std::io::Write` with the provided format settings and options.
+///
+pub fn write_json_value(
+    value: &Value,
+    to_writer: &mut impl Write,
+    format: &TreeFormatting,
+    options: &ValueTreeOptions,
+) -> Result<()> {
+    write_value_tree(value, to_writer, format, options)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl From<&Value> for StringTreeNode {
+    fn from(v: &Value) -> Self {
+        json_tree(v, &Default::default())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl TreeValue for Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    fn shape(&self) -> ValueShape<'_, Self> {
+        match self {
+            Value::Array(elements) => ValueShape::Sequence(elements.iter().collect()),
+            Value::Object(members) => ValueShape::Mapping(
+                members
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value))
+                    .collect(),
+            ),
+            scalar => ValueShape::Scalar(scalar.to_string()),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+/*!
+Separating the label written for a node from the data it carries, so that a tree may hold data
+that has no `Display` implementation, or whose `Display` implementation is not the label wanted.
+
+The data of each node is a [`Labeled`](struct.Labeled.html) value, holding the data and a
+function that computes its label; where the function is a function item, or a closure that
+captures nothing, it takes no space in the node.
+
+# Example
+
+```rust
+use text_trees::{Labeled, TreeNode};
+
+struct Employee {
+    name: &'static str,
+    title: &'static str,
+    salary: u32,
+}
+
+fn short_label(employee: &Employee) -> String {
+    format!("{} ({})", employee.name, employee.title)
+}
+
+let node = |name, title, salary| Labeled::new(Employee { name, title, salary }, short_label);
+let tree = TreeNode::with_children(
+    node("Ada", "CEO", 300),
+    vec![node("Grace", "CTO", 250), node("Alan", "CFO", 240)].into_iter(),
+);
+
+assert_eq!(tree.to_string(), "Ada (CEO)\n+-- Grace (CTO)\n'-- Alan (CFO)\n");
+assert_eq!(tree.children().map(|child| child.data().data().salary).sum::<u32>(), 490);
+```
+*/
+
+use std::fmt::{self, Debug, Display, Formatter};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Node data where the label is computed by the function `F`, rather than by a `Display`
+/// implementation of the data itself.
+///
+#[derive(Clone, Copy)]
+pub struct Labeled<T, F>
+where
+    F: Fn(&T) -> String,
+{
+    data: T,
+    label: F,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T, F> Debug for Labeled<T, F>
+where
+    T: Debug,
+    F: Fn(&T) -> String,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Labeled")
+            .field("data", &self.data)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, F> Display for Labeled<T, F>
+where
+    F: Fn(&T) -> String,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", (self.label)(&self.data))
+    }
+}
+
+impl<T, F> Labeled<T, F>
+where
+    F: Fn(&T) -> String,
+{
+    /// Construct a new value where the label of `data` is computed by `label`.
+    pub fn new(data: T, label: F) -> Self {
+        Self { data, label }
+    }
+
+    /// Return a reference to the data.
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    /// Return a mutable reference to the data; the label is computed from the data each time
+    /// it is written, and so reflects any change.
+    pub fn data_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+
+    /// Return the data, discarding the label function.
+    pub fn into_data(self) -> T {
+        self.data
+    }
+}
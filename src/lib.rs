@@ -221,6 +221,70 @@ the label spacing is shown as ".".
 >> │#######################└──────..Great Great Grand Child 2
 >> └─────┬..Aunt
 >> ######└──────..Child 3
+```
+
+Finally, the [`Classic`](enum.TreeOrientation.html#variant.Classic) orientation centers each
+node's label above those of its children, connected by diagonal lines; the
+[`OrgChart`](enum.TreeOrientation.html#variant.OrgChart) orientation uses the same layout but
+connects each parent to its children with a horizontal rail.
+
+| Option          | Setting   |
+|-----------------|-----------|
+| Character Set   | [`ASCII`](struct.FormatCharacters.html#method.ascii)     |
+| Orientation     | [`Classic`](enum.TreeOrientation.html#variant.Classic) |
+| Anchor Position | N/A       |
+| Prefix String   | `None`    |
+
+```text
+                         root
+              /          /              \
+Uncle               Parent                        Aunt
+                  /          \                      |
+          Child 1              Child 2           Child 3
+             |                    |
+       Grand Child 1        Grand Child 2
+                                  |
+                         Great Grand Child 2
+                                  |
+                      Great Great Grand Child 2
+```
+
+# Features
+
+The following optional features are provided.
+
+* `cargo-metadata` — adds the [`cargo_tree`](fn.cargo_tree.html) function which constructs a
+  dependency tree from `cargo metadata` output.
+* `cli` — builds the `ttree` command which reads indented text, or JSON, from standard input
+  and writes it as a tree.
+* `derive` — adds the [`DisplayTree`](derive.DisplayTree.html) derive macro which implements
+  [`ToTreeNode`](trait.ToTreeNode.html) for structs and enums.
+* `fs` — adds the [`fs`](fs/index.html) module which constructs trees from the file system.
+* `globset` — adds the [`GlobFilter`](struct.GlobFilter.html) type which matches node labels
+  against glob patterns.
+* `icu` — adds the [`locale_order`](fn.locale_order.html) and
+  [`locale_case_insensitive_order`](fn.locale_case_insensitive_order.html) comparators which sort
+  labels by the collation rules of a locale.
+* `proptest` — adds the [`tree_strategy`](fn.tree_strategy.html) and
+  [`string_tree_strategy`](fn.string_tree_strategy.html) functions which generate random trees
+  for property tests.
+* `ratatui` — adds the [`to_text`](struct.TreeNode.html#method.to_text) methods that render a tree
+  into styled [ratatui](https://ratatui.rs) text.
+* `rayon` — adds the [`write_with_format_parallel`](struct.TreeNode.html#method.write_with_format_parallel)
+  methods which write the subtrees of the root in parallel, and the
+  [`build_parallel`](struct.TreeNode.html#method.build_parallel) constructor.
+* `roxmltree` — adds the [`xml_tree`](fn.xml_tree.html) and
+  [`write_xml_document`](fn.write_xml_document.html) functions that render the element hierarchy
+  of XML documents as trees.
+* `serde_json` — adds the [`json_tree`](fn.json_tree.html) and
+  [`write_json_value`](fn.write_json_value.html) functions that render JSON values as trees.
+* `serde_yaml` — adds the [`from_yaml_str`](fn.from_yaml_str.html),
+  [`yaml_tree`](fn.yaml_tree.html), and [`write_yaml_value`](fn.write_yaml_value.html)
+  functions that render YAML values as trees.
+* `toml` — adds the [`toml_tree`](fn.toml_tree.html) and
+  [`write_toml_value`](fn.write_toml_value.html) functions that render TOML values as trees.
+* `tui` — adds the [`view_interactive`](fn.view_interactive.html) function, an interactive
+  terminal viewer supporting expand/collapse, search, and scrolling.
 
 */
 
@@ -244,9 +308,166 @@ the label spacing is shown as ".".
     unused_results,
 )]
 
-use std::fmt::{Display, Formatter};
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::fs::File;
 use std::io::Result;
-use std::io::Write;
+use std::io::{BufWriter, Write};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use std::{fmt, io};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+// ------------------------------------------------------------------------------------------------
+// Modules
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "cargo-metadata")]
+mod cargo_tree;
+#[cfg(feature = "cargo-metadata")]
+pub use cargo_tree::{cargo_tree, cargo_tree_for, CargoTreeOptions};
+
+mod accessible;
+pub use accessible::AccessibleOptions;
+
+mod body;
+
+mod cached;
+pub use cached::CachedLabel;
+
+mod classic;
+
+mod export;
+pub use export::CsvOptions;
+
+mod group;
+pub use group::GroupOptions;
+
+mod hook;
+pub use hook::RenderHook;
+
+mod indent_writer;
+pub use indent_writer::IndentWriter;
+
+mod labeled;
+pub use labeled::Labeled;
+
+mod line_diff;
+pub use line_diff::LineDiff;
+
+mod line_sink;
+pub use line_sink::LineSink;
+
+mod metrics;
+pub use metrics::{RenderMetrics, RenderStats};
+
+mod newick;
+pub use newick::{from_newick, NewickLabel};
+
+mod paths;
+
+mod persistent;
+pub use persistent::PersistentTree;
+
+pub mod progress;
+
+pub mod roundtrip;
+
+mod sample;
+
+mod search;
+pub use search::Visit;
+
+mod selector;
+pub use selector::Selector;
+
+mod slot_tree;
+pub use slot_tree::{SlotId, SlotTree};
+
+mod sort;
+pub use sort::{by_value_descending, case_insensitive_order, natural_order};
+
+mod stats;
+pub use stats::TreeStats;
+
+mod table;
+pub use table::{TableColumn, TreeTable};
+
+pub mod testing;
+
+mod to_tree;
+pub use to_tree::ToTreeNode;
+
+mod totals;
+pub use totals::{BarOptions, BarScale, ColorScale, ColorTarget, NodeValue, TotalsOptions};
+
+mod tree_write;
+pub use tree_write::{FmtWrite, IoWrite, TreeWrite};
+
+mod writer;
+pub use writer::TreeWriter;
+
+#[cfg(feature = "derive")]
+pub use text_trees_derive::DisplayTree;
+
+#[cfg(feature = "fs")]
+pub mod fs;
+
+#[cfg(feature = "globset")]
+mod glob_filter;
+#[cfg(feature = "globset")]
+pub use glob_filter::GlobFilter;
+
+#[cfg(feature = "icu")]
+mod collation;
+#[cfg(feature = "icu")]
+pub use collation::{locale_case_insensitive_order, locale_order};
+
+#[cfg(feature = "proptest")]
+mod strategy;
+#[cfg(feature = "proptest")]
+pub use strategy::{string_tree_strategy, tree_strategy, TreeStrategyOptions};
+
+#[cfg(feature = "ratatui")]
+mod ratatui_text;
+#[cfg(feature = "ratatui")]
+pub use ratatui_text::TextStyles;
+
+#[cfg(any(feature = "serde_json", feature = "serde_yaml", feature = "toml"))]
+mod value_tree;
+#[cfg(any(feature = "serde_json", feature = "serde_yaml", feature = "toml"))]
+pub use value_tree::ValueTreeOptions;
+
+#[cfg(feature = "tui")]
+mod tui_viewer;
+#[cfg(feature = "tui")]
+pub use tui_viewer::{view_interactive, view_interactive_with_format};
+
+#[cfg(feature = "rayon")]
+mod parallel;
+
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(feature = "serde_json")]
+pub use json::{json_tree, write_json_value};
+
+#[cfg(feature = "serde_yaml")]
+mod yaml_value;
+#[cfg(feature = "serde_yaml")]
+pub use yaml_value::{from_yaml_str, write_yaml_value, yaml_tree};
+
+#[cfg(feature = "toml")]
+mod toml_value;
+#[cfg(feature = "toml")]
+pub use toml_value::{toml_tree, write_toml_value};
+
+#[cfg(feature = "roxmltree")]
+mod xml;
+#[cfg(feature = "roxmltree")]
+pub use xml::{write_xml_document, xml_tree, XmlLabel};
 
 // ------------------------------------------------------------------------------------------------
 // Public Types
@@ -278,6 +499,41 @@ pub enum TreeOrientation {
     /// ```
     ///
     TopDown,
+    /// This writes a tree with each node's label centered above the labels of its children,
+    /// each connected by diagonal lines. This is the layout commonly used in textbook diagrams.
+    ///
+    /// Note that the whole tree is laid out in memory before any output is written, and that
+    /// the `anchor` and `align_depths` formatting options do not apply to this orientation.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    ///       root
+    ///      /     \
+    /// Uncle      Parent
+    ///             /    \
+    ///        Child 1  Child 2
+    /// ```
+    ///
+    Classic,
+    /// This writes a tree as an organizational chart, where each node's label is centered above
+    /// a horizontal rail that spans its children, which hang below the rail in columns.
+    ///
+    /// Note that, as for `Classic`, the whole tree is laid out in memory before any output is
+    /// written, and that the `anchor` and `align_depths` formatting options do not apply to this
+    /// orientation.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    ///       root
+    ///   ┌─────┴─────┐
+    /// Uncle       Parent
+    ///          ┌────┴────┐
+    ///       Child 1   Child 2
+    /// ```
+    ///
+    OrgChart,
 }
 
 ///
@@ -304,6 +560,198 @@ pub enum AnchorPosition {
     Left,
 }
 
+///
+/// Denotes the position of a node's badge relative to its label. In either case the badges are
+/// written into a fixed-width slot so that they align vertically across the whole tree.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum BadgePosition {
+    /// The badge slot is written between the tree lines and the label.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// [ok] parent_node
+    /// '-- [!]  child_node
+    /// ```
+    BeforeLabel,
+    /// The badge slot is written after the label, all badges start in the same column.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// parent_node    [ok]
+    /// '-- child_node [!]
+    /// ```
+    #[default]
+    AfterLabel,
+}
+
+///
+/// The alignment, and width limits, of a column written after the tree: a column of a
+/// [`TreeTable`](struct.TreeTable.html), a field aligned by the
+/// [`elastic_tabs`](struct.TreeFormatting.html#structfield.elastic_tabs) formatting option, a
+/// column written by [`write_with_totals`](struct.TreeNode.html#method.write_with_totals), or
+/// the badges written after the label by
+/// [`write_with_badges`](struct.TreeNode.html#method.write_with_badges).
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ColumnFormat {
+    /// The alignment of text within the column, default is `ColumnAlignment::Left`.
+    pub alignment: ColumnAlignment,
+    /// If set, the column is at least this number of display columns wide.
+    pub min_width: Option<usize>,
+    /// If set, text wider than this number of display columns is clipped, ending with an
+    /// ellipsis, `'…'`, within the width.
+    pub max_width: Option<usize>,
+}
+
+///
+/// Denotes the alignment of text within a column; numeric columns are usually right aligned.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColumnAlignment {
+    /// Text starts at the left of the column.
+    #[default]
+    Left,
+    /// Text ends at the right of the column.
+    Right,
+    /// Text is centered within the column, any odd space is written to its right.
+    Center,
+}
+
+///
+/// Denotes how often output written to an implementation of `std::io::Write` is flushed. Output
+/// is buffered, so without intermediate flushes a long-running render over a socket or pipe
+/// shows nothing until the entire tree has been written.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FlushPolicy {
+    /// The writer is flushed once, when the entire tree has been written.
+    #[default]
+    OnFinish,
+    /// The writer is flushed after each run of this number of lines.
+    EveryLines(usize),
+    /// The writer is flushed once at least this number of bytes have been written since it was
+    /// last flushed.
+    EveryBytes(usize),
+}
+
+///
+/// Denotes the style used to quote a label that contains whitespace, quotes, or one of the
+/// delimiter characters in [`LabelQuoting`](struct.LabelQuoting.html).
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum QuoteStyle {
+    /// The label is wrapped in double quotes, any `'"'` and `'\\'` characters are escaped with a
+    /// backslash.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// "My Documents"
+    /// ```
+    Double,
+    /// The label is wrapped in single quotes, any `'\''` and `'\\'` characters are escaped with a
+    /// backslash.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// 'My Documents'
+    /// ```
+    Single,
+    /// The label is not wrapped, each whitespace, quote, or delimiter character is escaped with a
+    /// backslash.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// My\ Documents
+    /// ```
+    Backslash,
+}
+
+///
+/// Determines whether, and how, node labels are quoted when the tree is output. This is useful
+/// where the output is to be read by scripts, or other tools, that would otherwise be unable to
+/// tell where a label starts and ends.
+///
+/// Control characters in quoted labels are always escaped, as `\n`, `\t`, etc.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabelQuoting {
+    /// The style of quoting to use.
+    pub style: QuoteStyle,
+    /// Any characters, in addition to whitespace, quotes, and backslash, that require a label
+    /// to be quoted; for example a path separator.
+    pub delimiters: Vec<char>,
+}
+
+///
+/// Denotes how the labels of deleted nodes are written, see
+/// [`write_with_deleted`](struct.TreeNode.html#method.write_with_deleted).
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeletedStyle {
+    /// Each character of the label is followed by the Unicode combining long stroke overlay
+    /// character (`U+0336`), which most terminals display as struck-through text.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// '-- c̶h̶i̶l̶d̶_̶n̶o̶d̶e̶
+    /// ```
+    Strikethrough,
+    /// The label is wrapped in the provided start and end markers.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// '-- ~~child_node~~
+    /// ```
+    Markers(String, String),
+}
+
+///
+/// Denotes whether a node is written, see
+/// [`set_visibility`](struct.TreeNode.html#method.set_visibility). The visibility of the root
+/// node is ignored, the root is always written.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Visibility {
+    /// The node, and its children, are written; this is the default.
+    #[default]
+    Visible,
+    /// Neither the node, nor any of its descendants, are written.
+    Hidden,
+    /// The node is not written, and its children are written in its place as though they were
+    /// children of its parent.
+    Flattened,
+}
+
+///
+/// The characters used, in place of `right_facing_tee` and `right_facing_angle` in
+/// [`FormatCharacters`](struct.FormatCharacters.html), to connect children that themselves have
+/// children when lines are anchored below labels; see
+/// [`branch_connectors`](struct.TreeFormatting.html#structfield.branch_connectors).
+///
+/// # Example
+///
+/// ```text
+/// parent_node
+/// ┝── child_node
+/// │   └── grandchild_node
+/// └── child_node
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct BranchConnectors {
+    /// This character is used to connect non-terminal child nodes that have children.
+    pub tee: char,
+    /// This character is used to connect terminal child nodes that have children.
+    pub angle: char,
+}
+
 ///
 /// This structure collects together all the formatting options that control how the tree is
 /// output.
@@ -320,8 +768,173 @@ pub struct TreeFormatting {
     pub anchor: AnchorPosition,
     /// The set of characters to use when line formatting.
     pub chars: FormatCharacters,
+    /// If set, the width of the tree lines written for each level of ancestors, the vertical
+    /// line and the spaces that follow it, so that the indent of each level is independent of
+    /// the length of connectors, `chars.horizontal_line_count`, and of the gap between
+    /// connectors and labels, `chars.label_space_count`. By default the width is one more than
+    /// the length of connectors and, where lines are anchored below labels and there is a gap,
+    /// one more again. A width of `0` is written as `1`.
+    pub continuation_indent: Option<usize>,
+    /// If set, and lines are anchored below labels, the number of columns from the first
+    /// character of a node's label to the vertical line connecting its children, where `0`
+    /// places the line below the first character; this takes precedence over
+    /// `continuation_indent`.
+    ///
+    /// # Example
+    ///
+    /// With an offset of `2`.
+    ///
+    /// ```text
+    /// parent_node
+    ///   '-- child_node
+    ///         '-- grandchild_node
+    /// ```
+    pub label_guide_offset: Option<usize>,
+    /// The position of any badges, relative to the node label.
+    pub badge_position: BadgePosition,
+    /// If `true` a legend is written after the tree, and an empty line, listing each badge that
+    /// was written, in the order first written, with its
+    /// [`description`](trait.Badge.html#method.description); badges without a description are
+    /// not listed.
+    pub badge_legend: bool,
+    /// If set, labels that contain whitespace, quotes, or delimiter characters are quoted.
+    pub label_quoting: Option<LabelQuoting>,
+    /// If `true`, connecting lines are extended so that all labels at the same depth start in
+    /// the same display column. This is only necessary where the tree characters, or labels, have
+    /// differing display widths; note that it requires the entire tree to be laid out in memory
+    /// before any output is written.
+    pub align_depths: bool,
+    /// The style used for the labels of deleted nodes.
+    pub deleted_style: DeletedStyle,
+    /// The string written between a label and its annotation, default is a single space.
+    pub annotation_separator: String,
+    /// If `true` annotations are written as numbered footnote markers, such as `[1]`, after the
+    /// label and their text is listed, by number, after the tree; annotations with the same text
+    /// share a marker. Markers are numbered in reading order only in the `TopDown` orientation,
+    /// so the `Classic` and `OrgChart` orientations write annotations after labels instead.
+    pub annotation_footnotes: bool,
+    /// If set, this transform is applied to every label, after it is generated by the data's
+    /// `Display` implementation and before any quoting.
+    pub label_transform: Option<LabelTransform>,
+    /// If set, and lines are anchored below labels, a line containing only this character is
+    /// written between the root's label and its first child, connecting the root to the lines
+    /// of its children; typically this is the same as `chars.vertical_line`.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// root
+    /// |
+    /// +-- child_node
+    /// '-- child_node
+    /// ```
+    pub root_connector: Option<char>,
+    /// If set, and lines are anchored below labels, children that themselves have children are
+    /// connected with these characters, so that the lines which expand are distinct from those
+    /// of leaf nodes. When lines are anchored to the left of labels this is already shown by the
+    /// `down_facing_tee` character.
+    pub branch_connectors: Option<BranchConnectors>,
+    /// If set, every line written is clipped to this number of display columns, including the
+    /// prefix string and the tree lines, and a clipped line ends with an ellipsis, `'…'`, within
+    /// the width. This keeps the tree lines intact when output is shown in a fixed-width pane
+    /// that would otherwise wrap long lines.
+    pub max_line_width: Option<usize>,
+    /// If `true`, each run of consecutive sibling leaf nodes with identical labels is written as
+    /// a single line with the number of nodes in the run, for example `retry (×12)`. This is
+    /// ignored by the `Classic` and `OrgChart` orientations, and by
+    /// [`TreeWriter`](struct.TreeWriter.html).
+    pub collapse_repeated_leaves: bool,
+    /// If set, tab characters in the output are replaced so that the text following the n-th
+    /// tab on every line starts in the same column, this many columns after the widest text
+    /// preceding it; so that annotations with several tab-separated fields are aligned as
+    /// columns, _elastic tabstops_. The entire tree is laid out before any output is written,
+    /// and this is ignored by [`TreeWriter`](struct.TreeWriter.html).
+    pub elastic_tabs: Option<usize>,
+    /// The format of each column written after the tree, in order; columns without a format
+    /// have no limit on their width and are left aligned, except for totals which are right
+    /// aligned.
+    pub column_formats: Vec<ColumnFormat>,
+    /// The maximum display width of labels at each depth, the root has depth `0`, where the
+    /// last width applies to all deeper levels; for example `vec![60, 40, 20]` allows long
+    /// labels near the root while keeping deep levels narrow. Wider labels are clipped, ending
+    /// with an ellipsis, `'…'`, within the width. If empty, the default, labels are not clipped.
+    pub max_label_widths: Vec<usize>,
+    /// Transforms applied, in order, to each line written, after any clipping and alignment;
+    /// for example to highlight the syntax of labels holding code, or to add terminal
+    /// hyperlinks. Each transform is given the line without its line ending.
+    pub line_transforms: Vec<LineTransform>,
+    /// If set, a tree with more than this number of nodes written is not written, and an error
+    /// of kind `InvalidInput` is returned instead; a guard against producing very large output
+    /// from untrusted input.
+    pub max_nodes: Option<usize>,
+    /// If set, a tree with nodes written deeper than this depth, where the root has depth `0`,
+    /// is not written, and an error of kind `InvalidInput` is returned instead.
+    pub max_depth: Option<usize>,
+    /// How often output written to an implementation of `std::io::Write` is flushed, default is
+    /// `FlushPolicy::OnFinish`.
+    pub flush_policy: FlushPolicy,
 }
 
+///
+/// A function applied to each node label before it is written, for example to truncate, redact,
+/// or change the case of labels. This keeps presentation concerns out of the data type's
+/// `Display` implementation.
+///
+/// # Example
+///
+/// ```rust
+/// use text_trees::{LabelTransform, StringTreeNode, TreeFormatting};
+///
+/// let tree = StringTreeNode::with_children(
+///     "root".to_string(),
+///     vec!["child".to_string()].into_iter(),
+/// );
+/// let format = TreeFormatting {
+///     label_transform: Some(LabelTransform::new(|label| label.to_uppercase())),
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     tree.to_string_with_format(&format).unwrap(),
+///     "ROOT\n'-- CHILD\n"
+/// );
+/// ```
+///
+#[derive(Clone)]
+pub struct LabelTransform(Arc<dyn Fn(String) -> String + Send + Sync>);
+
+///
+/// A function applied to each line of output, in the order given by the
+/// [`line_transforms`](struct.TreeFormatting.html#structfield.line_transforms) formatting option.
+/// Unlike a [`RenderHook`](trait.RenderHook.html) a transform sees only the final text of
+/// each line.
+///
+/// # Example
+///
+/// ```rust
+/// use text_trees::{LineTransform, StringTreeNode, TreeFormatting};
+///
+/// let tree = StringTreeNode::with_children(
+///     "root".to_string(),
+///     vec!["child".to_string()].into_iter(),
+/// );
+/// let format = TreeFormatting {
+///     line_transforms: vec![
+///         LineTransform::new(|line| line.replace("'--", "`--")),
+///         LineTransform::new(|line| format!("> {}", line)),
+///     ],
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(
+///     tree.to_string_with_format(&format).unwrap(),
+///     "> root\n> `-- child\n"
+/// );
+/// ```
+///
+#[derive(Clone)]
+pub struct LineTransform(Arc<dyn Fn(String) -> String + Send + Sync>);
+
 ///
 /// Contains the set of characters, and counts, to use when line formatting.
 ///
@@ -339,6 +952,31 @@ pub struct FormatCharacters {
     /// ASCII value `'|'`, box character value `'│'`.
     pub vertical_line: char,
 
+    /// This character is used to connect a parent to a child below and to its left, in the
+    /// classic orientation.
+    /// ASCII value `'/'`, box character value `'╱'`.
+    pub down_left_diagonal: char,
+
+    /// This character is used to connect a parent to a child below and to its right, in the
+    /// classic orientation.
+    /// ASCII value `'\\'`, box character value `'╲'`.
+    pub down_right_diagonal: char,
+
+    /// This character is used at the right-hand end of the rail connecting a parent to its
+    /// children, in the org chart orientation.
+    /// ASCII value `'+'`, box character value `'┐'`.
+    pub down_left_angle: char,
+
+    /// This character is used where a parent meets the rail connecting it to its children, in
+    /// the org chart orientation.
+    /// ASCII value `'+'`, box character value `'┴'`.
+    pub up_facing_tee: char,
+
+    /// This character is used where a parent meets the rail directly above one of its children,
+    /// in the org chart orientation.
+    /// ASCII value `'+'`, box character value `'┼'`.
+    pub cross: char,
+
     /// This character is used as the horizontal connector to node labels.
     /// ASCII value `'-'`, box character value `'─'`.
     pub horizontal_line: char,
@@ -375,22 +1013,117 @@ pub struct FormatCharacters {
 /// Note that `From<T>` is implemented allowing a nice short-cut for node creation, and `From<&T>`
 /// is also implemented for types that also implement `Clone`.
 ///
-#[derive(Clone, Debug)]
+/// The children of a node are stored in a `Vec` which is only allocated when the first child is
+/// added, so leaf nodes require no allocation beyond the node itself. Where the number of
+/// children is known, constructing a node with
+/// [`with_child_nodes`](struct.TreeNode.html#method.with_child_nodes) allocates it once. There is
+/// no `smallvec` feature to store children inline, as a node cannot contain nodes of its own
+/// type without boxing each of them, which would cost an allocation per child.
+///
+/// A tree is `Send` and `Sync` when its data is, as are the format settings, so that a tree
+/// built once may be shared, as an `Arc<TreeNode<T>>` whose clones share the one tree, and
+/// written concurrently with different formats on multiple threads. Writing a tree never
+/// changes it.
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use std::thread;
+/// use text_trees::{FormatCharacters, StringTreeNode, TreeFormatting};
+///
+/// let tree = Arc::new(StringTreeNode::with_children(
+///     "root".to_string(),
+///     vec!["a".to_string()].into_iter(),
+/// ));
+///
+/// let handles: Vec<thread::JoinHandle<String>> =
+///     vec![FormatCharacters::ascii(), FormatCharacters::box_chars()]
+///         .into_iter()
+///         .map(|chars| {
+///             let tree = Arc::clone(&tree);
+///             thread::spawn(move || {
+///                 tree.to_string_with_format(&TreeFormatting::dir_tree(chars)).unwrap()
+///             })
+///         })
+///         .collect();
+/// let results: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+///
+/// assert_eq!(results, vec!["root\n'-- a\n", "root\n└── a\n"]);
+/// ```
+///
+#[derive(Clone)]
 pub struct TreeNode<T>
 where
     T: Display,
 {
     data: T,
-    children: Vec<TreeNode<T>>,
+    children: Children<T>,
+    collapsed: bool,
+    visibility: Visibility,
 }
 
 ///
 /// A common type where the only data is the node's label as a `String`.
 ///
-/// Note that `From<&str> is implemented for `TreeNode<String>`.
+/// Note that `From<&str>` is implemented for `TreeNode<String>`.
 ///
 pub type StringTreeNode = TreeNode<String>;
 
+///
+/// A common type where the only data is the node's label as a borrowed `&str`, allowing a tree
+/// to borrow its labels from an existing buffer, or arena, rather than allocating a `String` for
+/// each node.
+///
+/// Note that `From<&str>` is implemented for `TreeNode<&str>`, without allocation.
+///
+/// ```rust
+/// use text_trees::StrTreeNode;
+///
+/// let source = String::from("root a b");
+/// let mut words = source.split(' ');
+/// let tree = StrTreeNode::with_children(words.next().unwrap(), words);
+///
+/// assert_eq!(tree.to_string(), "root\n+-- a\n'-- b\n");
+/// ```
+///
+pub type StrTreeNode<'a> = TreeNode<&'a str>;
+
+///
+/// A wrapper, returned by [`display_with`](struct.TreeNode.html#method.display_with), whose
+/// `Display` implementation writes the tree with the provided format settings.
+///
+pub struct TreeDisplay<'a, T>
+where
+    T: Display,
+{
+    node: &'a TreeNode<T>,
+    format: &'a TreeFormatting,
+}
+
+///
+/// Provides a short status token, a _badge_, for a node's data, for example `"[ok]"`, `"[!]"`,
+/// `"✔"`, or `"✖"`. Badges are written by the
+/// [`write_with_badges`](struct.TreeNode.html#method.write_with_badges) method in a slot whose
+/// width is that of the widest badge in the tree, positioned according to the
+/// [`badge_position`](struct.TreeFormatting.html#structfield.badge_position) formatting option.
+///
+/// This trait is implemented for any closure of the form `Fn(&T) -> Option<String>`, such
+/// closures have no badge descriptions.
+///
+pub trait Badge<T> {
+    /// Return the badge for the provided node data, or `None` if the node has no badge.
+    fn badge(&self, data: &T) -> Option<String>;
+
+    ///
+    /// Return a description of the provided badge, written in the legend if the
+    /// [`badge_legend`](struct.TreeFormatting.html#structfield.badge_legend) formatting option is
+    /// set, or `None` if the badge has no description. The default returns `None`.
+    ///
+    fn description(&self, badge: &str) -> Option<String> {
+        let _ = badge;
+        None
+    }
+}
+
 // ------------------------------------------------------------------------------------------------
 // Implementations
 // ------------------------------------------------------------------------------------------------
@@ -409,6 +1142,27 @@ impl TreeFormatting {
             orientation: TreeOrientation::TopDown,
             anchor: AnchorPosition::Below,
             chars,
+            continuation_indent: None,
+            label_guide_offset: None,
+            badge_position: Default::default(),
+            badge_legend: false,
+            label_quoting: None,
+            align_depths: false,
+            deleted_style: Default::default(),
+            annotation_separator: " ".to_string(),
+            annotation_footnotes: false,
+            label_transform: None,
+            root_connector: None,
+            branch_connectors: None,
+            max_line_width: None,
+            collapse_repeated_leaves: false,
+            elastic_tabs: None,
+            column_formats: Vec::new(),
+            max_label_widths: Vec::new(),
+            line_transforms: Vec::new(),
+            max_nodes: None,
+            max_depth: None,
+            flush_policy: Default::default(),
         }
     }
 
@@ -417,9 +1171,7 @@ impl TreeFormatting {
     pub fn dir_tree_with_prefix(chars: FormatCharacters, prefix_str: String) -> Self {
         Self {
             prefix_str: Some(prefix_str),
-            orientation: TreeOrientation::TopDown,
-            anchor: AnchorPosition::Below,
-            chars,
+            ..Self::dir_tree(chars)
         }
     }
 
@@ -427,10 +1179,8 @@ impl TreeFormatting {
     /// the provided format characters.
     pub fn dir_tree_left(chars: FormatCharacters) -> Self {
         Self {
-            prefix_str: None,
-            orientation: TreeOrientation::TopDown,
             anchor: AnchorPosition::Left,
-            chars,
+            ..Self::dir_tree(chars)
         }
     }
 
@@ -440,61 +1190,181 @@ impl TreeFormatting {
     pub fn dir_tree_left_with_prefix(chars: FormatCharacters, prefix_str: String) -> Self {
         Self {
             prefix_str: Some(prefix_str),
-            orientation: TreeOrientation::TopDown,
-            anchor: AnchorPosition::Left,
-            chars,
+            ..Self::dir_tree_left(chars)
         }
     }
 
-    #[inline]
-    pub(crate) fn just_space(&self) -> String {
-        format!(
-            "{}{}",
-            self.chars.just_space(),
-            if self.anchor == AnchorPosition::Below && self.chars.label_space_count > 0 {
-                self.chars.horizontal_space.to_string()
-            } else {
-                String::new()
-            }
-        )
+    ///
+    /// Construct the options that reproduce the tree lines of the GNU `tree` command; see also
+    /// [`write_gnu_tree`](fs/fn.write_gnu_tree.html), with the `fs` feature, for the rest of its
+    /// output.
+    ///
+    pub fn gnu_tree() -> Self {
+        Self::dir_tree(FormatCharacters::box_chars())
     }
 
-    #[inline]
-    pub(crate) fn bar_and_space(&self) -> String {
-        format!(
-            "{}{}",
-            self.chars.bar_and_space(),
-            if self.anchor == AnchorPosition::Below && self.chars.label_space_count > 0 {
-                self.chars.horizontal_space.to_string()
-            } else {
-                String::new()
-            }
-        )
+    ///
+    /// Construct the options that reproduce the tree lines of the Windows `tree` command; for
+    /// the output of its `/A` option use `dir_tree(FormatCharacters::windows_ascii())`.
+    ///
+    pub fn windows_tree() -> Self {
+        Self::dir_tree(FormatCharacters::windows_box_chars())
     }
 
     #[inline]
-    pub(crate) fn tee(&self, has_children: bool) -> String {
-        format!(
-            "{}{}{}{}",
-            self.chars.right_facing_tee,
-            self.chars.horizontal_line(),
-            if self.anchor == AnchorPosition::Below {
-                String::new()
-            } else if has_children {
-                self.chars.down_facing_tee.to_string()
-            } else {
-                self.chars.horizontal_line.to_string()
-            },
-            self.chars.label_space()
+    pub(crate) fn format_label(&self, label: String) -> String {
+        let label = match &self.label_transform {
+            None => label,
+            Some(transform) => transform.apply(label),
+        };
+        match &self.label_quoting {
+            None => label,
+            Some(quoting) => quoting.quote(&label),
+        }
+    }
+
+    ///
+    /// Return the line as it is written, clipped to the maximum line width, if any, and with
+    /// each of the line transforms applied.
+    ///
+    pub(crate) fn output_line(&self, line: &str) -> String {
+        let line = match self.max_line_width {
+            Some(width) => clip_line(line, width),
+            None => line.to_string(),
+        };
+        self.line_transforms
+            .iter()
+            .fold(line, |line, transform| transform.apply(line))
+    }
+
+    ///
+    /// Return the label clipped to the maximum label width, if any, for nodes at `depth`.
+    ///
+    pub(crate) fn clip_label(&self, label: String, depth: usize) -> String {
+        match label_width_at(&self.max_label_widths, depth) {
+            Some(width) => clip_line(&label, width),
+            None => label,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn node_label<T>(&self, node: &TreeNode<T>) -> String
+    where
+        T: Display,
+    {
+        let label = self.format_label(node.label());
+        if node.collapsed && node.has_children() {
+            format!("{} [+{}]", label, node.descendant_count())
+        } else {
+            label
+        }
+    }
+
+    ///
+    /// Return the width of the tree lines for each level of ancestors, if it is not derived
+    /// from the format characters.
+    ///
+    fn continuation_width(&self) -> Option<usize> {
+        match (&self.anchor, self.label_guide_offset) {
+            (AnchorPosition::Below, Some(offset)) => {
+                Some(1 + self.chars.horizontal_line_count + self.chars.label_space_count + offset)
+            }
+            _ => self.continuation_indent,
+        }
+    }
+
+    ///
+    /// Return the spaces written, after any prefix, before the tree lines of every node other
+    /// than the root; so that the lines connecting the root's children are offset from the
+    /// root's label in the same way as those of other nodes.
+    ///
+    fn root_indent(&self) -> String {
+        match (&self.anchor, self.label_guide_offset) {
+            (AnchorPosition::Below, Some(offset)) => {
+                char_repeat(self.chars.horizontal_space, offset)
+            }
+            _ => String::new(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn just_space(&self) -> String {
+        if let Some(width) = self.continuation_width() {
+            return char_repeat(self.chars.horizontal_space, width.max(1));
+        }
+        format!(
+            "{}{}",
+            self.chars.just_space(),
+            if self.anchor == AnchorPosition::Below && self.chars.label_space_count > 0 {
+                self.chars.horizontal_space.to_string()
+            } else {
+                String::new()
+            }
+        )
+    }
+
+    #[inline]
+    pub(crate) fn bar_and_space(&self) -> String {
+        if let Some(width) = self.continuation_width() {
+            return format!(
+                "{}{}",
+                self.chars.vertical_line,
+                char_repeat(self.chars.horizontal_space, width.max(1) - 1)
+            );
+        }
+        format!(
+            "{}{}",
+            self.chars.bar_and_space(),
+            if self.anchor == AnchorPosition::Below && self.chars.label_space_count > 0 {
+                self.chars.horizontal_space.to_string()
+            } else {
+                String::new()
+            }
+        )
+    }
+
+    #[inline]
+    pub(crate) fn tee(&self) -> String {
+        format!(
+            "{}{}",
+            self.chars.right_facing_tee,
+            self.chars.horizontal_line(),
         )
     }
 
     #[inline]
-    pub(crate) fn angle(&self, has_children: bool) -> String {
+    pub(crate) fn angle(&self) -> String {
         format!(
-            "{}{}{}{}",
+            "{}{}",
             self.chars.right_facing_angle,
             self.chars.horizontal_line(),
+        )
+    }
+
+    #[inline]
+    pub(crate) fn branch_tee(&self) -> String {
+        match (&self.anchor, &self.branch_connectors) {
+            (AnchorPosition::Below, Some(branch)) => {
+                format!("{}{}", branch.tee, self.chars.horizontal_line())
+            }
+            _ => self.tee(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn branch_angle(&self) -> String {
+        match (&self.anchor, &self.branch_connectors) {
+            (AnchorPosition::Below, Some(branch)) => {
+                format!("{}{}", branch.angle, self.chars.horizontal_line())
+            }
+            _ => self.angle(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn connector_tail(&self, has_children: bool) -> String {
+        format!(
+            "{}{}",
             if self.anchor == AnchorPosition::Below {
                 String::new()
             } else if has_children {
@@ -509,6 +1379,167 @@ impl TreeFormatting {
 
 // ------------------------------------------------------------------------------------------------
 
+impl<T, F> Badge<T> for F
+where
+    F: Fn(&T) -> Option<String>,
+{
+    fn badge(&self, data: &T) -> Option<String> {
+        self(data)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl BranchConnectors {
+    /// Box characters with a heavy horizontal line, `'┝'` and `'┕'`, matching
+    /// [`FormatCharacters::box_chars`](struct.FormatCharacters.html#method.box_chars).
+    pub fn box_chars() -> Self {
+        Self {
+            tee: '┝',
+            angle: '┕',
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl LabelQuoting {
+    /// Quote labels with double quotes, with no additional delimiters.
+    pub fn double_quotes() -> Self {
+        Self::with_delimiters(QuoteStyle::Double, Default::default())
+    }
+
+    /// Quote labels with single quotes, with no additional delimiters.
+    pub fn single_quotes() -> Self {
+        Self::with_delimiters(QuoteStyle::Single, Default::default())
+    }
+
+    /// Escape special characters in labels with backslashes, with no additional delimiters.
+    pub fn backslash() -> Self {
+        Self::with_delimiters(QuoteStyle::Backslash, Default::default())
+    }
+
+    /// Quote labels with the provided style, and the provided additional delimiters.
+    pub fn with_delimiters(style: QuoteStyle, delimiters: Vec<char>) -> Self {
+        Self { style, delimiters }
+    }
+
+    /// Returns `true` if the provided label requires quoting. An empty label is always quoted,
+    /// except in the `Backslash` style, so that it remains visible.
+    pub fn needs_quoting(&self, label: &str) -> bool {
+        (label.is_empty() && self.style != QuoteStyle::Backslash)
+            || label.chars().any(|c| self.is_special(c))
+    }
+
+    /// Return the provided label quoted according to this style, if the label needs quoting,
+    /// else the label unchanged.
+    pub fn quote(&self, label: &str) -> String {
+        if !self.needs_quoting(label) {
+            return label.to_string();
+        }
+        let quote_char = match self.style {
+            QuoteStyle::Double => Some('"'),
+            QuoteStyle::Single => Some('\''),
+            QuoteStyle::Backslash => None,
+        };
+        let mut quoted = String::with_capacity(label.len() + 2);
+        quoted.extend(quote_char);
+        for c in label.chars() {
+            match c {
+                '\n' => quoted.push_str("\\n"),
+                '\r' => quoted.push_str("\\r"),
+                '\t' => quoted.push_str("\\t"),
+                '\\' => quoted.push_str("\\\\"),
+                c if quote_char == Some(c) || (quote_char.is_none() && self.is_special(c)) => {
+                    quoted.push('\\');
+                    quoted.push(c);
+                }
+                c if c.is_control() => quoted.push_str(&c.escape_default().to_string()),
+                c => quoted.push(c),
+            }
+        }
+        quoted.extend(quote_char);
+        quoted
+    }
+
+    #[inline]
+    fn is_special(&self, c: char) -> bool {
+        c.is_whitespace()
+            || c.is_control()
+            || c == '"'
+            || c == '\''
+            || c == '\\'
+            || self.delimiters.contains(&c)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Debug for LabelTransform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LabelTransform")
+            .field(&"Fn(String) -> String")
+            .finish()
+    }
+}
+
+impl LabelTransform {
+    /// Construct a new transform from the provided function.
+    pub fn new(transform: impl Fn(String) -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(transform))
+    }
+
+    /// Apply this transform to the provided label.
+    pub fn apply(&self, label: String) -> String {
+        (self.0)(label)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Debug for LineTransform {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LineTransform")
+            .field(&"Fn(String) -> String")
+            .finish()
+    }
+}
+
+impl LineTransform {
+    /// Construct a new transform from the provided function.
+    pub fn new(transform: impl Fn(String) -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(transform))
+    }
+
+    /// Apply this transform to the provided line, which has no line ending.
+    pub fn apply(&self, line: String) -> String {
+        (self.0)(line)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Default for DeletedStyle {
+    fn default() -> Self {
+        Self::Markers("~~".to_string(), "~~".to_string())
+    }
+}
+
+impl DeletedStyle {
+    /// Return the provided label styled as deleted.
+    pub fn apply(&self, label: &str) -> String {
+        match self {
+            Self::Strikethrough => label
+                .chars()
+                .flat_map(|c| std::iter::once(c).chain(std::iter::once(COMBINING_LONG_STROKE)))
+                .collect(),
+            Self::Markers(start, end) => format!("{}{}{}", start, label, end),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
 impl Default for FormatCharacters {
     fn default() -> Self {
         Self::ascii()
@@ -522,6 +1553,11 @@ impl FormatCharacters {
             down_facing_angle: '+',
             down_facing_tee: ',',
             vertical_line: '|',
+            down_left_diagonal: '/',
+            down_right_diagonal: '\\',
+            down_left_angle: '+',
+            up_facing_tee: '+',
+            cross: '+',
             horizontal_line: '-',
             horizontal_space: ' ',
             horizontal_line_count: 2,
@@ -538,6 +1574,11 @@ impl FormatCharacters {
             down_facing_angle: '┌',
             down_facing_tee: '┬',
             vertical_line: '│',
+            down_left_diagonal: '╱',
+            down_right_diagonal: '╲',
+            down_left_angle: '┐',
+            up_facing_tee: '┴',
+            cross: '┼',
             horizontal_line: '─',
             horizontal_space: ' ',
             horizontal_line_count: 2,
@@ -548,6 +1589,34 @@ impl FormatCharacters {
         }
     }
 
+    ///
+    /// The ASCII characters used by the Windows `tree` command with its `/A` option, as in
+    /// `+---child` and `\---last_child`.
+    ///
+    pub fn windows_ascii() -> Self {
+        Self {
+            horizontal_line_count: 3,
+            right_facing_angle: '\\',
+            label_space_count: 0,
+            ..Self::ascii()
+        }
+    }
+
+    ///
+    /// The line drawing characters used by the Windows `tree` command, as in `├───child` and
+    /// `└───last_child`; all of these characters, including those for the classic orientation,
+    /// are in code page 437 so that they may be written to a console using it.
+    ///
+    pub fn windows_box_chars() -> Self {
+        Self {
+            down_left_diagonal: '/',
+            down_right_diagonal: '\\',
+            horizontal_line_count: 3,
+            label_space_count: 0,
+            ..Self::box_chars()
+        }
+    }
+
     #[inline]
     pub(crate) fn just_space(&self) -> String {
         format!("{}{}", self.horizontal_space, self.horizontal_space(),)
@@ -580,12 +1649,63 @@ impl<T> Display for TreeNode<T>
 where
     T: Display,
 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.to_string_with_format(&Default::default()).unwrap()
-        )
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write_with_format_fmt(f, &Default::default())
+    }
+}
+
+impl<T> Debug for TreeNode<T>
+where
+    T: Display + Debug,
+{
+    ///
+    /// The alternate form, `{:#?}`, writes the structure of the tree as by
+    /// [`fmt_structure`](#method.fmt_structure); otherwise the node's fields are written.
+    ///
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            self.fmt_structure(f)
+        } else {
+            f.debug_struct("TreeNode")
+                .field("data", &self.data)
+                .field("children", &self.children)
+                .field("collapsed", &self.collapsed)
+                .field("visibility", &self.visibility)
+                .finish()
+        }
+    }
+}
+
+// Clone, Copy, and Debug are implemented by hand, as deriving them would require the same of `T`
+// although only references are held.
+impl<T> Clone for TreeDisplay<'_, T>
+where
+    T: Display,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TreeDisplay<'_, T> where T: Display {}
+
+impl<T> Debug for TreeDisplay<'_, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TreeDisplay")
+            .field("format", self.format)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Display for TreeDisplay<'_, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.node.write_with_format_fmt(f, self.format)
     }
 }
 
@@ -595,10 +1715,7 @@ where
 {
     /// Construct a new tree node with the provided data value.
     pub fn new(data: T) -> Self {
-        Self {
-            data,
-            children: Default::default(),
-        }
+        Self::with_child_nodes(data, std::iter::empty())
     }
 
     /// Construct a new tree node with the provided data value and an iterator that provides
@@ -619,6 +1736,42 @@ where
         Self {
             data,
             children: children.collect(),
+            collapsed: false,
+            visibility: Visibility::Visible,
+        }
+    }
+
+    /// Construct a new tree node with the provided data value, and with space reserved for at
+    /// least `capacity` child nodes.
+    pub fn with_capacity(data: T, capacity: usize) -> Self {
+        let mut node = Self::new(data);
+        node.children.reserve(capacity);
+        node
+    }
+
+    ///
+    /// Construct a new tree from a seed value, the provided function expands each seed into the
+    /// data value of its node and the seeds of the node's children, in order. The tree is built
+    /// without recursion, so that very deep trees do not overflow the stack.
+    ///
+    pub fn unfold<S>(seed: S, mut expand_fn: impl FnMut(S) -> (T, Vec<S>)) -> Self {
+        let (data, seeds) = expand_fn(seed);
+        let mut stack = vec![(Self::new(data), seeds.into_iter())];
+        loop {
+            let next = stack.last_mut().unwrap().1.next();
+            match next {
+                Some(seed) => {
+                    let (data, seeds) = expand_fn(seed);
+                    stack.push((Self::new(data), seeds.into_iter()));
+                }
+                None => {
+                    let (node, _) = stack.pop().unwrap();
+                    match stack.last_mut() {
+                        Some((parent, _)) => parent.children.push(node),
+                        None => return node,
+                    }
+                }
+            }
         }
     }
 
@@ -632,6 +1785,15 @@ where
         self.data.to_string()
     }
 
+    ///
+    /// Return a copy of this tree where the data of each node is its label, so that the
+    /// structure and text of the tree may be kept without its data. The collapsed state, and
+    /// visibility, of each node is retained. The tree is copied without recursion.
+    ///
+    pub fn to_string_tree(&self) -> StringTreeNode {
+        map_tree_ref(self, T::to_string)
+    }
+
     /// Returns `true` if this node has child nodes, else `false`.
     pub fn has_children(&self) -> bool {
         !self.children.is_empty()
@@ -642,12 +1804,68 @@ where
         self.children.iter()
     }
 
+    /// Return the child nodes as a slice, so that slice methods such as `binary_search` and
+    /// `chunks` may be used.
+    pub fn children_slice(&self) -> &[TreeNode<T>] {
+        &self.children
+    }
+
+    /// Return a mutable reference to the vector of child nodes, so that children may be
+    /// reordered, inserted, or removed with the standard vector and slice methods.
+    pub fn children_vec_mut(&mut self) -> &mut Vec<TreeNode<T>> {
+        &mut self.children.0
+    }
+
+    /// Returns the number of nodes below this one, that is its children, their children, and so
+    /// on.
+    pub fn descendant_count(&self) -> usize {
+        let mut count = 0;
+        let mut work: Vec<&TreeNode<T>> = self.children.iter().collect();
+        while let Some(node) = work.pop() {
+            count += 1;
+            work.extend(node.children.iter());
+        }
+        count
+    }
+
+    /// Returns `true` if this node is collapsed, else `false`. A collapsed node is written as a
+    /// single line, with a suffix denoting the number of hidden descendants, in place of its
+    /// children.
+    pub fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    /// Set whether this node is collapsed when written. Note that collapsing a node does not
+    /// affect the children themselves, or the result of comparing nodes for equality.
+    pub fn set_collapsed(&mut self, collapsed: bool) {
+        self.collapsed = collapsed
+    }
+
+    /// Return whether this node is written, see [`Visibility`](enum.Visibility.html).
+    pub fn visibility(&self) -> Visibility {
+        self.visibility
+    }
+
+    /// Set whether this node is written. Like collapsing, this does not affect the node's
+    /// children, or the result of comparing nodes for equality; so that interactive tools may
+    /// show and hide nodes without removing them from the tree.
+    pub fn set_visibility(&mut self, visibility: Visibility) {
+        self.visibility = visibility
+    }
+
+    /// Set whether this node, and its descendants, are hidden; a shorthand for
+    /// [`set_visibility`](struct.TreeNode.html#method.set_visibility).
+    pub fn set_hidden(&mut self, hidden: bool) {
+        self.visibility = if hidden {
+            Visibility::Hidden
+        } else {
+            Visibility::Visible
+        }
+    }
+
     /// Push a new data item into the list of children.
     pub fn push(&mut self, data: T) {
-        self.push_node(TreeNode {
-            data,
-            children: Default::default(),
-        })
+        self.push_node(TreeNode::new(data))
     }
 
     /// Push a new pre-constructed `TreeNode` into the list of children.
@@ -667,10 +1885,27 @@ where
     /// _Note_: in effect `Display::fmt` calls this method with default formatting.
     ///  
     pub fn to_string_with_format(&self, format: &TreeFormatting) -> Result<String> {
-        use std::io::Cursor;
-        let mut buffer = Cursor::new(Vec::new());
-        self.write_with_format(&mut buffer, format)?;
-        Ok(String::from_utf8(buffer.into_inner()).unwrap())
+        self.render_to_string(format, |mut w| {
+            write_tree_inner(self, &mut w, format, &|node| format.node_label(node))
+        })
+    }
+
+    ///
+    /// Return a wrapper that implements `Display` by writing this tree with the provided format
+    /// settings, so that the tree may be used directly in `format!` and `println!`.
+    ///
+    /// ```rust
+    /// use text_trees::{StringTreeNode, TreeFormatting};
+    ///
+    /// let tree =
+    ///     StringTreeNode::with_children("root".to_string(), vec!["a".to_string()].into_iter());
+    /// let format = TreeFormatting::dir_tree_with_prefix(Default::default(), "> ".to_string());
+    ///
+    /// assert_eq!(format!("{}", tree.display_with(&format)), "> root\n> '-- a\n");
+    /// ```
+    ///
+    pub fn display_with<'a>(&'a self, format: &'a TreeFormatting) -> TreeDisplay<'a, T> {
+        TreeDisplay { node: self, format }
     }
 
     /// Write this tree to the provided implementation of `std::io::Write` with default formatting.
@@ -686,6 +1921,10 @@ where
 
     /// Write this tree to the provided implementation of `std::io::Write` with the provided
     /// format settings.
+    ///
+    /// _Note_: output is buffered internally, as it is by all the methods that write to an
+    /// implementation of `std::io::Write`, so there is no need to wrap a `File`, or similar
+    /// writer, in a `BufWriter`.
     pub fn write_with_format(
         &self,
         to_writer: &mut impl Write,
@@ -694,52 +1933,966 @@ where
     where
         T: Display,
     {
-        write_tree_inner(self, to_writer, format, Default::default())
+        self.render_to(&mut IoWrite(to_writer), format, |mut w| {
+            write_tree_inner(self, &mut w, format, &|node| format.node_label(node))
+        })
     }
-}
 
-// ------------------------------------------------------------------------------------------------
+    ///
+    /// Write this tree to the file at `path`, which is created or truncated, with the provided
+    /// format settings.
+    ///
+    pub fn write_to_file(&self, path: impl AsRef<Path>, format: &TreeFormatting) -> Result<()> {
+        self.write_with_format(&mut File::create(path)?, format)
+    }
 
-impl<T> From<T> for TreeNode<T>
-where
-    T: Display,
-{
-    fn from(v: T) -> Self {
-        Self {
-            data: v,
-            children: Default::default(),
+    ///
+    /// Return an error of kind `InvalidInput` if this tree exceeds either of the
+    /// [`max_nodes`](struct.TreeFormatting.html#structfield.max_nodes) or
+    /// [`max_depth`](struct.TreeFormatting.html#structfield.max_depth) limits of the provided
+    /// format settings. Only the nodes that would be written are counted, and the tree is
+    /// walked no further than the limits, so this is cheap even for very large trees.
+    ///
+    /// This check is made, once, by every method that writes a tree with format settings; it is
+    /// not made by [`lines_with_format`](struct.TreeNode.html#method.lines_with_format), which
+    /// cannot return an error, and so should be made before that method is called.
+    ///
+    pub fn check_limits(&self, format: &TreeFormatting) -> Result<()> {
+        if format.max_nodes.is_none() && format.max_depth.is_none() {
+            return Ok(());
+        }
+        let mut count = 0;
+        let mut work: Vec<(&TreeNode<T>, usize)> = vec![(self, 0)];
+        while let Some((node, depth)) = work.pop() {
+            count += 1;
+            match (format.max_nodes, format.max_depth) {
+                (Some(max_nodes), _) if count > max_nodes => {
+                    return Err(limit_exceeded(format!(
+                        "the tree has more than the maximum of {} nodes",
+                        max_nodes
+                    )));
+                }
+                (_, Some(max_depth)) if depth > max_depth => {
+                    return Err(limit_exceeded(format!(
+                        "the tree is deeper than the maximum depth of {}",
+                        max_depth
+                    )));
+                }
+                _ => {}
+            }
+            work.extend(node.visible_children().map(|child| (child, depth + 1)));
         }
+        Ok(())
     }
-}
 
-impl<T> From<&T> for TreeNode<T>
-where
-    T: Display + Clone,
-{
+    ///
+    /// The single path by which a tree is written with format settings: the limits of the format
+    /// are checked, once, and the text produced by `render` is then written to the provided
+    /// destination, flushed according to the format's `flush_policy`.
+    ///
+    fn render_to(
+        &self,
+        to_writer: &mut impl TreeWrite,
+        format: &TreeFormatting,
+        render: impl FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+    ) -> Result<()> {
+        self.check_limits(format)?;
+        to_writer.write_text(format.flush_policy, render)
+    }
+
+    /// As [`render_to`](#method.render_to), returning the text written as a string.
+    fn render_to_string(
+        &self,
+        format: &TreeFormatting,
+        render: impl FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+    ) -> Result<String> {
+        let mut buffer = String::new();
+        self.render_to(&mut buffer, format, render)?;
+        Ok(buffer)
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::fmt::Write` with the provided
+    /// format settings. This allows a tree to be written into a `String`, or from within a
+    /// `Display` implementation, without the cost of UTF-8 validation.
+    ///
+    /// _Note_: if the tree exceeds the limits of the format nothing is written and, as
+    /// `std::fmt::Error` cannot carry a message, the reason is lost; to keep it write the tree
+    /// with [`write_to`](struct.TreeNode.html#method.write_to) and the
+    /// [`FmtWrite`](struct.FmtWrite.html) adapter, which returns the limit's message as an
+    /// `std::io::Error`.
+    ///
+    pub fn write_with_format_fmt(
+        &self,
+        to_writer: &mut impl fmt::Write,
+        format: &TreeFormatting,
+    ) -> fmt::Result {
+        self.write_to(&mut FmtWrite(to_writer), format)
+            .map_err(|_| fmt::Error)
+    }
+
+    ///
+    /// Return an iterator over the lines of this tree, without line endings, formatted according
+    /// to the provided format settings. Lines are produced on demand, so that output may be
+    /// streamed, windowed, or stopped early without writing the entire tree.
+    ///
+    /// _Note_: the `max_nodes` and `max_depth` limits are not checked, see
+    /// [`check_limits`](struct.TreeNode.html#method.check_limits).
+    ///
+    /// _Note_: the `align_depths` formatting option, and the `Classic` and `OrgChart` orientations,
+    /// all require the entire tree to be laid out before the first line can be produced.
+    ///
+    pub fn lines_with_format<'a>(
+        &'a self,
+        format: &'a TreeFormatting,
+    ) -> impl Iterator<Item = String> + 'a {
+        let (eager, lazy) = match format.orientation {
+            TreeOrientation::TopDown if format.align_depths || format.elastic_tabs.is_some() => {
+                let lines: Vec<String> = collect_rows(self, format)
+                    .into_iter()
+                    .flat_map(|(row, node)| row_lines(row, format.node_label(node)))
+                    .collect();
+                (Some(lines), None)
+            }
+            TreeOrientation::TopDown => (None, Some(Rows::new(self, format))),
+            TreeOrientation::Classic | TreeOrientation::OrgChart => {
+                let mut text = String::new();
+                let _ =
+                    classic::write_tree(self, &mut text, format, &|node| format.node_label(node));
+                (Some(text.lines().map(str::to_string).collect()), None)
+            }
+        };
+        let eager = eager.map(|mut lines| {
+            if let Some(gap) = format.elastic_tabs {
+                align_tabs(&mut lines, gap, &format.column_formats);
+            }
+            lines
+        });
+        eager
+            .into_iter()
+            .flatten()
+            .chain(
+                lazy.into_iter()
+                    .flatten()
+                    .flat_map(move |(row, node)| row_lines(row, format.node_label(node))),
+            )
+            .map(move |line| format.output_line(&line))
+    }
+
+    ///
+    /// Return a string containing the generated tree text formatted according to the provided
+    /// format settings, where the labels of nodes for which `is_deleted` returns `true` are
+    /// styled according to the [`deleted_style`](struct.TreeFormatting.html#structfield.deleted_style)
+    /// formatting option.
+    ///
+    pub fn to_string_with_deleted(
+        &self,
+        format: &TreeFormatting,
+        is_deleted: impl Fn(&T) -> bool,
+    ) -> Result<String> {
+        self.render_to_string(format, |mut w| {
+            write_tree_with_deleted(self, &mut w, format, is_deleted)
+        })
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` with the provided
+    /// format settings, where the labels of nodes for which `is_deleted` returns `true` are
+    /// styled according to the [`deleted_style`](struct.TreeFormatting.html#structfield.deleted_style)
+    /// formatting option.
+    ///
+    pub fn write_with_deleted(
+        &self,
+        to_writer: &mut impl Write,
+        format: &TreeFormatting,
+        is_deleted: impl Fn(&T) -> bool,
+    ) -> Result<()> {
+        self.render_to(&mut IoWrite(to_writer), format, |mut w| {
+            write_tree_with_deleted(self, &mut w, format, is_deleted)
+        })
+    }
+
+    ///
+    /// Return a string containing the generated tree text formatted according to the provided
+    /// format settings, where any annotation returned by `annotations` is appended to the
+    /// node's label.
+    ///
+    pub fn to_string_with_annotations(
+        &self,
+        format: &TreeFormatting,
+        annotations: impl Fn(&T) -> Option<String>,
+    ) -> Result<String> {
+        self.render_to_string(format, |mut w| {
+            write_tree_with_annotations(self, &mut w, format, annotations)
+        })
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` with the provided
+    /// format settings, where any annotation returned by `annotations` is appended to the
+    /// node's label, separated by the
+    /// [`annotation_separator`](struct.TreeFormatting.html#structfield.annotation_separator)
+    /// formatting option. This allows the output of values such as sizes, durations, or counts
+    /// without changing the `Display` implementation of the node data.
+    ///
+    /// With the
+    /// [`annotation_footnotes`](struct.TreeFormatting.html#structfield.annotation_footnotes)
+    /// formatting option, and the `TopDown` orientation, each annotation is replaced by a
+    /// footnote marker, and the footnotes are written after the tree and an empty line, one per
+    /// line, as in `[1] annotation`.
+    ///
+    pub fn write_with_annotations(
+        &self,
+        to_writer: &mut impl Write,
+        format: &TreeFormatting,
+        annotations: impl Fn(&T) -> Option<String>,
+    ) -> Result<()> {
+        self.render_to(&mut IoWrite(to_writer), format, |mut w| {
+            write_tree_with_annotations(self, &mut w, format, annotations)
+        })
+    }
+
+    ///
+    /// Return a string containing the generated tree text formatted according to the provided
+    /// format settings, with each node's badge written in an aligned slot.
+    ///
+    pub fn to_string_with_badges(
+        &self,
+        format: &TreeFormatting,
+        badges: &impl Badge<T>,
+    ) -> Result<String> {
+        self.render_to_string(format, |mut w| {
+            write_tree_with_badges(self, &mut w, format, badges)
+        })
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` with the provided
+    /// format settings, with each node's badge written in an aligned slot.
+    ///
+    /// _Note_: alignment requires the width of every line to be known, so the entire tree is
+    /// laid out in memory before any output is written. In the `Classic` and `OrgChart`
+    /// orientations badges are written adjacent to the label, as there is no common column in which
+    /// to align them.
+    ///
+    pub fn write_with_badges(
+        &self,
+        to_writer: &mut impl Write,
+        format: &TreeFormatting,
+        badges: &impl Badge<T>,
+    ) -> Result<()> {
+        self.render_to(&mut IoWrite(to_writer), format, |mut w| {
+            write_tree_with_badges(self, &mut w, format, badges)
+        })
+    }
+}
+
+impl<T> TreeNode<T>
+where
+    T: Display + Debug,
+{
+    ///
+    /// Write the structure of this tree with the default format settings, where each node is
+    /// labeled with the `Debug` form of its data. Every node is written, collapsed and hidden
+    /// nodes are marked as such, and the final line ending is omitted, as is usual for `Debug`
+    /// output.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use text_trees::StringTreeNode;
+    ///
+    /// let mut tree = StringTreeNode::with_children(
+    ///     "root".to_string(),
+    ///     vec!["a".to_string(), "b".to_string()].into_iter(),
+    /// );
+    /// tree.children_vec_mut()[1].set_hidden(true);
+    ///
+    /// assert_eq!(format!("{:#?}", tree), "\"root\"\n+-- \"a\"\n'-- \"b\" [Hidden]");
+    /// ```
+    ///
+    pub fn fmt_structure(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let structure = TreeNode::unfold(self, |node| {
+            let mut label = format!("{:?}", node.data);
+            if node.collapsed {
+                label.push_str(" [Collapsed]");
+            }
+            if node.visibility != Visibility::Visible {
+                label.push_str(&format!(" [{:?}]", node.visibility));
+            }
+            (label, node.children.iter().collect())
+        });
+        let text = structure.to_string();
+        f.write_str(text.strip_suffix('\n').unwrap_or(&text))
+    }
+}
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    #[inline]
+    pub(crate) fn has_rendered_children(&self) -> bool {
+        self.visible_children().next().is_some()
+    }
+
+    ///
+    /// Return the children written below this node, skipping hidden children and replacing
+    /// flattened children with their own visible children; without recursion, so that long
+    /// chains of flattened nodes do not overflow the stack.
+    ///
+    pub(crate) fn visible_children(&self) -> impl Iterator<Item = &TreeNode<T>> {
+        let mut work = Vec::new();
+        if !self.collapsed {
+            work.push(self.children.iter());
+        }
+        std::iter::from_fn(move || loop {
+            let child = match work.last_mut()?.next() {
+                Some(child) => child,
+                None => {
+                    let _ = work.pop();
+                    continue;
+                }
+            };
+            match child.visibility {
+                Visibility::Visible => return Some(child),
+                Visibility::Hidden => {}
+                Visibility::Flattened => {
+                    if !child.collapsed {
+                        work.push(child.children.iter());
+                    }
+                }
+            }
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> From<T> for TreeNode<T>
+where
+    T: Display,
+{
+    fn from(v: T) -> Self {
+        Self::new(v)
+    }
+}
+
+impl<T> From<&T> for TreeNode<T>
+where
+    T: Display + Clone,
+{
     fn from(v: &T) -> Self {
         Self::from(v.clone())
     }
 }
 
-// ------------------------------------------------------------------------------------------------
-
-impl<T> PartialEq for TreeNode<T>
+impl<T> Default for TreeNode<T>
+where
+    T: Display + Default,
+{
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl<T> AsRef<T> for TreeNode<T>
+where
+    T: Display,
+{
+    fn as_ref(&self) -> &T {
+        &self.data
+    }
+}
+
+///
+/// Note that, unlike the data, nodes are only equal if their children are also equal; so a
+/// node may only be used in place of its data where the data alone is compared, or hashed.
+///
+impl<T> Borrow<T> for TreeNode<T>
+where
+    T: Display,
+{
+    fn borrow(&self) -> &T {
+        &self.data
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> PartialEq for TreeNode<T>
+where
+    T: Display + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data && self.children == other.children
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl From<&str> for TreeNode<String> {
+    fn from(v: &str) -> Self {
+        Self::new(v.to_string())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+const COMBINING_LONG_STROKE: char = '\u{0336}';
+
+///
+/// The children of a node; this dereferences to the underlying `Vec` but is dropped
+/// iteratively, rather than recursively, so that very deep trees do not overflow the stack.
+///
+/// There is no `smallvec` feature storing children inline, as `SmallVec<[TreeNode<T>; 4]>`,
+/// because a node storing its children inline would contain itself and so have no finite size;
+/// boxing each child to avoid this costs an allocation per child rather than one per parent.
+/// Leaf nodes, typically the majority, have an empty `Vec` which does not allocate.
+///
+#[derive(Clone, PartialEq)]
+struct Children<T>(Vec<TreeNode<T>>)
+where
+    T: Display;
+
+impl<T> Debug for Children<T>
+where
+    T: Display + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T> Drop for Children<T>
+where
+    T: Display,
+{
+    fn drop(&mut self) {
+        let mut work = std::mem::take(&mut self.0);
+        while let Some(mut node) = work.pop() {
+            work.append(&mut node.children.0);
+        }
+    }
+}
+
+impl<T> std::ops::Deref for Children<T>
+where
+    T: Display,
+{
+    type Target = Vec<TreeNode<T>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for Children<T>
+where
+    T: Display,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<Vec<TreeNode<T>>> for Children<T>
+where
+    T: Display,
+{
+    fn from(v: Vec<TreeNode<T>>) -> Self {
+        Self(v)
+    }
+}
+
+impl<T> std::iter::FromIterator<TreeNode<T>> for Children<T>
+where
+    T: Display,
+{
+    fn from_iter<I: IntoIterator<Item = TreeNode<T>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<T> IntoIterator for Children<T>
+where
+    T: Display,
+{
+    type Item = TreeNode<T>;
+    type IntoIter = std::vec::IntoIter<TreeNode<T>>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        std::mem::take(&mut self.0).into_iter()
+    }
+}
+
+///
+/// The text that precedes a node's label on an output line.
+///
+#[derive(Clone, Debug)]
+struct RowPrefix {
+    /// The depth of the node, the root has depth `0`.
+    depth: usize,
+    /// The prefix string, and all tree lines up to, and including, the horizontal line
+    /// connecting the node.
+    lines: String,
+    /// Any connecting character, and spacing, between the tree lines and the label.
+    label_space: String,
+    /// A line, without line ending, written before this row; this is the root connector for
+    /// the first child of the root.
+    connector: Option<String>,
+    /// The number of identical sibling leaves written as this row, normally `1`.
+    repeats: usize,
+    /// The maximum display width of the label written for this row, if any.
+    max_label_width: Option<usize>,
+}
+
+impl Display for RowPrefix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(connector) = &self.connector {
+            writeln!(f, "{}", connector)?;
+        }
+        write!(f, "{}{}", self.lines, self.label_space)
+    }
+}
+
+impl RowPrefix {
+    #[inline]
+    fn width(&self) -> usize {
+        display_width(&self.lines) + display_width(&self.label_space)
+    }
+
+    ///
+    /// Return the label written for this row, clipped to the maximum label width for its depth,
+    /// and including the number of repeats if this row stands for a run of identical leaves.
+    ///
+    #[inline]
+    fn repeated_label(&self, label: String) -> String {
+        let label = match self.max_label_width {
+            Some(width) => clip_line(&label, width),
+            None => label,
+        };
+        if self.repeats > 1 {
+            format!("{} (×{})", label, self.repeats)
+        } else {
+            label
+        }
+    }
+}
+
+///
+/// The connector segments for a format, computed once per rendering rather than for every row.
+///
+#[derive(Clone, Debug)]
+struct CompiledFormat {
+    prefix: String,
+    root_indent: String,
+    root: String,
+    root_label_space: String,
+    root_connector: Option<String>,
+    tee: String,
+    angle: String,
+    branch_tee: String,
+    branch_angle: String,
+    just_space: String,
+    bar_and_space: String,
+    tail: String,
+    tail_with_children: String,
+    horizontal_space: char,
+    vertical_line: char,
+    max_label_widths: Vec<usize>,
+}
+
+impl CompiledFormat {
+    fn new(format: &TreeFormatting) -> Self {
+        let prefix = format.prefix_str.clone().unwrap_or_default();
+        let (root, root_label_space) = if format.anchor == AnchorPosition::Below {
+            (prefix.clone(), String::new())
+        } else {
+            (
+                format!("{}{}", prefix, format.chars.down_facing_angle),
+                format.chars.label_space(),
+            )
+        };
+        let root_indent = format.root_indent();
+        let root_connector = match (&format.anchor, format.root_connector) {
+            (AnchorPosition::Below, Some(c)) => Some(format!("{}{}{}", prefix, root_indent, c)),
+            _ => None,
+        };
+        Self {
+            prefix,
+            root_indent,
+            root,
+            root_label_space,
+            root_connector,
+            tee: format.tee(),
+            angle: format.angle(),
+            branch_tee: format.branch_tee(),
+            branch_angle: format.branch_angle(),
+            just_space: format.just_space(),
+            bar_and_space: format.bar_and_space(),
+            tail: format.connector_tail(false),
+            tail_with_children: format.connector_tail(true),
+            horizontal_space: format.chars.horizontal_space,
+            vertical_line: format.chars.vertical_line,
+            max_label_widths: format.max_label_widths.clone(),
+        }
+    }
+
+    #[inline]
+    fn max_label_width(&self, depth: usize) -> Option<usize> {
+        label_width_at(&self.max_label_widths, depth)
+    }
+
+    ///
+    /// Return the tree lines that precede text written below the label of the node at
+    /// `position`, the last-child flag of each node from a child of the root down to the node,
+    /// and which does, or does not, have children written below the text. Text below a node
+    /// without children starts in the column of its label, whatever the width of each level of
+    /// tree lines.
+    ///
+    fn indent(&self, position: &[bool], has_children: bool) -> String {
+        let mut indent = self.prefix.clone();
+        let (node_is_last, ancestors) = match (position.split_last(), has_children) {
+            (None, false) => {
+                let width = display_width(&self.root[self.prefix.len()..])
+                    + display_width(&self.root_label_space);
+                indent.push_str(&char_repeat(self.horizontal_space, width));
+                return indent;
+            }
+            (Some((is_last, ancestors)), false) => (Some(*is_last), ancestors),
+            (_, true) => (None, position),
+        };
+
+        indent.push_str(&self.root_indent);
+        for is_last in ancestors {
+            indent.push_str(if *is_last {
+                &self.just_space
+            } else {
+                &self.bar_and_space
+            });
+        }
+        match node_is_last {
+            Some(is_last) => {
+                let width = display_width(&self.angle) + display_width(&self.tail);
+                if !is_last {
+                    indent.push(self.vertical_line);
+                }
+                let spaces = if is_last { width } else { width - 1 };
+                indent.push_str(&char_repeat(self.horizontal_space, spaces));
+            }
+            None => indent.push_str(&self.bar_and_space),
+        }
+        indent
+    }
+}
+
+///
+/// Builds the `RowPrefix` for each row of a tree, which must be requested in output order.
+///
+/// Rather than each row being built from the complete list of its ancestors, a single
+/// `continuation` string holds the vertical lines, or spaces, for each ancestor level of the
+/// current row and is truncated, or extended, as rows move between levels.
+///
+#[derive(Clone, Debug)]
+struct RowPrefixBuilder {
+    segments: CompiledFormat,
+    /// The tree lines, for each ancestor level, that precede the connector of the current row.
+    continuation: String,
+    /// The length of `continuation` at each level, `ends[0]` is the length of the root indent.
+    ends: Vec<usize>,
+    /// `true` if the next row is the first child of the root, and is preceded by the root
+    /// connector.
+    connect_next: bool,
+}
+
+impl RowPrefixBuilder {
+    fn new(format: &TreeFormatting) -> Self {
+        let segments = CompiledFormat::new(format);
+        Self {
+            continuation: segments.root_indent.clone(),
+            ends: vec![segments.root_indent.len()],
+            segments,
+            connect_next: false,
+        }
+    }
+
+    ///
+    /// Return the prefix for the next row, for a node at `depth` that is, or is not, the last
+    /// child of its parent, and that does, or does not, have children written below it.
+    ///
+    fn row(&mut self, depth: usize, is_last: bool, has_children: bool) -> RowPrefix {
+        let segments = &self.segments;
+        if depth == 0 {
+            self.connect_next = has_children && segments.root_connector.is_some();
+            return RowPrefix {
+                depth,
+                lines: segments.root.clone(),
+                label_space: segments.root_label_space.clone(),
+                connector: None,
+                repeats: 1,
+                max_label_width: segments.max_label_width(depth),
+            };
+        }
+        let connector = if self.connect_next {
+            self.connect_next = false;
+            segments.root_connector.clone()
+        } else {
+            None
+        };
+
+        // Write the leading structures, for the ancestors and then this node
+        self.continuation.truncate(self.ends[depth - 1]);
+        self.ends.truncate(depth);
+        let mut lines = String::with_capacity(
+            segments.prefix.len() + self.continuation.len() + segments.tee.len(),
+        );
+        lines.push_str(&segments.prefix);
+        lines.push_str(&self.continuation);
+        lines.push_str(match (is_last, has_children) {
+            (true, false) => &segments.angle,
+            (false, false) => &segments.tee,
+            (true, true) => &segments.branch_angle,
+            (false, true) => &segments.branch_tee,
+        });
+        let label_space = if has_children {
+            segments.tail_with_children.clone()
+        } else {
+            segments.tail.clone()
+        };
+
+        if has_children {
+            self.continuation.push_str(if is_last {
+                &segments.just_space
+            } else {
+                &segments.bar_and_space
+            });
+            self.ends.push(self.continuation.len());
+        }
+
+        RowPrefix {
+            depth,
+            lines,
+            label_space,
+            connector,
+            repeats: 1,
+            max_label_width: self.segments.max_label_width(depth),
+        }
+    }
+}
+
+///
+/// An iterator over the rows of a tree, in output order, returning each node and the `RowPrefix`
+/// that precedes its label on the output line.
+///
+/// This uses an explicit work stack, rather than recursion, so that very deep trees do not
+/// overflow the call stack.
+///
+struct Rows<'a, T>
+where
+    T: Display,
+{
+    /// Nodes still to be visited, with their depth, whether they are their parent's last
+    /// rendered child, and the number of identical sibling leaves they stand for.
+    work: Vec<(&'a TreeNode<T>, usize, bool, usize)>,
+    prefix: RowPrefixBuilder,
+    collapse_repeated_leaves: bool,
+}
+
+impl<'a, T> Rows<'a, T>
+where
+    T: Display,
+{
+    fn new(node: &'a TreeNode<T>, format: &TreeFormatting) -> Self {
+        Self {
+            work: vec![(node, 0, true, 1)],
+            prefix: RowPrefixBuilder::new(format),
+            collapse_repeated_leaves: format.collapse_repeated_leaves,
+        }
+    }
+
+    ///
+    /// Return the rows of the subtree rooted at `node`, a child of the root of the tree; this
+    /// allows the subtrees of the root to be written independently of each other.
+    ///
+    #[cfg_attr(not(feature = "rayon"), allow(dead_code))]
+    fn root_child(node: &'a TreeNode<T>, is_last: bool, format: &TreeFormatting) -> Self {
+        Self {
+            work: vec![(node, 1, is_last, 1)],
+            prefix: RowPrefixBuilder::new(format),
+            collapse_repeated_leaves: format.collapse_repeated_leaves,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Rows<'a, T>
+where
+    T: Display,
+{
+    type Item = (RowPrefix, &'a TreeNode<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, depth, is_last, repeats) = self.work.pop()?;
+        let mut row = self
+            .prefix
+            .row(depth, is_last, node.has_rendered_children());
+        row.repeats = repeats;
+
+        // Schedule any children, marking the last, then reverse them so the first is visited next
+        let start = self.work.len();
+        if self.collapse_repeated_leaves {
+            self.work.extend(
+                repeated_leaf_runs(node.visible_children())
+                    .map(|(first, run)| (first, depth + 1, false, run.len())),
+            );
+        } else {
+            self.work.extend(
+                node.visible_children()
+                    .map(|child| (child, depth + 1, false, 1)),
+            );
+        }
+        if let Some(last) = self.work[start..].last_mut() {
+            last.2 = true;
+        }
+        self.work[start..].reverse();
+
+        Some((row, node))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Records each distinct badge returned by the wrapped badges, in the order first returned, so
+/// that the legend lists only those badges written.
+///
+struct UsedBadges<'a, B> {
+    badges: &'a B,
+    used: RefCell<Vec<String>>,
+}
+
+impl<T, B> Badge<T> for UsedBadges<'_, B>
 where
-    T: Display + PartialEq,
+    B: Badge<T>,
 {
-    fn eq(&self, other: &Self) -> bool {
-        self.data == other.data && self.children == other.children
+    fn badge(&self, data: &T) -> Option<String> {
+        let badge = self.badges.badge(data)?;
+        let mut used = self.used.borrow_mut();
+        if !used.contains(&badge) {
+            used.push(badge.clone());
+        }
+        Some(badge)
     }
 }
 
 // ------------------------------------------------------------------------------------------------
 
-impl From<&str> for TreeNode<String> {
-    fn from(v: &str) -> Self {
+///
+/// Rows of cells written as aligned columns, shared by every writer that writes columns after the
+/// tree, so that widths, alignment, and clipping behave the same for each. The first cell of each
+/// row is the tree itself, its lines and label, which is left aligned and never clipped; each
+/// following cell is clipped to, and aligned within, its column according to the corresponding
+/// column format. Every row must be measured before any row is written.
+///
+#[derive(Debug)]
+struct AlignedColumns<'a> {
+    formats: &'a [ColumnFormat],
+    /// The alignment of columns without a format.
+    alignment: ColumnAlignment,
+    widths: Vec<usize>,
+}
+
+impl<'a> AlignedColumns<'a> {
+    fn new(formats: &'a [ColumnFormat], alignment: ColumnAlignment) -> Self {
         Self {
-            data: v.to_string(),
-            children: Default::default(),
+            formats,
+            alignment,
+            widths: Vec::new(),
+        }
+    }
+
+    /// Clip each cell of a row to the maximum width of its column, and widen the columns to fit.
+    fn measure(&mut self, cells: &mut [String]) {
+        for (index, cell) in cells.iter_mut().enumerate() {
+            let format = self.format(index);
+            if let Some(max_width) = format.and_then(|format| format.max_width) {
+                *cell = clip_line(cell, max_width);
+            }
+            let min_width = format
+                .and_then(|format| format.min_width)
+                .unwrap_or_default();
+            if index == self.widths.len() {
+                self.widths.push(0);
+            }
+            self.widths[index] = self.widths[index].max(min_width).max(display_width(cell));
+        }
+    }
+
+    /// The width of each column, the tree first, wide enough for every row measured.
+    fn widths(&self) -> &[usize] {
+        &self.widths
+    }
+
+    /// As [`write_styled_row`](#method.write_styled_row), without styling the cells.
+    fn write_row(
+        &self,
+        w: &mut impl fmt::Write,
+        cells: Vec<String>,
+        separator: &str,
+    ) -> fmt::Result {
+        self.write_styled_row(w, cells, separator, |_, cell| cell)
+    }
+
+    ///
+    /// Write the cells of a row, without a line ending, separated by `separator` and each aligned
+    /// within its column. The text of each cell is passed to `style`, with the cell's index, once
+    /// it has been aligned, so that any escape sequences added do not affect the alignment.
+    /// Trailing space is not written after the last cell of a row.
+    ///
+    fn write_styled_row(
+        &self,
+        w: &mut impl fmt::Write,
+        cells: Vec<String>,
+        separator: &str,
+        style: impl Fn(usize, String) -> String,
+    ) -> fmt::Result {
+        let last = cells.len().saturating_sub(1);
+        for (index, cell) in cells.into_iter().enumerate() {
+            if index > 0 {
+                w.write_str(separator)?;
+            }
+            let alignment = match index {
+                0 => ColumnAlignment::Left,
+                _ => self
+                    .format(index)
+                    .map(|format| format.alignment)
+                    .unwrap_or(self.alignment),
+            };
+            let width = self.widths.get(index).copied().unwrap_or_default();
+            let padding = width.saturating_sub(display_width(&cell));
+            let (before, after) = match alignment {
+                ColumnAlignment::Left => (0, padding),
+                ColumnAlignment::Right => (padding, 0),
+                ColumnAlignment::Center => (padding / 2, padding - padding / 2),
+            };
+            write!(
+                w,
+                "{}{}{}",
+                " ".repeat(before),
+                style(index, cell),
+                " ".repeat(if index == last { 0 } else { after })
+            )?;
         }
+        Ok(())
+    }
+
+    /// The format of the column at `index`, the tree column, at index `0`, has none.
+    fn format(&self, index: usize) -> Option<&'a ColumnFormat> {
+        index
+            .checked_sub(1)
+            .and_then(|index| self.formats.get(index))
     }
 }
 
@@ -747,65 +2900,640 @@ impl From<&str> for TreeNode<String> {
 // Private Functions
 // ------------------------------------------------------------------------------------------------
 
+///
+/// Counts the lines, or bytes, written since a writer was last flushed, so that it may be flushed
+/// according to a [`FlushPolicy`](enum.FlushPolicy.html).
+///
+#[derive(Clone, Copy, Debug)]
+struct FlushCounter {
+    policy: FlushPolicy,
+    unflushed: usize,
+}
+
+impl FlushCounter {
+    fn new(policy: FlushPolicy) -> Self {
+        Self {
+            policy,
+            unflushed: 0,
+        }
+    }
+
+    /// Record that `lines` line endings, and `bytes` bytes, were written, returning `true` if
+    /// the writer should now be flushed.
+    fn wrote(&mut self, lines: usize, bytes: usize) -> bool {
+        let limit = match self.policy {
+            FlushPolicy::OnFinish => return false,
+            FlushPolicy::EveryLines(limit) => {
+                self.unflushed += lines;
+                limit
+            }
+            FlushPolicy::EveryBytes(limit) => {
+                self.unflushed += bytes;
+                limit
+            }
+        };
+        if self.unflushed >= limit {
+            self.unflushed = 0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+///
+/// Adapts an implementation of `std::io::Write` so that the rendering functions, which write to
+/// an implementation of `std::fmt::Write`, can write to it; any I/O error is retained so that it
+/// can be returned in place of the uninformative `std::fmt::Error`.
+///
+/// Rendering writes many small fragments, labels, and individual tree lines, so the writer is
+/// buffered to avoid a system call per fragment when writing to files, sockets, or pipes.
+///
+struct IoWriter<'a, W: Write> {
+    inner: BufWriter<&'a mut W>,
+    error: Option<io::Error>,
+    flush: FlushCounter,
+}
+
+impl<W: Write> fmt::Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let result = self.inner.write_all(s.as_bytes()).and_then(|_| {
+            if self.flush.wrote(s.matches('\n').count(), s.len()) {
+                self.inner.flush()
+            } else {
+                Ok(())
+            }
+        });
+        result.map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+///
+/// Adapts an implementation of `std::fmt::Write` so that each line written to it is clipped to
+/// the format's `max_line_width`, and has the format's `line_transforms` applied; if the format
+/// sets `elastic_tabs`, all lines are held until `finish` is called so that their tab-separated
+/// fields may be aligned. If none of these are set text is passed through unchanged. Any text
+/// following the last line ending is held until `finish` is called.
+///
+struct ClipLines<'a, W: fmt::Write> {
+    inner: &'a mut W,
+    format: TreeFormatting,
+    line: String,
+    held: Vec<String>,
+}
+
+impl<W: fmt::Write> fmt::Write for ClipLines<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.passes_through() {
+            return self.inner.write_str(s);
+        }
+        let mut rest = s;
+        while let Some(end) = rest.find('\n') {
+            self.line.push_str(&rest[..end]);
+            if self.format.elastic_tabs.is_some() {
+                self.held.push(std::mem::take(&mut self.line));
+            } else {
+                writeln!(self.inner, "{}", self.format.output_line(&self.line))?;
+                self.line.clear();
+            }
+            rest = &rest[end + 1..];
+        }
+        self.line.push_str(rest);
+        Ok(())
+    }
+}
+
+impl<'a, W: fmt::Write> ClipLines<'a, W> {
+    fn new(inner: &'a mut W, format: &TreeFormatting) -> Self {
+        Self {
+            inner,
+            format: format.clone(),
+            line: String::new(),
+            held: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn passes_through(&self) -> bool {
+        self.format.max_line_width.is_none()
+            && self.format.elastic_tabs.is_none()
+            && self.format.line_transforms.is_empty()
+    }
+
+    fn finish(mut self) -> fmt::Result {
+        if let Some(gap) = self.format.elastic_tabs {
+            let partial = !self.line.is_empty();
+            if partial {
+                self.held.push(std::mem::take(&mut self.line));
+            }
+            align_tabs(&mut self.held, gap, &self.format.column_formats);
+            let last = self.held.len().saturating_sub(1);
+            for (index, line) in self.held.iter().enumerate() {
+                let line = self.format.output_line(line);
+                if partial && index == last {
+                    self.inner.write_str(&line)?;
+                } else {
+                    writeln!(self.inner, "{}", line)?;
+                }
+            }
+            Ok(())
+        } else if !self.passes_through() && !self.line.is_empty() {
+            self.inner.write_str(&self.format.output_line(&self.line))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn write_io<W: Write>(
+    w: &mut W,
+    flush_policy: FlushPolicy,
+    render: impl FnOnce(&mut IoWriter<'_, W>) -> fmt::Result,
+) -> Result<()> {
+    let mut writer = IoWriter {
+        inner: BufWriter::new(w),
+        error: None,
+        flush: FlushCounter::new(flush_policy),
+    };
+    render(&mut writer).map_err(|e| writer.error.take().unwrap_or_else(|| io::Error::other(e)))?;
+    writer.inner.flush()
+}
+
 fn write_tree_inner<T>(
     node: &TreeNode<T>,
-    w: &mut impl Write,
+    w: &mut impl fmt::Write,
+    format: &TreeFormatting,
+    label_fn: &impl Fn(&TreeNode<T>) -> String,
+) -> fmt::Result
+where
+    T: Display,
+{
+    let mut w = ClipLines::new(w, format);
+    write_tree_lines(node, &mut w, format, label_fn)?;
+    w.finish()
+}
+
+fn write_tree_lines<T>(
+    node: &TreeNode<T>,
+    w: &mut impl fmt::Write,
+    format: &TreeFormatting,
+    label_fn: &impl Fn(&TreeNode<T>) -> String,
+) -> fmt::Result
+where
+    T: Display,
+{
+    match format.orientation {
+        TreeOrientation::TopDown if format.align_depths => {
+            for (row, node) in collect_rows(node, format) {
+                writeln!(w, "{}{}", row, row.repeated_label(label_fn(node)))?;
+            }
+            Ok(())
+        }
+        TreeOrientation::TopDown => {
+            for (row, node) in Rows::new(node, format) {
+                writeln!(w, "{}{}", row, row.repeated_label(label_fn(node)))?;
+            }
+            Ok(())
+        }
+        TreeOrientation::Classic | TreeOrientation::OrgChart => {
+            classic::write_tree(node, w, format, label_fn)
+        }
+    }
+}
+
+///
+/// Collect all the rows of the tree, in output order, if the format requires it labels are
+/// aligned at each depth.
+///
+fn collect_rows<'a, T>(
+    node: &'a TreeNode<T>,
     format: &TreeFormatting,
-    remaining_children_stack: Vec<usize>,
-) -> Result<()>
+) -> Vec<(RowPrefix, &'a TreeNode<T>)>
 where
     T: Display,
 {
-    // Write any requested prefix
-    if let Some(prefix_str) = &format.prefix_str {
-        write!(w, "{}", prefix_str)?;
+    let mut rows: Vec<(RowPrefix, &'a TreeNode<T>)> = Rows::new(node, format).collect();
+
+    if format.align_depths {
+        let mut depth_widths: Vec<usize> = Default::default();
+        for (row, _) in &rows {
+            if row.depth >= depth_widths.len() {
+                depth_widths.resize(row.depth + 1, 0);
+            }
+            depth_widths[row.depth] = depth_widths[row.depth].max(row.width());
+        }
+        for (row, _) in &mut rows {
+            let padding = depth_widths[row.depth] - row.width();
+            row.lines
+                .push_str(&char_repeat(format.chars.horizontal_line, padding));
+        }
     }
 
-    if !(format.anchor == AnchorPosition::Below) && remaining_children_stack.is_empty() {
-        write!(
-            w,
-            "{}{}",
-            format.chars.down_facing_angle,
-            char_repeat(
-                format.chars.label_space_char,
-                format.chars.label_space_count
-            )
-        )?;
+    rows
+}
+
+///
+/// Return the runs of consecutive sibling leaf nodes with identical labels, each written as a
+/// single row if `collapse_repeated_leaves` is set, as the first node of each run and the range
+/// of indices the run covers in `children`; every other node is a run of its own.
+///
+fn repeated_leaf_runs<'a, T>(
+    children: impl Iterator<Item = &'a TreeNode<T>>,
+) -> impl Iterator<Item = (&'a TreeNode<T>, Range<usize>)>
+where
+    T: Display + 'a,
+{
+    let mut children = children
+        .enumerate()
+        .map(|(index, child)| (index, child, (!child.has_children()).then(|| child.label())))
+        .peekable();
+    std::iter::from_fn(move || {
+        let (start, first, label) = children.next()?;
+        let mut end = start + 1;
+        if label.is_some() {
+            while children.next_if(|(_, _, next)| *next == label).is_some() {
+                end += 1;
+            }
+        }
+        Some((first, start..end))
+    })
+}
+
+///
+/// Return a tree of the same shape, with the same collapsed state and visibility, where the
+/// data of each node is replaced by the result of `f`; `f` is called for each node in the order
+/// the nodes are written. The tree is traversed without recursion.
+///
+fn map_tree<T, U>(node: TreeNode<T>, mut f: impl FnMut(T) -> U) -> TreeNode<U>
+where
+    T: Display,
+    U: Display,
+{
+    let mut start = |node: TreeNode<T>| {
+        let TreeNode {
+            data,
+            children,
+            collapsed,
+            visibility,
+        } = node;
+        let mapped = TreeNode {
+            data: f(data),
+            children: Vec::with_capacity(children.len()).into(),
+            collapsed,
+            visibility,
+        };
+        (mapped, children.into_iter())
+    };
+
+    let mut stack = vec![start(node)];
+    loop {
+        match stack.last_mut().unwrap().1.next() {
+            Some(child) => stack.push(start(child)),
+            None => {
+                let (done, _) = stack.pop().unwrap();
+                match stack.last_mut() {
+                    Some((parent, _)) => parent.children.push(done),
+                    None => return done,
+                }
+            }
+        }
+    }
+}
+
+///
+/// Return a tree of the same shape, as `map_tree` does, where the data of each node is the
+/// result of `f` called with a reference to the data of the provided tree.
+///
+fn map_tree_ref<T, U>(node: &TreeNode<T>, mut f: impl FnMut(&T) -> U) -> TreeNode<U>
+where
+    T: Display,
+    U: Display,
+{
+    let mut start = |node: &TreeNode<T>| TreeNode {
+        data: f(&node.data),
+        children: Vec::with_capacity(node.children.len()).into(),
+        collapsed: node.collapsed,
+        visibility: node.visibility,
+    };
+
+    let mut stack = vec![(start(node), node.children.iter())];
+    loop {
+        match stack.last_mut().unwrap().1.next() {
+            Some(child) => stack.push((start(child), child.children.iter())),
+            None => {
+                let (done, _) = stack.pop().unwrap();
+                match stack.last_mut() {
+                    Some((parent, _)) => parent.children.push(done),
+                    None => return done,
+                }
+            }
+        }
+    }
+}
+
+fn write_tree_with_deleted<T>(
+    node: &TreeNode<T>,
+    w: &mut impl fmt::Write,
+    format: &TreeFormatting,
+    is_deleted: impl Fn(&T) -> bool,
+) -> fmt::Result
+where
+    T: Display,
+{
+    write_tree_inner(node, w, format, &|node| {
+        let label = format.node_label(node);
+        if is_deleted(node.data()) {
+            format.deleted_style.apply(&label)
+        } else {
+            label
+        }
+    })
+}
+
+fn write_tree_with_annotations<T>(
+    node: &TreeNode<T>,
+    w: &mut impl fmt::Write,
+    format: &TreeFormatting,
+    annotations: impl Fn(&T) -> Option<String>,
+) -> fmt::Result
+where
+    T: Display,
+{
+    // Labels are only generated in reading order, and so markers numbered in order, top-down.
+    let footnotes_enabled =
+        format.annotation_footnotes && format.orientation == TreeOrientation::TopDown;
+    // The text of each footnote, in order, and the number of each.
+    let footnotes: RefCell<(Vec<String>, HashMap<String, usize>)> = Default::default();
+    let mut w = ClipLines::new(w, format);
+    write_tree_lines(node, &mut w, format, &|node| {
+        let label = format.node_label(node);
+        match annotations(node.data()) {
+            None => label,
+            Some(annotation) if footnotes_enabled => {
+                let (texts, numbers) = &mut *footnotes.borrow_mut();
+                let number = *numbers.entry(annotation).or_insert_with_key(|annotation| {
+                    texts.push(annotation.clone());
+                    texts.len()
+                });
+                format!("{}{}[{}]", label, format.annotation_separator, number)
+            }
+            Some(annotation) => format!("{}{}{}", label, format.annotation_separator, annotation),
+        }
+    })?;
+    write_footnotes(&mut w, footnotes.into_inner().0)?;
+    w.finish()
+}
+
+///
+/// Write the text of each footnote, if any, after an empty line, with its number.
+///
+fn write_footnotes(w: &mut impl fmt::Write, footnotes: Vec<String>) -> fmt::Result {
+    if !footnotes.is_empty() {
+        writeln!(w)?;
+        for (index, footnote) in footnotes.iter().enumerate() {
+            writeln!(w, "[{}] {}", index + 1, footnote)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_tree_with_badges<T>(
+    node: &TreeNode<T>,
+    w: &mut impl fmt::Write,
+    format: &TreeFormatting,
+    badges: &impl Badge<T>,
+) -> fmt::Result
+where
+    T: Display,
+{
+    let mut w = ClipLines::new(w, format);
+    if !format.badge_legend {
+        write_badge_lines(node, &mut w, format, badges)?;
+        return w.finish();
+    }
+
+    let used = UsedBadges {
+        badges,
+        used: Default::default(),
+    };
+    write_badge_lines(node, &mut w, format, &used)?;
+    write_badge_legend(&mut w, used.used.into_inner(), badges)?;
+    w.finish()
+}
+
+///
+/// Write the legend of the badges written, if any have a description, after an empty line; the
+/// descriptions are aligned in the column after the widest badge.
+///
+fn write_badge_legend<T>(
+    w: &mut impl fmt::Write,
+    used: Vec<String>,
+    badges: &impl Badge<T>,
+) -> fmt::Result {
+    let legend: Vec<(String, String)> = used
+        .into_iter()
+        .filter_map(|badge| {
+            let description = badges.description(&badge)?;
+            Some((badge, description))
+        })
+        .collect();
+    if !legend.is_empty() {
+        let badge_width = legend
+            .iter()
+            .map(|(badge, _)| display_width(badge))
+            .max()
+            .unwrap_or_default();
+        writeln!(w)?;
+        for (badge, description) in legend {
+            writeln!(w, "{} {}", pad_to_width(&badge, badge_width), description)?;
+        }
     }
+    Ok(())
+}
 
-    // Write the leading structures
-    let stack_depth = remaining_children_stack.len();
-    for (row, remaining_children) in remaining_children_stack.iter().enumerate() {
-        write!(
-            w,
-            "{}",
-            match (*remaining_children, row == (stack_depth - 1)) {
-                (1, true) => format.angle(node.has_children()),
-                (1, false) => format.just_space(),
-                (_, true) => format.tee(node.has_children()),
-                (_, false) => format.bar_and_space(),
+fn write_badge_lines<T>(
+    node: &TreeNode<T>,
+    w: &mut impl fmt::Write,
+    format: &TreeFormatting,
+    badges: &impl Badge<T>,
+) -> fmt::Result
+where
+    T: Display,
+{
+    if format.orientation != TreeOrientation::TopDown {
+        return classic::write_tree(node, w, format, &|node| {
+            let label = format.node_label(node);
+            match (badges.badge(node.data()), &format.badge_position) {
+                (None, _) => label,
+                (Some(badge), BadgePosition::BeforeLabel) => format!("{} {}", badge, label),
+                (Some(badge), BadgePosition::AfterLabel) => format!("{} {}", label, badge),
             }
-        )?;
+        });
     }
 
-    // Write the node label, and any children (recursively)
-    if node.has_children() {
-        writeln!(w, "{}", node.label())?;
-        let mut d = node.children.len();
-        for child in &node.children {
-            let mut new_child_stack = remaining_children_stack.clone();
-            new_child_stack.push(d);
-            d -= 1;
-            write_tree_inner(child, w, format, new_child_stack)?;
+    // Each row is the root connector line, if any, the prefix, the label, and the badge.
+    let rows: Vec<(Option<String>, String, String, Option<String>)> = collect_rows(node, format)
+        .into_iter()
+        .map(|(mut row, node)| {
+            (
+                row.connector.take(),
+                row.to_string(),
+                row.repeated_label(format.node_label(node)),
+                badges.badge(node.data()),
+            )
+        })
+        .collect();
+
+    if format.badge_position == BadgePosition::BeforeLabel {
+        let badge_width = rows
+            .iter()
+            .filter_map(|(_, _, _, badge)| badge.as_ref().map(|badge| display_width(badge)))
+            .max()
+            .unwrap_or_default();
+        for (connector, line_prefix, label, badge) in rows {
+            if let Some(connector) = connector {
+                writeln!(w, "{}", connector)?;
+            }
+            if badge_width > 0 {
+                writeln!(
+                    w,
+                    "{}{} {}",
+                    line_prefix,
+                    pad_to_width(&badge.unwrap_or_default(), badge_width),
+                    label
+                )?;
+            } else {
+                writeln!(w, "{}{}", line_prefix, label)?;
+            }
         }
-    } else {
-        writeln!(w, "{}", node.label())?;
+        return Ok(());
     }
 
-    // All done :)
+    // After the label the badges are written as a column following the tree.
+    let mut columns = AlignedColumns::new(&format.column_formats, ColumnAlignment::Left);
+    let rows: Vec<(Option<String>, Vec<String>)> = rows
+        .into_iter()
+        .map(|(connector, line_prefix, label, badge)| {
+            let mut cells = vec![format!("{}{}", line_prefix, label)];
+            cells.extend(badge);
+            columns.measure(&mut cells);
+            (connector, cells)
+        })
+        .collect();
+    for (connector, cells) in rows {
+        if let Some(connector) = connector {
+            writeln!(w, "{}", connector)?;
+        }
+        columns.write_row(w, cells, " ")?;
+        writeln!(w)?;
+    }
     Ok(())
 }
 
+///
+/// Return the lines written for a row, the root connector if the row has one, and the row itself.
+///
+fn row_lines(mut row: RowPrefix, label: String) -> impl Iterator<Item = String> {
+    row.connector
+        .take()
+        .into_iter()
+        .chain(std::iter::once(format!(
+            "{}{}",
+            row,
+            row.repeated_label(label)
+        )))
+}
+
+// Trees, and format settings, are written concurrently from multiple threads, this fails to
+// compile if either stops being `Send` and `Sync`.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<TreeNode<String>>();
+    assert_send_sync::<TreeFormatting>();
+};
+
+#[inline]
+fn limit_exceeded(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+///
+/// Return the width, from a list of widths for each depth, for `depth`; the last width applies
+/// to all deeper levels.
+///
+#[inline]
+fn label_width_at(widths: &[usize], depth: usize) -> Option<usize> {
+    widths.get(depth).or_else(|| widths.last()).copied()
+}
+
+///
+/// Return the provided line clipped to `width` display columns, ending with an ellipsis if any
+/// characters were removed.
+///
+fn clip_line(line: &str, width: usize) -> String {
+    if display_width(line) <= width {
+        return line.to_string();
+    }
+    let mut clipped = String::with_capacity(width + 2);
+    let mut used = 0;
+    for c in line.chars() {
+        let c_width = UnicodeWidthChar::width(c).unwrap_or_default();
+        if used + c_width >= width {
+            break;
+        }
+        used += c_width;
+        clipped.push(c);
+    }
+    if width > 0 {
+        clipped.push('…');
+    }
+    clipped
+}
+
+///
+/// Replace the tabs in each of the provided lines so that the text following the n-th tab on
+/// every line starts in the same column, `gap` columns after the widest text preceding it. The
+/// text following each tab is formatted according to the corresponding column format.
+///
+fn align_tabs(lines: &mut [String], gap: usize, columns: &[ColumnFormat]) {
+    let mut aligned = AlignedColumns::new(columns, ColumnAlignment::Left);
+    let rows: Vec<Option<Vec<String>>> = lines
+        .iter()
+        .map(|line| {
+            line.contains('\t').then(|| {
+                let mut cells: Vec<String> = line.split('\t').map(str::to_string).collect();
+                aligned.measure(&mut cells);
+                cells
+            })
+        })
+        .collect();
+    let separator = " ".repeat(gap);
+    for (line, cells) in lines.iter_mut().zip(rows) {
+        if let Some(cells) = cells {
+            let mut text = String::with_capacity(line.len());
+            let _ = aligned.write_row(&mut text, cells, &separator);
+            *line = text;
+        }
+    }
+}
+
+#[inline]
+fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+#[inline]
+fn pad_to_width(s: &str, width: usize) -> String {
+    format!(
+        "{}{}",
+        s,
+        " ".repeat(width.saturating_sub(display_width(s)))
+    )
+}
+
 #[inline]
 fn char_repeat(c: char, n: usize) -> String {
     c.to_string().as_str().repeat(n)
@@ -826,7 +3554,9 @@ mod tests {
             node,
             TreeNode {
                 data: "hello".to_string(),
-                children: vec![]
+                children: vec![].into(),
+                collapsed: false,
+                visibility: Visibility::Visible,
             }
         );
     }
@@ -840,8 +3570,13 @@ mod tests {
                 data: "hello".to_string(),
                 children: vec![TreeNode {
                     data: "world".to_string(),
-                    children: vec![]
+                    children: vec![].into(),
+                    collapsed: false,
+                    visibility: Visibility::Visible,
                 }]
+                .into(),
+                collapsed: false,
+                visibility: Visibility::Visible,
             }
         );
     }
@@ -853,8 +3588,87 @@ mod tests {
             node,
             TreeNode {
                 data: "hello".to_string(),
-                children: vec![]
+                children: vec![].into(),
+                collapsed: false,
+                visibility: Visibility::Visible,
             }
         );
     }
+
+    #[test]
+    fn test_node_from_str() {
+        let buffer = String::from("hello world");
+        let mut node: StrTreeNode<'_> = buffer[..5].into();
+        node.push(&buffer[6..]);
+        assert_eq!(*node.data(), "hello");
+        assert!(std::ptr::eq(*node.data(), &buffer[..5]));
+        assert_eq!(node.to_string(), "hello\n'-- world\n");
+    }
+
+    #[test]
+    fn test_quote_double() {
+        let quoting = LabelQuoting::double_quotes();
+        assert_eq!(quoting.quote("simple"), "simple");
+        assert_eq!(quoting.quote("My Documents"), r#""My Documents""#);
+        assert_eq!(quoting.quote(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(quoting.quote("a\tb"), r#""a\tb""#);
+        assert_eq!(quoting.quote(""), r#""""#);
+    }
+
+    #[test]
+    fn test_quote_single() {
+        let quoting = LabelQuoting::single_quotes();
+        assert_eq!(quoting.quote("it's"), r#"'it\'s'"#);
+        assert_eq!(quoting.quote(r#"C:\Temp"#), r#"'C:\\Temp'"#);
+    }
+
+    #[test]
+    fn test_quote_backslash_with_delimiters() {
+        let quoting = LabelQuoting::with_delimiters(QuoteStyle::Backslash, vec!['/']);
+        assert_eq!(quoting.quote("src/lib.rs"), r#"src\/lib.rs"#);
+        assert_eq!(quoting.quote("My Documents"), r#"My\ Documents"#);
+        assert_eq!(quoting.quote(""), "");
+    }
+
+    #[test]
+    fn test_node_descendant_count() {
+        let mut node = TreeNode::with_child_nodes(
+            String::from("hello"),
+            vec![TreeNode::with_children(
+                String::from("world"),
+                vec![String::from("!")].into_iter(),
+            )]
+            .into_iter(),
+        );
+        assert_eq!(node.descendant_count(), 2);
+        assert!(!node.is_collapsed());
+        node.set_collapsed(true);
+        assert!(node.is_collapsed());
+        assert_eq!(node.to_string(), "hello [+2]\n");
+    }
+
+    #[test]
+    fn test_deleted_style() {
+        assert_eq!(DeletedStyle::default().apply("gone"), "~~gone~~");
+        assert_eq!(
+            DeletedStyle::Markers("[-".to_string(), "-]".to_string()).apply("gone"),
+            "[-gone-]"
+        );
+        assert_eq!(
+            DeletedStyle::Strikethrough.apply("ab"),
+            "a\u{0336}b\u{0336}"
+        );
+        assert_eq!(display_width(&DeletedStyle::Strikethrough.apply("ab")), 2);
+    }
+
+    #[test]
+    fn test_clip_line() {
+        assert_eq!(clip_line("+-- label", 20), "+-- label");
+        assert_eq!(clip_line("+-- label", 9), "+-- label");
+        assert_eq!(clip_line("+-- label", 8), "+-- lab…");
+        assert_eq!(clip_line("└── 日本語", 7), "└── 日…");
+        assert_eq!(clip_line("└── 日本語", 6), "└── …");
+        assert_eq!(clip_line("label", 1), "…");
+        assert_eq!(clip_line("label", 0), "");
+    }
 }
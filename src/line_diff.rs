@@ -0,0 +1,115 @@
+/*!
+Incremental re-rendering, where a tree that is written repeatedly, such as by a terminal
+dashboard, is compared line by line with its previous output so that only the lines that
+changed need to be written again.
+
+The previous output is held as a list of lines by the caller; each call to
+[`diff_render`](struct.TreeNode.html#method.diff_render) replaces that list with the current
+lines and returns a [`LineDiff`](struct.LineDiff.html) of the index and new content of each line
+that changed, and the number of lines left over from a longer previous output.
+
+# Example
+
+```rust
+use text_trees::{StringTreeNode, TreeFormatting};
+
+let mut tree = StringTreeNode::with_children(
+    "jobs".to_string(),
+    vec!["build: running".to_string(), "test: waiting".to_string()].into_iter(),
+);
+let format = TreeFormatting::default();
+let mut lines: Vec<String> = Vec::new();
+
+let diff = tree.diff_render(&mut lines, &format);
+assert_eq!(diff.changed.len(), 3);
+
+tree.children_vec_mut()[1] = "test: running".to_string().into();
+let diff = tree.diff_render(&mut lines, &format);
+assert_eq!(diff.changed, vec![(2, "'-- test: running".to_string())]);
+assert!(diff.removed().is_empty());
+```
+*/
+
+use crate::{TreeFormatting, TreeNode};
+use std::fmt::Display;
+use std::ops::Range;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The differences between two renderings of a tree, as the lines that must be written to turn
+/// the previous output into the current output.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LineDiff {
+    /// The index, and new content, of each line that differs from the previous output, in
+    /// order; this includes every line beyond the end of the previous output.
+    pub changed: Vec<(usize, String)>,
+    /// The number of lines in the current output.
+    pub line_count: usize,
+    /// The number of lines in the previous output.
+    pub previous_line_count: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl LineDiff {
+    /// Return the differences between the previous and current lines of output.
+    pub fn between(previous: &[String], current: &[String]) -> Self {
+        Self {
+            changed: current
+                .iter()
+                .enumerate()
+                .filter(|(index, line)| previous.get(*index) != Some(*line))
+                .map(|(index, line)| (index, line.clone()))
+                .collect(),
+            line_count: current.len(),
+            previous_line_count: previous.len(),
+        }
+    }
+
+    /// Return `true` if the current output is identical to the previous output.
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.line_count == self.previous_line_count
+    }
+
+    ///
+    /// Return the indices of the lines of the previous output that are beyond the end of the
+    /// current output, and so are to be cleared.
+    ///
+    pub fn removed(&self) -> Range<usize> {
+        self.line_count..self.previous_line_count.max(self.line_count)
+    }
+
+    /// Apply these differences to the previous lines of output, making them the current lines.
+    pub fn apply(&self, lines: &mut Vec<String>) {
+        lines.resize(self.line_count, String::new());
+        for (index, line) in &self.changed {
+            lines[*index].clone_from(line);
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return the differences between the provided lines, previously written, and the lines of
+    /// this tree formatted with the provided format settings; the provided lines are replaced
+    /// with the current lines, ready for the next call. Lines are compared by index, so a line
+    /// inserted near the top of the tree changes every line below it.
+    ///
+    pub fn diff_render(&self, previous: &mut Vec<String>, format: &TreeFormatting) -> LineDiff {
+        let current: Vec<String> = self.lines_with_format(format).collect();
+        let diff = LineDiff::between(previous, &current);
+        *previous = current;
+        diff
+    }
+}
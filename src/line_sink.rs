@@ -0,0 +1,162 @@
+/*!
+Writing a tree as a sequence of discrete lines, rather than as a stream of text, for consumers
+such as GUI lists or log frameworks that would otherwise have to split the output again.
+
+The renderer drives a [`LineSink`](trait.LineSink.html) with each line, without its line
+ending, as it is produced, and flushes it according to the `flush_policy` formatting option. The
+trait is implemented for `Vec<String>`, for the [`IoWrite`](struct.IoWrite.html) adapter, which
+writes each line followed by a line ending, without buffering, and for the sending half of
+`std::sync::mpsc` channels.
+
+# Example
+
+```rust
+use text_trees::{StringTreeNode, TreeFormatting};
+use std::sync::mpsc::channel;
+
+let tree = StringTreeNode::with_children(
+    "root".to_string(),
+    vec!["a".to_string(), "b".to_string()].into_iter(),
+);
+
+let mut lines: Vec<String> = Vec::new();
+tree.write_lines_to(&mut lines, &TreeFormatting::default()).unwrap();
+assert_eq!(lines, vec!["root", "+-- a", "'-- b"]);
+
+let (mut sender, receiver) = channel();
+tree.write_lines_to(&mut sender, &TreeFormatting::default()).unwrap();
+drop(sender);
+assert_eq!(receiver.iter().collect::<Vec<String>>(), lines);
+```
+*/
+
+use crate::{write_tree_inner, FlushCounter, IoWrite, TreeFormatting, TreeNode};
+use std::fmt::{self, Display};
+use std::io::{self, ErrorKind, Result, Write};
+use std::sync::mpsc::{Sender, SyncSender};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A destination for the lines of a tree, each line is provided without its line ending.
+///
+pub trait LineSink {
+    /// Accept the next line of output, returning an error if the line cannot be accepted.
+    fn line(&mut self, line: &str) -> Result<()>;
+
+    /// Flush any lines accepted so far, the default does nothing.
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl LineSink for Vec<String> {
+    fn line(&mut self, line: &str) -> Result<()> {
+        self.push(line.to_string());
+        Ok(())
+    }
+}
+
+///
+/// Each line is written directly to the wrapped writer, which should be a `BufWriter` where
+/// each write is costly, as it is for a `File` or `Stdout`.
+///
+impl<W> LineSink for IoWrite<W>
+where
+    W: Write,
+{
+    fn line(&mut self, line: &str) -> Result<()> {
+        writeln!(self.0, "{}", line)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.0.flush()
+    }
+}
+
+impl LineSink for Sender<String> {
+    fn line(&mut self, line: &str) -> Result<()> {
+        self.send(line.to_string())
+            .map_err(|e| io::Error::new(ErrorKind::BrokenPipe, e))
+    }
+}
+
+impl LineSink for SyncSender<String> {
+    fn line(&mut self, line: &str) -> Result<()> {
+        self.send(line.to_string())
+            .map_err(|e| io::Error::new(ErrorKind::BrokenPipe, e))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Write each line of this tree, formatted with the provided format settings, to the
+    /// provided sink; any error returned by the sink stops the rendering and is returned. The
+    /// sink is flushed according to the `flush_policy` formatting option.
+    ///
+    pub fn write_lines_to(&self, sink: &mut impl LineSink, format: &TreeFormatting) -> Result<()> {
+        self.check_limits(format)?;
+        let mut lines = SinkLines {
+            sink,
+            line: String::new(),
+            error: None,
+            flush: FlushCounter::new(format.flush_policy),
+        };
+        let result = write_tree_inner(self, &mut lines, format, &|node| format.node_label(node));
+        if let Some(error) = lines.error.take() {
+            return Err(error);
+        }
+        result.map_err(io::Error::other)?;
+        if !lines.line.is_empty() {
+            lines.sink.line(&lines.line)?;
+        }
+        lines.sink.flush()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Adapts a `LineSink` to `std::fmt::Write`, splitting text into lines and keeping any error
+/// from the sink so that it can be returned in place of `std::fmt::Error`.
+///
+struct SinkLines<'a, S: LineSink> {
+    sink: &'a mut S,
+    line: String,
+    error: Option<io::Error>,
+    flush: FlushCounter,
+}
+
+impl<S: LineSink> fmt::Write for SinkLines<'_, S> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut rest = s;
+        while let Some(end) = rest.find('\n') {
+            self.line.push_str(&rest[..end]);
+            let mut result = self.sink.line(&self.line);
+            if result.is_ok() && self.flush.wrote(1, self.line.len() + 1) {
+                result = self.sink.flush();
+            }
+            if let Err(e) = result {
+                self.error = Some(e);
+                return Err(fmt::Error);
+            }
+            self.line.clear();
+            rest = &rest[end + 1..];
+        }
+        self.line.push_str(rest);
+        Ok(())
+    }
+}
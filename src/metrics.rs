@@ -0,0 +1,211 @@
+/*!
+Measurement of a tree's output, either without producing it or while writing it.
+
+The [`measure_with_format`](struct.TreeNode.html#method.measure_with_format) method renders the
+tree into a writer that only counts what it is given, so the measurements are exactly those of
+the text that [`write_with_format`](struct.TreeNode.html#method.write_with_format) would write,
+for any orientation and formatting option, without the output being held in memory. The
+[`write_with_format_stats`](struct.TreeNode.html#method.write_with_format_stats) method counts
+the output as it is written.
+*/
+
+use crate::{display_width, write_tree_inner, FmtWrite, IoWrite, TreeFormatting, TreeNode};
+use std::fmt::{self, Display};
+use std::io::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The dimensions of a tree's output, as returned by
+/// [`measure_with_format`](struct.TreeNode.html#method.measure_with_format).
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RenderMetrics {
+    /// The number of lines in the output.
+    pub lines: usize,
+    /// The display width, in columns, of the widest line in the output.
+    pub max_width: usize,
+    /// The number of bytes in the output, including line endings.
+    pub bytes: usize,
+}
+
+///
+/// Statistics describing the output written by
+/// [`write_with_format_stats`](struct.TreeNode.html#method.write_with_format_stats).
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RenderStats {
+    /// The number of lines written.
+    pub lines: usize,
+    /// The number of nodes whose label was written.
+    pub nodes_rendered: usize,
+    /// The number of nodes not written, as they are descendants of a collapsed node, or are not
+    /// visible.
+    pub nodes_elided: usize,
+    /// The display width, in columns, of the widest line written.
+    pub max_width: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return the dimensions of the output of this tree, formatted according to the provided
+    /// format settings, without producing the output. This allows a caller to choose between
+    /// formats, size a display area, or allocate a buffer before writing. An error is returned
+    /// if the tree exceeds the `max_nodes` or `max_depth` limits of the format.
+    ///
+    pub fn measure_with_format(&self, format: &TreeFormatting) -> Result<RenderMetrics> {
+        let mut measure = MeasuringWriter::new(Discard);
+        self.write_to(&mut FmtWrite(&mut measure), format)?;
+        Ok(measure.finish())
+    }
+
+    ///
+    /// Return the display width, in columns, of the widest line of the output of this tree,
+    /// formatted according to the provided format settings, without producing the output. Widths
+    /// follow the same rules as rendering, so wide and combining characters are accounted for;
+    /// this allows a caller to fall back to a more compact format before writing. An error is
+    /// returned if the tree exceeds the `max_nodes` or `max_depth` limits of the format.
+    ///
+    pub fn widest_line_with_format(&self, format: &TreeFormatting) -> Result<usize> {
+        Ok(self.measure_with_format(format)?.max_width)
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` with the provided
+    /// format settings, as [`write_with_format`](struct.TreeNode.html#method.write_with_format)
+    /// does, returning statistics describing the output.
+    ///
+    pub fn write_with_format_stats(
+        &self,
+        to_writer: &mut impl Write,
+        format: &TreeFormatting,
+    ) -> Result<RenderStats> {
+        let mut metrics = RenderMetrics::default();
+        self.render_to(&mut IoWrite(to_writer), format, |w| {
+            let mut measure = MeasuringWriter::new(w);
+            write_tree_inner(self, &mut measure, format, &|node| format.node_label(node))?;
+            metrics = measure.finish();
+            Ok(())
+        })?;
+
+        let mut nodes_rendered = 0;
+        let mut work: Vec<&TreeNode<T>> = vec![self];
+        while let Some(node) = work.pop() {
+            nodes_rendered += 1;
+            work.extend(node.visible_children());
+        }
+        let nodes_elided = 1 + self.descendant_count() - nodes_rendered;
+
+        Ok(RenderStats {
+            lines: metrics.lines,
+            nodes_rendered,
+            nodes_elided,
+            max_width: metrics.max_width,
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A writer that measures everything written to it before passing it on to `inner`.
+///
+#[derive(Debug)]
+struct MeasuringWriter<W: fmt::Write> {
+    inner: W,
+    metrics: RenderMetrics,
+    line_width: usize,
+    line_open: bool,
+}
+
+///
+/// A writer that discards everything written to it.
+///
+#[derive(Debug)]
+struct Discard;
+
+impl fmt::Write for Discard {
+    fn write_str(&mut self, _: &str) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<W: fmt::Write> fmt::Write for MeasuringWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_str(s)?;
+        self.metrics.bytes += s.len();
+        let mut parts = s.split('\n').peekable();
+        while let Some(part) = parts.next() {
+            if !part.is_empty() {
+                self.line_width += display_width(part);
+                self.line_open = true;
+            }
+            if parts.peek().is_some() {
+                self.end_line();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: fmt::Write> MeasuringWriter<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            metrics: Default::default(),
+            line_width: 0,
+            line_open: false,
+        }
+    }
+
+    fn end_line(&mut self) {
+        self.metrics.lines += 1;
+        self.metrics.max_width = self.metrics.max_width.max(self.line_width);
+        self.line_width = 0;
+        self.line_open = false;
+    }
+
+    fn finish(&mut self) -> RenderMetrics {
+        if self.line_open {
+            self.end_line();
+        }
+        self.metrics
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Write;
+
+    #[test]
+    fn test_measuring_writer() {
+        let mut measure = MeasuringWriter::new(String::new());
+        write!(measure, "ab").unwrap();
+        write!(measure, "c\n\n├─ 日本\nlast").unwrap();
+        assert_eq!(
+            measure.finish(),
+            RenderMetrics {
+                lines: 4,
+                max_width: 7,
+                bytes: "abc\n\n├─ 日本\nlast".len(),
+            }
+        );
+        assert_eq!(measure.inner, "abc\n\n├─ 日本\nlast");
+    }
+}
@@ -0,0 +1,327 @@
+/*!
+Import and export of trees in the Newick format, as used for phylogenetic trees.
+
+A Newick tree lists the children of each node in parentheses before its name, and each node may
+be followed by a branch length, for example `((A:0.1,B:0.2)E:0.5,C:0.3)F;`. Trees are parsed by
+[`from_newick`](fn.from_newick.html) into nodes of [`NewickLabel`](struct.NewickLabel.html),
+which write their name and carry their branch length to be written as an annotation, and any
+tree is written by [`to_newick`](struct.TreeNode.html#method.to_newick).
+
+Names may be quoted with apostrophes, where a doubled apostrophe is a literal one; in unquoted
+names underscores are read as spaces. Comments, in square brackets, and whitespace between
+names are ignored. Neither parsing nor writing recurses, so very deep trees may be converted
+safely.
+
+# Example
+
+```rust
+use text_trees::{from_newick, NewickLabel, TreeFormatting};
+
+let tree = from_newick("((A:0.1,B:0.2)E:0.5,C:0.3)F;").unwrap();
+
+let result = tree
+    .to_string_with_annotations(&TreeFormatting::default(), NewickLabel::annotation)
+    .unwrap();
+assert_eq!(
+    result,
+    "F\n+-- E :0.5\n|   +-- A :0.1\n|   '-- B :0.2\n'-- C :0.3\n"
+);
+assert_eq!(tree.to_newick_with_lengths(NewickLabel::length), "((A:0.1,B:0.2)E:0.5,C:0.3)F;");
+```
+*/
+
+use crate::TreeNode;
+use std::fmt::{self, Display, Formatter};
+use std::io::{Error, ErrorKind, Result};
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The data of each node of a tree parsed from the Newick format, a name, which may be empty,
+/// and an optional branch length, the length of the edge from the node's parent.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NewickLabel {
+    name: String,
+    length: Option<f64>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Parse a tree in the Newick format, which must be terminated by a semicolon, returning an error
+/// of kind `InvalidData` that identifies the offset of the error if the text is not a single,
+/// well-formed, tree.
+///
+pub fn from_newick(text: &str) -> Result<TreeNode<NewickLabel>> {
+    let mut parser = Parser {
+        text,
+        chars: text.char_indices().peekable(),
+    };
+    // The children of each open parenthesis; the first entry collects nothing but marks the
+    // outermost level.
+    let mut stack: Vec<Vec<TreeNode<NewickLabel>>> = vec![Vec::new()];
+    loop {
+        parser.skip_ignored()?;
+        if parser.next_if('(') {
+            stack.push(Vec::new());
+            continue;
+        }
+        let mut node = TreeNode::new(parser.label()?);
+        loop {
+            parser.skip_ignored()?;
+            let offset = parser.offset();
+            match parser.chars.next() {
+                Some((_, ',')) if stack.len() > 1 => {
+                    stack.last_mut().unwrap().push(node);
+                    break;
+                }
+                Some((_, ')')) if stack.len() > 1 => {
+                    let mut children = stack.pop().unwrap();
+                    children.push(node);
+                    node = TreeNode::with_child_nodes(parser.label()?, children.into_iter());
+                }
+                Some((_, ';')) if stack.len() == 1 => {
+                    parser.skip_ignored()?;
+                    return match parser.chars.peek() {
+                        None => Ok(node),
+                        Some((offset, _)) => Err(invalid(*offset, "unexpected text after ';'")),
+                    };
+                }
+                Some((_, c)) => {
+                    return Err(invalid(offset, &format!("unexpected character {:?}", c)));
+                }
+                None if stack.len() > 1 => return Err(invalid(offset, "unclosed '('")),
+                None => return Err(invalid(offset, "missing ';'")),
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for NewickLabel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl From<&str> for NewickLabel {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl NewickLabel {
+    /// Construct a new label with the provided name and no branch length.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            length: None,
+        }
+    }
+
+    /// Construct a new label with the provided name and branch length.
+    pub fn with_length(name: impl Into<String>, length: f64) -> Self {
+        Self {
+            name: name.into(),
+            length: Some(length),
+        }
+    }
+
+    /// Return the name of this node, which may be empty.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the branch length of this node, if any.
+    pub fn length(&self) -> Option<f64> {
+        self.length
+    }
+
+    ///
+    /// Return the branch length as it is written in the Newick format, such as `":0.5"`, for use
+    /// with [`write_with_annotations`](struct.TreeNode.html#method.write_with_annotations).
+    ///
+    pub fn annotation(&self) -> Option<String> {
+        self.length.map(|length| format!(":{}", length))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return this tree in the Newick format, without branch lengths, where the name of each
+    /// node is written by its `Display` implementation. Names containing whitespace, or any of
+    /// the characters that delimit Newick names, are quoted. Collapsed and hidden nodes are
+    /// included as they are part of the tree's data.
+    ///
+    pub fn to_newick(&self) -> String {
+        self.to_newick_with_lengths(|_| None)
+    }
+
+    ///
+    /// Return this tree in the Newick format, as
+    /// [`to_newick`](struct.TreeNode.html#method.to_newick) does, where the branch length of
+    /// each node is that returned by `lengths`.
+    ///
+    pub fn to_newick_with_lengths(&self, lengths: impl Fn(&T) -> Option<f64>) -> String {
+        let mut buffer = String::new();
+        let mut work: Vec<Step<'_, T>> = vec![Step::Node(self)];
+        while let Some(step) = work.pop() {
+            match step {
+                Step::Node(node) if node.has_children() => {
+                    buffer.push('(');
+                    work.push(Step::Close(node));
+                    for (index, child) in node.children.iter().enumerate().rev() {
+                        work.push(Step::Node(child));
+                        if index > 0 {
+                            work.push(Step::Comma);
+                        }
+                    }
+                }
+                Step::Node(node) => write_label(&mut buffer, node, &lengths),
+                Step::Close(node) => {
+                    buffer.push(')');
+                    write_label(&mut buffer, node, &lengths);
+                }
+                Step::Comma => buffer.push(','),
+            }
+        }
+        buffer.push(';');
+        buffer
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+const DELIMITERS: &[char] = &['(', ')', '[', ']', '\'', ':', ';', ','];
+
+#[derive(Debug)]
+struct Parser<'a> {
+    text: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+#[derive(Debug)]
+enum Step<'a, T>
+where
+    T: Display,
+{
+    Node(&'a TreeNode<T>),
+    Close(&'a TreeNode<T>),
+    Comma,
+}
+
+impl Parser<'_> {
+    fn offset(&mut self) -> usize {
+        self.chars
+            .peek()
+            .map(|(offset, _)| *offset)
+            .unwrap_or(self.text.len())
+    }
+
+    fn next_if(&mut self, c: char) -> bool {
+        self.chars.next_if(|(_, next)| *next == c).is_some()
+    }
+
+    /// Skip whitespace and comments.
+    fn skip_ignored(&mut self) -> Result<()> {
+        loop {
+            while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+            let offset = self.offset();
+            if !self.next_if('[') {
+                return Ok(());
+            }
+            if !self.chars.any(|(_, c)| c == ']') {
+                return Err(invalid(offset, "unclosed comment"));
+            }
+        }
+    }
+
+    /// Parse a, possibly empty, name and the optional branch length that follows it.
+    fn label(&mut self) -> Result<NewickLabel> {
+        self.skip_ignored()?;
+        let offset = self.offset();
+        let mut name = String::new();
+        if self.next_if('\'') {
+            loop {
+                match self.chars.next() {
+                    Some((_, '\'')) if !self.next_if('\'') => break,
+                    Some((_, c)) => name.push(c),
+                    None => return Err(invalid(offset, "unclosed quoted name")),
+                }
+            }
+        } else {
+            while let Some((_, c)) = self
+                .chars
+                .next_if(|(_, c)| !c.is_whitespace() && !DELIMITERS.contains(c))
+            {
+                name.push(if c == '_' { ' ' } else { c });
+            }
+        }
+
+        self.skip_ignored()?;
+        if !self.next_if(':') {
+            return Ok(NewickLabel::new(name));
+        }
+        self.skip_ignored()?;
+        let offset = self.offset();
+        let mut length = String::new();
+        while let Some((_, c)) = self
+            .chars
+            .next_if(|(_, c)| !c.is_whitespace() && !DELIMITERS.contains(c))
+        {
+            length.push(c);
+        }
+        match length.parse() {
+            Ok(length) => Ok(NewickLabel::with_length(name, length)),
+            Err(_) => Err(invalid(offset, "invalid branch length")),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn write_label<T>(buffer: &mut String, node: &TreeNode<T>, lengths: &impl Fn(&T) -> Option<f64>)
+where
+    T: Display,
+{
+    let name = node.label();
+    if name
+        .chars()
+        .any(|c| c == '_' || c.is_whitespace() || DELIMITERS.contains(&c))
+    {
+        buffer.push('\'');
+        buffer.push_str(&name.replace('\'', "''"));
+        buffer.push('\'');
+    } else {
+        buffer.push_str(&name);
+    }
+    if let Some(length) = lengths(&node.data) {
+        buffer.push_str(&format!(":{}", length));
+    }
+}
+
+fn invalid(offset: usize, message: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("invalid Newick at offset {}: {}", offset, message),
+    )
+}
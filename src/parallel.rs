@@ -0,0 +1,166 @@
+/*!
+Parallel rendering of trees, available with the `rayon` feature.
+
+The tree lines that precede any node below a child of the root depend only on the nodes within
+that child's subtree, and on whether the child is the root's last. Each subtree of the root can
+therefore be written into its own buffer, in parallel, and the buffers joined in order to produce
+exactly the output of [`write_with_format`](struct.TreeNode.html#method.write_with_format).
+
+Trees may also be built in parallel, by
+[`build_parallel`](struct.TreeNode.html#method.build_parallel), which expands the seeds of the
+children of each node in parallel; the result is the same tree as that built by
+[`unfold`](struct.TreeNode.html#method.unfold).
+*/
+
+use crate::{
+    write_tree_inner, IoWrite, RowPrefix, Rows, TreeFormatting, TreeNode, TreeOrientation,
+};
+use rayon::prelude::*;
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display + Sync,
+{
+    ///
+    /// Return a string containing the generated tree text formatted according to the provided
+    /// format settings, where the subtrees of the root are written in parallel. An error is
+    /// returned if the tree exceeds the `max_nodes` or `max_depth` limits of the format.
+    ///
+    pub fn to_string_with_format_parallel(&self, format: &TreeFormatting) -> io::Result<String> {
+        self.render_to_string(format, |w| {
+            for part in render_parallel(self, format)? {
+                w.write_str(&part)?;
+            }
+            Ok(())
+        })
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` with the provided
+    /// format settings, where the subtrees of the root are written in parallel into buffers
+    /// which are then written in order.
+    ///
+    /// _Note_: the `align_depths` formatting option, and the `Classic` and `OrgChart` orientations,
+    /// all require the entire tree to be laid out together, and so these are written sequentially;
+    /// as is a tree written with a `root_connector`, a `max_line_width`, `elastic_tabs`,
+    /// `line_transforms`, or with `collapse_repeated_leaves`.
+    ///
+    pub fn write_with_format_parallel(
+        &self,
+        to_writer: &mut impl Write,
+        format: &TreeFormatting,
+    ) -> io::Result<()> {
+        self.render_to(&mut IoWrite(to_writer), format, |w| {
+            for part in render_parallel(self, format)? {
+                w.write_str(&part)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display + Send,
+{
+    ///
+    /// Construct a new tree from a seed value, as [`unfold`](#method.unfold) does, where the
+    /// seeds of the children of each node are expanded in parallel; the order of each node's
+    /// children is that of the seeds returned for it.
+    ///
+    /// _Note_: unlike `unfold` each level of the tree is built by a nested call, and so a very
+    /// deep tree may overflow the stack of the threads in the `rayon` pool.
+    ///
+    pub fn build_parallel<S>(seed: S, expand_fn: impl Fn(S) -> (T, Vec<S>) + Sync) -> Self
+    where
+        S: Send,
+    {
+        build_node(seed, &expand_fn)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return the text of the tree in parts, the root and then each subtree of the root, which are
+/// written in parallel; any error from a label's `Display` implementation is returned.
+///
+fn render_parallel<T>(
+    node: &TreeNode<T>,
+    format: &TreeFormatting,
+) -> Result<Vec<String>, fmt::Error>
+where
+    T: Display + Sync,
+{
+    if format.orientation != TreeOrientation::TopDown
+        || format.align_depths
+        || format.root_connector.is_some()
+        || format.max_line_width.is_some()
+        || format.collapse_repeated_leaves
+        || format.elastic_tabs.is_some()
+        || !format.line_transforms.is_empty()
+    {
+        let mut result = String::new();
+        write_tree_inner(node, &mut result, format, &|node| format.node_label(node))?;
+        return Ok(vec![result]);
+    }
+
+    let mut root = String::new();
+    write_rows(&mut root, Rows::new(node, format).take(1), format)?;
+
+    let children: Vec<&TreeNode<T>> = node.visible_children().collect();
+    let subtrees: Vec<String> = children
+        .par_iter()
+        .enumerate()
+        .map(|(index, child)| {
+            let mut result = String::new();
+            write_rows(
+                &mut result,
+                Rows::root_child(child, index == children.len() - 1, format),
+                format,
+            )?;
+            Ok(result)
+        })
+        .collect::<Result<Vec<String>, fmt::Error>>()?;
+
+    Ok(std::iter::once(root).chain(subtrees).collect())
+}
+
+fn write_rows<'a, T>(
+    w: &mut impl fmt::Write,
+    rows: impl Iterator<Item = (RowPrefix, &'a TreeNode<T>)>,
+    format: &TreeFormatting,
+) -> fmt::Result
+where
+    T: 'a,
+    T: Display,
+{
+    for (row, node) in rows {
+        writeln!(w, "{}{}", row, row.repeated_label(format.node_label(node)))?;
+    }
+    Ok(())
+}
+
+fn build_node<S, T, F>(seed: S, expand_fn: &F) -> TreeNode<T>
+where
+    S: Send,
+    T: Display + Send,
+    F: Fn(S) -> (T, Vec<S>) + Sync,
+{
+    let (data, seeds) = expand_fn(seed);
+    let children: Vec<TreeNode<T>> = seeds
+        .into_par_iter()
+        .map(|seed| build_node(seed, expand_fn))
+        .collect();
+    TreeNode::with_child_nodes(data, children.into_iter())
+}
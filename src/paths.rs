@@ -0,0 +1,128 @@
+/*!
+Building string trees from paths, where each component of a path is a node and each node is the
+parent of the next component.
+
+A single path is converted into a chain of single-child nodes with `From<&Path>`, and further
+paths are merged into an existing tree with
+[`insert_path`](struct.TreeNode.html#method.insert_path), which reuses the children whose labels
+match the path's components and creates those that are missing; this is how a tree is built from
+a flat list of file names. Paths that are not file system paths, such as module paths, are
+inserted with
+[`insert_path_with_separator`](struct.TreeNode.html#method.insert_path_with_separator).
+
+# Example
+
+```rust
+use std::path::Path;
+use text_trees::StringTreeNode;
+
+let mut tree = StringTreeNode::from(Path::new("src/bin"));
+tree.insert_path(Path::new("bin"), "main.rs".to_string());
+tree.insert_path(Path::new(""), "lib.rs".to_string());
+
+assert_eq!(tree.to_string(), "src\n+-- bin\n|   '-- main.rs\n'-- lib.rs\n");
+```
+*/
+
+use crate::{StringTreeNode, TreeNode};
+use std::path::{Component, Path};
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl From<&Path> for TreeNode<String> {
+    ///
+    /// Construct a chain of nodes, one for each component of the path, where the root is the
+    /// first component and each node has the next component as its only child. Current
+    /// directory components, `"."`, are skipped; a path with no other components results in a
+    /// single node with an empty label.
+    ///
+    fn from(path: &Path) -> Self {
+        let mut labels = component_labels(path).rev();
+        let leaf = Self::new(labels.next().unwrap_or_default());
+        labels.fold(leaf, |child, label| {
+            Self::with_child_nodes(label, std::iter::once(child))
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl TreeNode<String> {
+    ///
+    /// Insert `leaf_value` into this tree below the provided path, which is relative to this
+    /// node; for each component of the path the child with a matching label is used, or created
+    /// if there is none, and `leaf_value` is then found, or created, among the children of the
+    /// last. A mutable reference to the leaf node is returned.
+    ///
+    /// As existing nodes are reused, inserting the same path and leaf more than once has no
+    /// further effect. Current directory components, `"."`, are skipped.
+    ///
+    pub fn insert_path(&mut self, path: &Path, leaf_value: String) -> &mut StringTreeNode {
+        let mut node = self;
+        for label in component_labels(path) {
+            node = node.child_labeled(label);
+        }
+        node.child_labeled(leaf_value)
+    }
+
+    ///
+    /// Insert a path, given as a string whose components are divided by `separator`, into this
+    /// tree; the path is relative to this node and, for each component, the child with a
+    /// matching label is used, or created if there is none. A mutable reference to the node of
+    /// the last component is returned, or this node if the path has no components.
+    ///
+    /// Empty components, such as those produced by a leading or repeated separator, are
+    /// skipped.
+    ///
+    /// ```rust
+    /// use text_trees::StringTreeNode;
+    ///
+    /// let mut tree = StringTreeNode::new("crate".to_string());
+    /// tree.insert_path_with_separator("std::fs::File", "::");
+    /// tree.insert_path_with_separator("std::io", "::").push("Write".to_string());
+    ///
+    /// assert_eq!(
+    ///     tree.to_string(),
+    ///     "crate\n'-- std\n    +-- fs\n    |   '-- File\n    '-- io\n        '-- Write\n"
+    /// );
+    /// ```
+    ///
+    pub fn insert_path_with_separator(
+        &mut self,
+        path: &str,
+        separator: &str,
+    ) -> &mut StringTreeNode {
+        let mut node = self;
+        for label in path.split(separator).filter(|label| !label.is_empty()) {
+            node = node.child_labeled(label.to_string());
+        }
+        node
+    }
+
+    ///
+    /// Return the first child whose label is `label`, pushing a new child with that label if
+    /// there is none.
+    ///
+    fn child_labeled(&mut self, label: String) -> &mut StringTreeNode {
+        let index = match self.children.iter().position(|child| child.data == label) {
+            Some(index) => index,
+            None => {
+                self.push(label);
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[index]
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn component_labels(path: &Path) -> impl DoubleEndedIterator<Item = String> + '_ {
+    path.components()
+        .filter(|component| *component != Component::CurDir)
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+}
@@ -0,0 +1,217 @@
+/*!
+An immutable tree, [`PersistentTree`](struct.PersistentTree.html), where each change returns a
+new tree and leaves the original unchanged; for undo and redo, or for keeping the history of a
+tree's state.
+
+A new tree shares every subtree, and every data value, not on the path from the root to the
+changed node with the tree it was made from, so keeping many versions of a large tree is
+inexpensive. Nodes are addressed by their path, the index of each child from the root, as for
+[`subtree_at`](struct.TreeNode.html#method.subtree_at). A tree is written by way of
+[`as_tree_node`](struct.PersistentTree.html#method.as_tree_node), which borrows its data values
+as a `TreeNode` so that all of the formatting options, and writing methods, apply.
+
+# Example
+
+```rust
+use text_trees::PersistentTree;
+
+let v1 = PersistentTree::with_children("root", vec!["a", "b"].into_iter());
+let v2 = v1.push(&[0], "a1").unwrap();
+let v3 = v2.remove(&[1]).unwrap();
+
+assert_eq!(v1.to_string(), "root\n+-- a\n'-- b\n");
+assert_eq!(v3.to_string(), "root\n'-- a\n    '-- a1\n");
+assert!(v2.children()[1].ptr_eq(&v1.children()[1]));
+```
+*/
+
+use crate::TreeNode;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A tree that is never changed in place; the methods that change a tree return a new tree which
+/// shares its unchanged subtrees with this one. Cloning a tree is inexpensive, as only a
+/// reference count is changed.
+///
+#[derive(Debug, PartialEq)]
+pub struct PersistentTree<T>(Arc<Node<T>>);
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T> Clone for PersistentTree<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Display for PersistentTree<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.as_tree_node().fmt(f)
+    }
+}
+
+impl<T> PersistentTree<T> {
+    /// Construct a new tree node with the provided data value.
+    pub fn new(data: T) -> Self {
+        Self::with_child_nodes(data, std::iter::empty())
+    }
+
+    /// Construct a new tree node with the provided data value and an iterator that provides
+    /// child data items.
+    pub fn with_children(data: T, children: impl Iterator<Item = T>) -> Self {
+        Self::with_child_nodes(data, children.map(PersistentTree::new))
+    }
+
+    /// Construct a new tree node with the provided data value and an iterator that provides
+    /// existing trees as child nodes.
+    pub fn with_child_nodes(data: T, children: impl Iterator<Item = PersistentTree<T>>) -> Self {
+        Self(Arc::new(Node {
+            data: Arc::new(data),
+            children: children.collect(),
+        }))
+    }
+
+    /// Return a reference to the data item for this node.
+    pub fn data(&self) -> &T {
+        &self.0.data
+    }
+
+    /// Return the children of this node, in order.
+    pub fn children(&self) -> &[PersistentTree<T>] {
+        &self.0.children
+    }
+
+    /// Return the node at the provided path, if any; the empty path is this node.
+    pub fn subtree_at(&self, path: &[usize]) -> Option<&PersistentTree<T>> {
+        path.iter()
+            .try_fold(self, |node, index| node.children().get(*index))
+    }
+
+    /// Return `true` if both trees are the same shared tree, rather than only equal trees.
+    pub fn ptr_eq(&self, other: &PersistentTree<T>) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    ///
+    /// Return a new tree where the node at the provided path has a new last child with the
+    /// provided data value, or `None` if there is no node at the path.
+    ///
+    pub fn push(&self, path: &[usize], data: T) -> Option<Self> {
+        self.with_updated(path, |node| {
+            node.children.push(Self::new(data));
+            true
+        })
+    }
+
+    ///
+    /// Return a new tree without the node, and its descendants, at the provided path, or `None`
+    /// if there is no node at the path or the path is empty.
+    ///
+    pub fn remove(&self, path: &[usize]) -> Option<Self> {
+        let (index, parent) = path.split_last()?;
+        self.with_updated(parent, |node| {
+            let found = *index < node.children.len();
+            if found {
+                let _ = node.children.remove(*index);
+            }
+            found
+        })
+    }
+
+    ///
+    /// Return a new tree where the node at the provided path has the provided data value, and
+    /// the same children, or `None` if there is no node at the path.
+    ///
+    pub fn replace(&self, path: &[usize], data: T) -> Option<Self> {
+        self.with_updated(path, |node| {
+            node.data = Arc::new(data);
+            true
+        })
+    }
+
+    ///
+    /// Return a copy of this node, changed by `update`, and of each node on the path from this
+    /// node to it; `None` is returned if there is no node at the path, or `update` fails. The
+    /// copies are made from the changed node up, rather than by recursion, so that very deep
+    /// paths do not overflow the stack.
+    ///
+    fn with_updated(
+        &self,
+        path: &[usize],
+        update: impl FnOnce(&mut Node<T>) -> bool,
+    ) -> Option<Self> {
+        let copy = |tree: &Self| Node {
+            data: tree.0.data.clone(),
+            children: tree.0.children.clone(),
+        };
+
+        // The nodes on the path, from this node to the node to change.
+        let mut spine: Vec<&Self> = Vec::with_capacity(path.len() + 1);
+        let mut node = self;
+        spine.push(node);
+        for index in path {
+            node = node.children().get(*index)?;
+            spine.push(node);
+        }
+
+        let mut changed = copy(spine.pop()?);
+        if !update(&mut changed) {
+            return None;
+        }
+        let mut changed = Self(Arc::new(changed));
+        for (parent, index) in spine.into_iter().zip(path).rev() {
+            let mut parent = copy(parent);
+            parent.children[*index] = changed;
+            changed = Self(Arc::new(parent));
+        }
+        Some(changed)
+    }
+}
+
+impl<T> PersistentTree<T>
+where
+    T: Display,
+{
+    ///
+    /// Return a `TreeNode` that borrows the data values of this tree, so that it may be written
+    /// with any of the formatting options and writing methods of `TreeNode`.
+    ///
+    pub fn as_tree_node(&self) -> TreeNode<&T> {
+        TreeNode::unfold(self, |node| (node.data(), node.children().iter().collect()))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug, PartialEq)]
+struct Node<T> {
+    data: Arc<T>,
+    children: Vec<PersistentTree<T>>,
+}
+
+impl<T> Drop for Node<T> {
+    ///
+    /// Drop the children that are not shared iteratively, rather than recursively, so that very
+    /// deep trees do not overflow the stack.
+    ///
+    fn drop(&mut self) {
+        let mut work = std::mem::take(&mut self.children);
+        while let Some(child) = work.pop() {
+            if let Ok(mut node) = Arc::try_unwrap(child.0) {
+                work.append(&mut node.children);
+            }
+        }
+    }
+}
@@ -0,0 +1,258 @@
+/*!
+A tree of tasks, each with a progress status, that is redrawn in place in a terminal as the
+statuses change.
+
+Each node of a [`ProgressTree`](type.ProgressTree.html) holds a label and a
+[`ProgressStatus`](enum.ProgressStatus.html), which is written as a badge beside the label. The
+[`ProgressRenderer`](struct.ProgressRenderer.html) writes the tree to a terminal and, on each
+subsequent draw, moves the cursor back to the first line of the tree and rewrites only those
+lines that have changed; if the tree has fewer lines than before the remaining lines are cleared.
+
+The renderer uses ANSI escape sequences to move the cursor, and so it should only be used when
+writing to a terminal; the tree must also fit within the height of the terminal as lines that
+have scrolled out of view cannot be redrawn.
+
+# Example
+
+```rust
+use text_trees::progress::{ProgressNode, ProgressRenderer, ProgressStatus, ProgressTree};
+use text_trees::TreeFormatting;
+
+let mut tree = ProgressTree::with_children(
+    ProgressNode::new("build"),
+    vec![ProgressNode::new("compile"), ProgressNode::new("test")].into_iter(),
+);
+let mut renderer = ProgressRenderer::new(Vec::new(), &TreeFormatting::default());
+renderer.draw(&tree).unwrap();
+
+assert!(tree.set_status(&[0], ProgressStatus::Running));
+renderer.draw(&tree).unwrap();
+```
+*/
+
+use crate::{LineDiff, TreeFormatting, TreeNode};
+use std::fmt::{self, Display, Formatter};
+use std::io::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The status of a single task in a progress tree.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ProgressStatus {
+    /// The task has not yet started, written as `'·'`.
+    #[default]
+    Pending,
+    /// The task is in progress, written as `'▶'`.
+    Running,
+    /// The task completed successfully, written as `'✔'`.
+    Ok,
+    /// The task failed, written as `'✖'`.
+    Failed,
+}
+
+///
+/// The data of each node in a progress tree, a label and the current status of the task.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProgressNode {
+    label: String,
+    status: ProgressStatus,
+}
+
+///
+/// A tree of tasks, where the label of each node is written with its status as a badge.
+///
+pub type ProgressTree = TreeNode<ProgressNode>;
+
+///
+/// Writes a progress tree to a terminal, redrawing it in place each time
+/// [`draw`](struct.ProgressRenderer.html#method.draw) is called.
+///
+/// Each call to `draw` writes the entire update and then flushes the writer, so that a partial
+/// redraw is never visible; there is no need to wrap the writer in a `BufWriter`.
+///
+#[derive(Debug)]
+pub struct ProgressRenderer<W>
+where
+    W: Write,
+{
+    writer: W,
+    format: TreeFormatting,
+    /// The lines written by the previous draw, the cursor is on the line following them.
+    previous: Vec<String>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for ProgressStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl ProgressStatus {
+    /// Return the symbol written for this status.
+    pub fn symbol(&self) -> char {
+        match self {
+            Self::Pending => '·',
+            Self::Running => '▶',
+            Self::Ok => '✔',
+            Self::Failed => '✖',
+        }
+    }
+
+    /// Returns `true` if the task has completed, successfully or not, else `false`.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Ok | Self::Failed)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for ProgressNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+impl From<&str> for ProgressNode {
+    fn from(label: &str) -> Self {
+        Self::new(label)
+    }
+}
+
+impl ProgressNode {
+    /// Construct a new, pending, task with the provided label.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self::with_status(label, Default::default())
+    }
+
+    /// Construct a new task with the provided label and status.
+    pub fn with_status(label: impl Into<String>, status: ProgressStatus) -> Self {
+        Self {
+            label: label.into(),
+            status,
+        }
+    }
+
+    /// Return the label of this task.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Return the current status of this task.
+    pub fn status(&self) -> ProgressStatus {
+        self.status
+    }
+
+    /// Set the current status of this task.
+    pub fn set_status(&mut self, status: ProgressStatus) {
+        self.status = status;
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl TreeNode<ProgressNode> {
+    ///
+    /// Set the status of the task at the provided index path, returning `false` if there is no
+    /// node at that path.
+    ///
+    pub fn set_status(&mut self, path: &[usize], status: ProgressStatus) -> bool {
+        match self.subtree_at_mut(path) {
+            Some(node) => {
+                node.data.set_status(status);
+                true
+            }
+            None => false,
+        }
+    }
+
+    ///
+    /// Returns `true` if every task in this tree has completed, successfully or not, else
+    /// `false`.
+    ///
+    pub fn is_finished(&self) -> bool {
+        self.iter_with_depth()
+            .all(|(_, task)| task.status().is_finished())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<W> ProgressRenderer<W>
+where
+    W: Write,
+{
+    ///
+    /// Construct a new renderer that writes to `writer` with the provided format settings; the
+    /// status of each task is written as a badge, positioned according to the format's
+    /// `badge_position`.
+    ///
+    pub fn new(writer: W, format: &TreeFormatting) -> Self {
+        Self {
+            writer,
+            format: format.clone(),
+            previous: Default::default(),
+        }
+    }
+
+    ///
+    /// Write the provided tree, replacing the tree written by the previous call, if any. Only
+    /// the lines that differ from those previously written are rewritten.
+    ///
+    pub fn draw(&mut self, tree: &ProgressTree) -> Result<()> {
+        let text = tree.to_string_with_badges(&self.format, &|task: &ProgressNode| {
+            Some(task.status().to_string())
+        })?;
+        let lines: Vec<String> = text.lines().map(str::to_string).collect();
+
+        let diff = LineDiff::between(&self.previous, &lines);
+        let mut changed = diff.changed.iter().peekable();
+        let mut update = String::new();
+        if !self.previous.is_empty() {
+            // Move to the start of the first line previously written.
+            update.push_str(&format!("{}[{}F", ESC, self.previous.len()));
+        }
+        for index in 0..diff.line_count {
+            match changed.next_if(|(changed, _)| *changed == index) {
+                Some((_, line)) => update.push_str(&format!("{}[2K{}\n", ESC, line)),
+                None => update.push_str(&format!("{}[1E", ESC)),
+            }
+        }
+        if !diff.removed().is_empty() {
+            // Clear the lines left over from a longer tree.
+            update.push_str(&format!("{}[J", ESC));
+        }
+
+        self.writer.write_all(update.as_bytes())?;
+        self.writer.flush()?;
+        self.previous = lines;
+        Ok(())
+    }
+
+    ///
+    /// Forget the lines previously written, so that the next draw writes the tree below any
+    /// output that follows, rather than replacing it.
+    ///
+    pub fn reset(&mut self) {
+        self.previous.clear();
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+const ESC: char = '\x1b';
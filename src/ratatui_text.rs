@@ -0,0 +1,159 @@
+/*!
+Rendering of trees into [ratatui](https://ratatui.rs) styled text, available with the `ratatui`
+feature.
+
+Rather than writing the tree to a string, and losing the distinction between the tree lines and
+labels, the methods here produce a `ratatui::text::Text` where each line consists of separately
+styled spans for the prefix string, the tree lines, and the label.
+*/
+
+use crate::{collect_rows, TreeFormatting, TreeNode, TreeOrientation};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span, Text};
+use std::fmt::Display;
+use std::io::Result;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The styles applied to the different parts of each line in the text produced by
+/// [`to_text_with_styles`](struct.TreeNode.html#method.to_text_with_styles).
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextStyles {
+    /// The style applied to the format's prefix string, if any.
+    pub prefix: Style,
+    /// The style applied to the tree lines, and the spacing between lines and labels.
+    pub lines: Style,
+    /// The style applied to node labels.
+    pub label: Style,
+    /// The style applied to every other line, starting with the second, beneath the styles
+    /// above; a subtle background color aids the tracking of lines across very wide trees. The
+    /// default style leaves every line unchanged.
+    pub alternate_rows: Style,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return this tree as `ratatui` text, formatted according to the provided format
+    /// settings, with no styling.
+    ///
+    pub fn to_text(&self, format: &TreeFormatting) -> Result<Text<'static>> {
+        self.to_text_with_styles(format, &Default::default())
+    }
+
+    ///
+    /// Return this tree as `ratatui` text, formatted according to the provided format settings,
+    /// with the prefix string, tree lines, and labels in each line styled separately. An error
+    /// is returned if the tree exceeds the `max_nodes` or `max_depth` limits of the format.
+    ///
+    /// _Note_: the `Classic` and `OrgChart` orientations do not separate tree lines from labels,
+    /// and so each line is a single span with the `lines` style.
+    ///
+    /// Each line is clipped to the `max_line_width`, and has the `line_transforms` applied, as
+    /// when the tree is written; text that a transform changes takes the style of the part of the
+    /// line in which the change starts.
+    ///
+    /// Alternate rows are counted by the lines produced, so that a line joining the root to its
+    /// children, as added by the
+    /// [`root_connector`](struct.TreeFormatting.html#structfield.root_connector) option, is
+    /// counted as a row.
+    ///
+    pub fn to_text_with_styles(
+        &self,
+        format: &TreeFormatting,
+        styles: &TextStyles,
+    ) -> Result<Text<'static>> {
+        if format.orientation != TreeOrientation::TopDown {
+            let text = self.to_string_with_format(format)?;
+            return Ok(Text::from(
+                text.lines()
+                    .map(|line| Line::from(Span::styled(line.to_string(), styles.lines)))
+                    .enumerate()
+                    .map(|(index, line)| shade_alternate(index, line, styles))
+                    .collect::<Vec<Line<'static>>>(),
+            ));
+        }
+
+        self.check_limits(format)?;
+        let prefix_len = format
+            .prefix_str
+            .as_ref()
+            .map(String::len)
+            .unwrap_or_default();
+        let rows = collect_rows(self, format);
+        Ok(Text::from(
+            rows.into_iter()
+                .flat_map(|(mut row, node)| {
+                    let connector = row
+                        .connector
+                        .take()
+                        .map(|connector| styled_line(format, vec![(connector, styles.lines)]));
+                    let (prefix, lines) = row.lines.split_at(prefix_len);
+                    let mut parts: Vec<(String, Style)> = Vec::with_capacity(3);
+                    if !prefix.is_empty() {
+                        parts.push((prefix.to_string(), styles.prefix));
+                    }
+                    parts.push((format!("{}{}", lines, row.label_space), styles.lines));
+                    parts.push((row.repeated_label(format.node_label(node)), styles.label));
+                    connector
+                        .into_iter()
+                        .chain(std::iter::once(styled_line(format, parts)))
+                })
+                .enumerate()
+                .map(|(index, line)| shade_alternate(index, line, styles))
+                .collect::<Vec<Line<'static>>>(),
+        ))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return the line made of the styled parts, as it is written, that is clipped and transformed
+/// by `output_line`. Each part keeps its style while the output starts with its text; the rest
+/// of the output, if any, takes the style of the first part that does not match, or else of the
+/// last part, so that the ellipsis of a clipped line takes the style of the part clipped.
+///
+fn styled_line(format: &TreeFormatting, parts: Vec<(String, Style)>) -> Line<'static> {
+    let line: String = parts.iter().map(|(text, _)| text.as_str()).collect();
+    let output = format.output_line(&line);
+    let mut rest = output.as_str();
+    let mut rest_style = Style::default();
+    let mut spans: Vec<Span<'static>> = Vec::with_capacity(parts.len());
+    for (text, style) in parts.into_iter().filter(|(text, _)| !text.is_empty()) {
+        rest_style = style;
+        if !rest.starts_with(&text) {
+            break;
+        }
+        rest = &rest[text.len()..];
+        spans.push(Span::styled(text, style));
+    }
+    if !rest.is_empty() {
+        spans.push(Span::styled(rest.to_string(), rest_style));
+    }
+    Line::from(spans)
+}
+
+///
+/// Apply the alternate row style to the line if it is at an odd index, the line style is applied
+/// to the whole width the line is rendered into, and not only to its spans.
+///
+fn shade_alternate(index: usize, line: Line<'static>, styles: &TextStyles) -> Line<'static> {
+    if index % 2 == 1 {
+        line.patch_style(styles.alternate_rows)
+    } else {
+        line
+    }
+}
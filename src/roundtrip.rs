@@ -0,0 +1,194 @@
+/*!
+A canonical text form for trees, that may be parsed back into a tree, so that the tree text may
+be used as a lightweight storage format.
+
+For any tree `t` with no collapsed, hidden, or flattened nodes, `parse(&to_string(&t))` returns
+a tree equal to `t` labeled with the strings written by each node's `Display` implementation; a
+tree of `String` labels is therefore returned unchanged. As these nodes are written as they are
+by [`write_with_format`](../struct.TreeNode.html#method.write_with_format), the descendants of a
+collapsed node, and a hidden node and its descendants, are not returned, and the children of a
+flattened node are returned as children of its parent.
+
+The canonical form is the tree as written with the [`format`](fn.format.html) settings, a
+top-down ASCII tree with no prefix, in which each label is escaped:
+
+| Character       | Escaped as |
+|-----------------|------------|
+| backslash       | `\\`       |
+| line feed       | `\n`       |
+| carriage return | `\r`       |
+| tab             | `\t`       |
+
+Labels may contain the connector characters, `'+'`, `'-'`, `'|'`, and apostrophe, without
+escaping as the parser relies on the position of the connectors, four columns for each level of
+depth, and not their content; so labels may also start, or end, with spaces. Note that tools
+that trim trailing whitespace will change labels that end with spaces.
+
+# Example
+
+```rust
+use text_trees::{roundtrip, StringTreeNode};
+
+let tree = StringTreeNode::with_children(
+    "root".to_string(),
+    vec!["'-- not a connector".to_string(), "two\nlines".to_string()].into_iter(),
+);
+
+let text = roundtrip::to_string(&tree);
+assert_eq!(text, "root\n+-- '-- not a connector\n'-- two\\nlines\n");
+assert_eq!(roundtrip::parse(&text).unwrap(), tree);
+```
+*/
+
+use crate::{FormatCharacters, LabelTransform, StringTreeNode, TreeFormatting, TreeNode};
+use std::fmt::Display;
+use std::io::{Error, ErrorKind, Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return the format settings of the canonical text form; a tree written with any other
+/// settings may not be parsed.
+///
+pub fn format() -> TreeFormatting {
+    TreeFormatting {
+        label_transform: Some(LabelTransform::new(escape)),
+        ..TreeFormatting::dir_tree(FormatCharacters::ascii())
+    }
+}
+
+///
+/// Return the canonical text form of the provided tree. Note that collapsed, hidden, and
+/// flattened nodes are written as they are by
+/// [`write_with_format`](../struct.TreeNode.html#method.write_with_format), and so are not
+/// returned unchanged when the text is parsed.
+///
+pub fn to_string<T>(tree: &TreeNode<T>) -> String
+where
+    T: Display,
+{
+    let mut buffer = String::new();
+    // Writing to a String can only fail if a label's Display implementation fails.
+    let _ = tree.write_with_format_fmt(&mut buffer, &format());
+    buffer
+}
+
+///
+/// Write the canonical text form of the provided tree to the provided implementation of
+/// `std::io::Write`.
+///
+pub fn write<T>(tree: &TreeNode<T>, to_writer: &mut impl Write) -> Result<()>
+where
+    T: Display,
+{
+    tree.write_with_format(to_writer, &format())
+}
+
+///
+/// Parse the canonical text form of a tree, returning an error of kind `InvalidData` that
+/// identifies the line in error if the text is not in canonical form. Parsing does not recurse,
+/// so very deep trees may be parsed safely.
+///
+pub fn parse(text: &str) -> Result<StringTreeNode> {
+    let mut lines = text.lines().enumerate();
+    let mut stack: Vec<StringTreeNode> = match lines.next() {
+        Some((_, root)) => vec![TreeNode::new(unescape(root, 1)?)],
+        None => return Err(Error::new(ErrorKind::InvalidData, "no root node")),
+    };
+    for (index, line) in lines {
+        let line_number = index + 1;
+        let (depth, label) = parse_line(line, line_number)?;
+        if depth > stack.len() {
+            return Err(invalid(
+                line_number,
+                "node is more than one level below its parent",
+            ));
+        }
+        fold(&mut stack, depth);
+        stack.push(TreeNode::new(unescape(label, line_number)?));
+    }
+    fold(&mut stack, 1);
+    Ok(stack.pop().unwrap())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+const CONTINUATION: [&str; 2] = ["|   ", "    "];
+
+const CONNECTORS: [&str; 2] = ["+-- ", "'-- "];
+
+fn escape(label: String) -> String {
+    if !label.contains(['\\', '\n', '\r', '\t']) {
+        return label;
+    }
+    let mut escaped = String::with_capacity(label.len() + 2);
+    for c in label.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(label: &str, line_number: usize) -> Result<String> {
+    let mut unescaped = String::with_capacity(label.len());
+    let mut chars = label.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => unescaped.push('\\'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('t') => unescaped.push('\t'),
+            _ => return Err(invalid(line_number, "invalid escape sequence in label")),
+        }
+    }
+    Ok(unescaped)
+}
+
+///
+/// Return the depth, where the root has depth `0`, and the escaped label of a node's line.
+///
+fn parse_line(line: &str, line_number: usize) -> Result<(usize, &str)> {
+    let mut rest = line;
+    let mut depth = 1;
+    while let Some(after) = CONTINUATION.iter().find_map(|c| rest.strip_prefix(c)) {
+        rest = after;
+        depth += 1;
+    }
+    // As the continuation and connector strings differ, a label that itself starts with either
+    // is never mistaken for part of the line's prefix.
+    CONNECTORS
+        .iter()
+        .find_map(|c| rest.strip_prefix(c))
+        .map(|label| (depth, label))
+        .ok_or_else(|| invalid(line_number, "expected a connector"))
+}
+
+///
+/// Add each node on the stack, above `depth`, to the children of its parent.
+///
+fn fold(stack: &mut Vec<StringTreeNode>, depth: usize) {
+    while stack.len() > depth {
+        let node = stack.pop().unwrap();
+        stack.last_mut().unwrap().push_node(node);
+    }
+}
+
+fn invalid(line_number: usize, message: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("line {}: {}", line_number, message),
+    )
+}
@@ -0,0 +1,127 @@
+/*!
+Reduction of very large trees to a representative sample, for previews.
+
+The sample is built level by level, so that the overall shape of the tree is preserved: while
+the node budget allows, every child of every node is kept; once it does not, the remaining
+budget is shared between the nodes of each level, and each node keeps its first and last
+children with a placeholder node, denoting the number of nodes omitted, in their place. Once the
+budget is exhausted each node whose children are all omitted has a single placeholder in their
+place, beyond the budget, so that no nodes are omitted without a placeholder.
+*/
+
+use crate::TreeNode;
+use std::fmt::Display;
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display + Clone,
+{
+    ///
+    /// Return a copy of this tree reduced to about `budget` nodes, including placeholders, but
+    /// always including the root, where each run of omitted children is replaced with a
+    /// placeholder node labeled `"… N omitted"`, `N` being the number of nodes omitted.
+    ///
+    /// Once the budget is exhausted, each node kept whose children are all omitted has a
+    /// placeholder beyond the budget; so that the sample has, at most, one node more than the
+    /// budget for each node kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use text_trees::StringTreeNode;
+    ///
+    /// let tree = StringTreeNode::with_children(
+    ///     "root".to_string(),
+    ///     (1..=100).map(|i| format!("Child {}", i)),
+    /// );
+    ///
+    /// let sample = tree.sample(6);
+    /// let labels: Vec<String> = sample.children().map(|child| child.label()).collect();
+    /// assert_eq!(
+    ///     labels,
+    ///     vec!["Child 1", "Child 2", "… 96 omitted", "Child 99", "Child 100"]
+    /// );
+    /// ```
+    ///
+    pub fn sample(&self, budget: usize) -> TreeNode<T>
+    where
+        T: From<String>,
+    {
+        self.sample_with(budget, |omitted| format!("… {} omitted", omitted).into())
+    }
+
+    ///
+    /// Return a copy of this tree reduced to about `budget` nodes, as
+    /// [`sample`](struct.TreeNode.html#method.sample) does, where each placeholder node's data
+    /// is returned by `placeholder` given the number of nodes omitted.
+    ///
+    pub fn sample_with(&self, budget: usize, placeholder: impl Fn(usize) -> T) -> TreeNode<T> {
+        // The sampled nodes are held as a flat list, in breadth-first order, with the index of
+        // each node's parent, and assembled into a tree at the end; neither step recurses.
+        let mut sampled: Vec<(T, usize)> = vec![(self.data.clone(), 0)];
+        let mut remaining = budget.saturating_sub(1);
+        let mut level: Vec<(usize, &TreeNode<T>)> = vec![(0, self)];
+        while !level.is_empty() {
+            let total: usize = level.iter().map(|(_, node)| node.children.len()).sum();
+            let parents = level.iter().filter(|(_, node)| node.has_children()).count();
+            let (quota, mut extra) = if total <= remaining {
+                (total, 0)
+            } else {
+                (remaining / parents, remaining % parents)
+            };
+
+            let mut next_level = Vec::new();
+            for (parent, node) in level {
+                let children = &node.children;
+                if children.is_empty() {
+                    continue;
+                }
+                let mut allowed = quota;
+                if extra > 0 {
+                    allowed += 1;
+                    extra -= 1;
+                }
+                // with no children allowed, the placeholder for them all is beyond the budget.
+                let allowed = allowed.min(remaining);
+
+                let (head, tail) = if children.len() <= allowed {
+                    (children.len(), 0)
+                } else {
+                    let keep = allowed.saturating_sub(1);
+                    (keep - keep / 2, keep / 2)
+                };
+                for child in &children[..head] {
+                    sampled.push((child.data.clone(), parent));
+                    next_level.push((sampled.len() - 1, child));
+                }
+                if head + tail < children.len() {
+                    let omitted: usize = children[head..children.len() - tail]
+                        .iter()
+                        .map(|child| 1 + child.descendant_count())
+                        .sum();
+                    sampled.push((placeholder(omitted), parent));
+                }
+                for child in &children[children.len() - tail..] {
+                    sampled.push((child.data.clone(), parent));
+                    next_level.push((sampled.len() - 1, child));
+                }
+                remaining -= allowed.min(children.len());
+            }
+            level = next_level;
+        }
+
+        let mut children: Vec<Vec<TreeNode<T>>> = sampled.iter().map(|_| Vec::new()).collect();
+        while let Some((data, parent)) = sampled.pop() {
+            let node = TreeNode::with_child_nodes(data, children.pop().unwrap().into_iter().rev());
+            if sampled.is_empty() {
+                return node;
+            }
+            children[parent].push(node);
+        }
+        unreachable!()
+    }
+}
@@ -0,0 +1,463 @@
+/*!
+Iterating over a tree, and searching it for the nodes whose data matches a predicate.
+
+Each node is returned with its _index path_, the index of each child from the root down to the
+node, so that the caller knows where in the tree the node sits; the root's index path is empty.
+Nodes are visited in the order they appear when the tree is written, and the traversal does not
+recurse, so very deep trees may be searched safely.
+
+Where a search, or a validation pass, need not visit every node,
+[`try_visit`](struct.TreeNode.html#method.try_visit) calls a function on each node which may
+stop the traversal with a result, or skip the node's descendants.
+*/
+
+use crate::TreeNode;
+use std::fmt::Display;
+use std::ops::ControlFlow;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returned, within `ControlFlow::Continue`, by the function called for each node by
+/// [`try_visit`](struct.TreeNode.html#method.try_visit) to denote whether the node's children
+/// are visited.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Visit {
+    /// The node's children, and their descendants, are visited next.
+    Children,
+    /// The node's descendants are not visited, the traversal continues with the node's next
+    /// sibling.
+    SkipChildren,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return an iterator over every node in this tree, along with its index path, in the order
+    /// the nodes are written.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use text_trees::StringTreeNode;
+    ///
+    /// let tree = StringTreeNode::with_child_nodes(
+    ///     "root".to_string(),
+    ///     vec![
+    ///         StringTreeNode::with_children(
+    ///             "Parent".to_string(),
+    ///             vec!["Child".to_string()].into_iter(),
+    ///         ),
+    ///         "Uncle".into(),
+    ///     ]
+    ///     .into_iter(),
+    /// );
+    ///
+    /// for (path, node) in tree.iter_with_paths() {
+    ///     println!("{:?} {}", path, node.data());
+    /// }
+    /// // [] root
+    /// // [0] Parent
+    /// // [0, 0] Child
+    /// // [1] Uncle
+    /// ```
+    ///
+    pub fn iter_with_paths(&self) -> impl Iterator<Item = (Vec<usize>, &TreeNode<T>)> {
+        PathIter::new(self)
+    }
+
+    ///
+    /// Return an iterator over the data of every node in this tree, along with its depth, in
+    /// the order the nodes are written; the root has depth `0`. This is cheaper than
+    /// [`iter_with_paths`](struct.TreeNode.html#method.iter_with_paths) as no index paths are
+    /// constructed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use text_trees::StringTreeNode;
+    ///
+    /// let tree = StringTreeNode::with_children(
+    ///     "root".to_string(),
+    ///     vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+    /// );
+    ///
+    /// for (depth, label) in tree.iter_with_depth() {
+    ///     println!("{}{}", "  ".repeat(depth), label);
+    /// }
+    /// ```
+    ///
+    pub fn iter_with_depth(&self) -> impl Iterator<Item = (usize, &T)> {
+        DepthIter {
+            work: vec![(0, self)],
+        }
+    }
+
+    ///
+    /// Call the provided function on each node of this tree, with the node's depth, in the
+    /// order the nodes are written. The traversal stops at the first node for which the
+    /// function returns `ControlFlow::Break`, returning its value, and skips the descendants of
+    /// any node for which it returns `ControlFlow::Continue(Visit::SkipChildren)`; `None` is
+    /// returned if every node was visited, or skipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::ops::ControlFlow;
+    /// use text_trees::{StringTreeNode, Visit};
+    ///
+    /// let tree = StringTreeNode::with_child_nodes(
+    ///     "root".to_string(),
+    ///     vec![
+    ///         StringTreeNode::with_children(
+    ///             "target".to_string(),
+    ///             vec!["build.log".to_string()].into_iter(),
+    ///         ),
+    ///         StringTreeNode::with_children(
+    ///             "src".to_string(),
+    ///             vec!["main.rs".to_string(), "lib.rs".to_string()].into_iter(),
+    ///         ),
+    ///     ]
+    ///     .into_iter(),
+    /// );
+    ///
+    /// let mut visited = 0;
+    /// let found = tree.try_visit(|node, depth| {
+    ///     visited += 1;
+    ///     if node.data() == "target" {
+    ///         ControlFlow::Continue(Visit::SkipChildren)
+    ///     } else if node.data().ends_with(".rs") {
+    ///         ControlFlow::Break((depth, node.data().clone()))
+    ///     } else {
+    ///         ControlFlow::Continue(Visit::Children)
+    ///     }
+    /// });
+    /// assert_eq!(found, Some((2, "main.rs".to_string())));
+    /// assert_eq!(visited, 4);
+    /// ```
+    ///
+    pub fn try_visit<B>(
+        &self,
+        mut f: impl FnMut(&TreeNode<T>, usize) -> ControlFlow<B, Visit>,
+    ) -> Option<B> {
+        let mut work = vec![(0, self)];
+        while let Some((depth, node)) = work.pop() {
+            match f(node, depth) {
+                ControlFlow::Break(result) => return Some(result),
+                ControlFlow::Continue(Visit::SkipChildren) => {}
+                ControlFlow::Continue(Visit::Children) => {
+                    work.extend(node.children.iter().rev().map(|child| (depth + 1, child)));
+                }
+            }
+        }
+        None
+    }
+
+    ///
+    /// Return the nodes of this tree grouped by depth, the first level contains only this node,
+    /// the second its children, and so on. Within each level nodes are in the order they are
+    /// written.
+    ///
+    pub fn levels(&self) -> Vec<Vec<&TreeNode<T>>> {
+        let mut levels = vec![vec![self]];
+        loop {
+            let next: Vec<&TreeNode<T>> = levels
+                .last()
+                .unwrap()
+                .iter()
+                .flat_map(|node| node.children.iter())
+                .collect();
+            if next.is_empty() {
+                return levels;
+            }
+            levels.push(next);
+        }
+    }
+
+    ///
+    /// Return the deepest leaf node of this tree, along with its index path; where more than
+    /// one leaf is at the greatest depth the first, in the order nodes are written, is returned.
+    /// If this node has no children it is itself the deepest leaf.
+    ///
+    pub fn deepest_leaf(&self) -> (Vec<usize>, &TreeNode<T>) {
+        let mut deepest = (Vec::new(), self);
+        for (path, node) in PathIter::new(self) {
+            if !node.has_children() && path.len() > deepest.0.len() {
+                deepest = (path, node);
+            }
+        }
+        deepest
+    }
+
+    ///
+    /// Return the index paths of every leaf node at the greatest depth in this tree, in the
+    /// order the nodes are written; that is, every longest path from this node to a leaf. This
+    /// is the _critical path_ when the tree describes a chain of dependencies.
+    ///
+    pub fn longest_paths(&self) -> Vec<Vec<usize>> {
+        let mut longest: Vec<Vec<usize>> = Vec::new();
+        for (path, node) in PathIter::new(self) {
+            if node.has_children() {
+                continue;
+            }
+            match longest.first().map(Vec::len) {
+                Some(depth) if depth > path.len() => {}
+                Some(depth) if depth == path.len() => longest.push(path),
+                _ => longest = vec![path],
+            }
+        }
+        longest
+    }
+
+    ///
+    /// Return the node at the provided index path, relative to this node, or `None` if the path
+    /// does not address a node in this tree. The empty path addresses this node. Index paths
+    /// are returned by methods such as [`find_all`](struct.TreeNode.html#method.find_all) and
+    /// [`select`](struct.TreeNode.html#method.select).
+    ///
+    pub fn subtree_at(&self, path: &[usize]) -> Option<&TreeNode<T>> {
+        path.iter()
+            .try_fold(self, |node, index| node.children.get(*index))
+    }
+
+    ///
+    /// Return a mutable reference to the node at the provided index path, relative to this node,
+    /// or `None` if the path does not address a node in this tree. The empty path addresses this
+    /// node.
+    ///
+    pub fn subtree_at_mut(&mut self, path: &[usize]) -> Option<&mut TreeNode<T>> {
+        path.iter()
+            .try_fold(self, |node, index| node.children.get_mut(*index))
+    }
+
+    ///
+    /// Hide every node, other than the root, for which `predicate` is `false` and that has no
+    /// descendant for which it is `true`; so that the tree is written with only the matching
+    /// nodes and their ancestors. The visibility of the remaining nodes is not changed, so that
+    /// successive calls hide the nodes that fail any of the predicates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use text_trees::StringTreeNode;
+    ///
+    /// let mut tree = StringTreeNode::with_child_nodes(
+    ///     "root".to_string(),
+    ///     vec![
+    ///         StringTreeNode::with_children(
+    ///             "src".to_string(),
+    ///             vec!["lib.rs".to_string(), "notes.txt".to_string()].into_iter(),
+    ///         ),
+    ///         "README.md".into(),
+    ///     ]
+    ///     .into_iter(),
+    /// );
+    /// tree.hide_unmatched(|label| label.ends_with(".rs"));
+    ///
+    /// assert_eq!(tree.to_string(), "root\n'-- src\n    '-- lib.rs\n");
+    /// ```
+    ///
+    pub fn hide_unmatched(&mut self, predicate: impl Fn(&T) -> bool) {
+        // Whether each node, in pre-order, matches or has a matching descendant, found in a
+        // single pass from the end where `found` records, for each depth, whether a match has
+        // been passed since the last node at a lower depth.
+        let nodes: Vec<(usize, bool)> = self
+            .iter_with_depth()
+            .map(|(depth, data)| (depth, predicate(data)))
+            .collect();
+        let mut keep = vec![false; nodes.len()];
+        let mut found: Vec<bool> = Vec::new();
+        for (index, (depth, matches)) in nodes.iter().enumerate().rev() {
+            keep[index] = *matches || found.get(depth + 1).copied().unwrap_or_default();
+            found.resize(depth + 1, false);
+            found[*depth] |= keep[index];
+        }
+
+        let mut work: Vec<&mut TreeNode<T>> = vec![self];
+        let mut keep = keep.into_iter();
+        let mut is_root = true;
+        while let (Some(node), Some(keep)) = (work.pop(), keep.next()) {
+            if !keep && !is_root {
+                node.set_hidden(true);
+            }
+            is_root = false;
+            work.extend(node.children.iter_mut().rev());
+        }
+    }
+
+    ///
+    /// Return the index path of the lowest common ancestor of the nodes at the two provided
+    /// index paths, that is the longest common prefix of both paths. If either path does not
+    /// address a node in this tree, the common prefix is shortened to the deepest node that
+    /// exists; as the root is an ancestor of every node the result may be empty, but is never
+    /// invalid.
+    ///
+    /// Note that a node is considered its own ancestor, so the lowest common ancestor of a node
+    /// and one of its descendants is the node itself.
+    ///
+    pub fn lca(&self, a: &[usize], b: &[usize]) -> Vec<usize> {
+        self.lca_node(a, b).0
+    }
+
+    ///
+    /// Return the lowest common ancestor of the nodes at the two provided index paths, along
+    /// with its index path, as described for [`lca`](struct.TreeNode.html#method.lca).
+    ///
+    pub fn lca_node(&self, a: &[usize], b: &[usize]) -> (Vec<usize>, &TreeNode<T>) {
+        let mut path = Vec::new();
+        let mut node = self;
+        for (index_a, index_b) in a.iter().zip(b) {
+            match node.children.get(*index_a) {
+                Some(child) if index_a == index_b => {
+                    path.push(*index_a);
+                    node = child;
+                }
+                _ => break,
+            }
+        }
+        (path, node)
+    }
+
+    ///
+    /// Return the first node, in the order nodes are written, whose data matches the provided
+    /// predicate, along with its index path; or `None` if no node matches.
+    ///
+    pub fn find<P>(&self, predicate: P) -> Option<(Vec<usize>, &TreeNode<T>)>
+    where
+        P: Fn(&T) -> bool,
+    {
+        PathIter::new(self).find(|(_, node)| predicate(node.data()))
+    }
+
+    ///
+    /// Return every node whose data matches the provided predicate, along with its index path,
+    /// in the order the nodes are written.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use text_trees::StringTreeNode;
+    ///
+    /// let tree = StringTreeNode::with_child_nodes(
+    ///     "root".to_string(),
+    ///     vec![
+    ///         StringTreeNode::with_children(
+    ///             "Parent".to_string(),
+    ///             vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+    ///         ),
+    ///         "Child 3".into(),
+    ///     ]
+    ///     .into_iter(),
+    /// );
+    ///
+    /// let paths: Vec<Vec<usize>> = tree
+    ///     .find_all(|label| label.starts_with("Child"))
+    ///     .into_iter()
+    ///     .map(|(path, _)| path)
+    ///     .collect();
+    /// assert_eq!(paths, vec![vec![0, 0], vec![0, 1], vec![1]]);
+    /// ```
+    ///
+    pub fn find_all<P>(&self, predicate: P) -> Vec<(Vec<usize>, &TreeNode<T>)>
+    where
+        P: Fn(&T) -> bool,
+    {
+        PathIter::new(self)
+            .filter(|(_, node)| predicate(node.data()))
+            .collect()
+    }
+
+    ///
+    /// Return an iterator over every node whose data matches the provided predicate, along with
+    /// its index path, in the order the nodes are written. Unlike
+    /// [`find_all`](struct.TreeNode.html#method.find_all) the tree is only searched as far as
+    /// the iterator is advanced.
+    ///
+    pub fn find_iter<'a, P>(
+        &'a self,
+        predicate: P,
+    ) -> impl Iterator<Item = (Vec<usize>, &'a TreeNode<T>)> + 'a
+    where
+        P: Fn(&T) -> bool + 'a,
+    {
+        PathIter::new(self).filter(move |(_, node)| predicate(node.data()))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A pre-order iterator over the nodes of a tree, and their index paths.
+///
+struct PathIter<'a, T>
+where
+    T: Display,
+{
+    work: Vec<(Vec<usize>, &'a TreeNode<T>)>,
+}
+
+impl<'a, T> PathIter<'a, T>
+where
+    T: Display,
+{
+    fn new(node: &'a TreeNode<T>) -> Self {
+        Self {
+            work: vec![(Vec::new(), node)],
+        }
+    }
+}
+
+impl<'a, T> Iterator for PathIter<'a, T>
+where
+    T: Display,
+{
+    type Item = (Vec<usize>, &'a TreeNode<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, node) = self.work.pop()?;
+        for (index, child) in node.children.iter().enumerate().rev() {
+            let mut child_path = Vec::with_capacity(path.len() + 1);
+            child_path.extend_from_slice(&path);
+            child_path.push(index);
+            self.work.push((child_path, child));
+        }
+        Some((path, node))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A pre-order iterator over the data of the nodes of a tree, and their depths.
+///
+struct DepthIter<'a, T>
+where
+    T: Display,
+{
+    work: Vec<(usize, &'a TreeNode<T>)>,
+}
+
+impl<'a, T> Iterator for DepthIter<'a, T>
+where
+    T: Display,
+{
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, node) = self.work.pop()?;
+        self.work
+            .extend(node.children.iter().rev().map(|child| (depth + 1, child)));
+        Some((depth, &node.data))
+    }
+}
@@ -0,0 +1,349 @@
+/*!
+A small selector syntax for choosing nodes by the labels on the path from the root to them.
+
+A selector is a list of segments separated by `'/'`, each segment is matched against the
+label of one node on the path, starting with the root. Within a segment `'*'` matches any
+sequence of characters and `'?'` matches any single character; the segment `**` matches any
+number of nodes, including none; examples are given with [`Selector`](struct.Selector.html).
+
+A `Selector` may be used to query a tree, with
+[`select`](struct.TreeNode.html#method.select), or to filter the nodes that are written, with
+[`write_with_selector`](struct.TreeNode.html#method.write_with_selector).
+*/
+
+use crate::{write_tree_inner, IoWrite, TreeFormatting, TreeNode};
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+use std::io::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A compiled selector, see the [module documentation](index.html) for the syntax. Empty
+/// segments, such as those from a leading or trailing `'/'`, are ignored. For example:
+///
+/// * `root/*/Child*` selects the grandchildren of the node labeled `root` whose labels start
+///   with `Child`.
+/// * `**/leaf` selects every node labeled `leaf`, at any depth.
+/// * `root/Parent/**` selects the node `Parent`, and all of its descendants.
+///
+/// # Example
+///
+/// ```rust
+/// use text_trees::Selector;
+///
+/// let selector = Selector::new("root/*/Child*");
+/// assert!(selector.matches(&["root", "Parent", "Child 1"]));
+/// assert!(!selector.matches(&["root", "Parent"]));
+///
+/// let selector = Selector::new("**/leaf");
+/// assert!(selector.matches(&["root", "leaf"]));
+/// assert!(selector.matches(&["root", "a", "b", "leaf"]));
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Selector {
+    segments: Vec<Segment>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Selector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let segments: Vec<&str> = self
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::AnyDepth => "**",
+                Segment::Pattern(pattern) => pattern,
+            })
+            .collect();
+        write!(f, "{}", segments.join("/"))
+    }
+}
+
+impl From<&str> for Selector {
+    fn from(selector: &str) -> Self {
+        Self::new(selector)
+    }
+}
+
+impl Selector {
+    /// Compile the provided selector string.
+    pub fn new(selector: &str) -> Self {
+        Self {
+            segments: selector
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| {
+                    if segment == "**" {
+                        Segment::AnyDepth
+                    } else {
+                        Segment::Pattern(segment.to_string())
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    ///
+    /// Returns `true` if the provided labels, those of each node from the root down to the node
+    /// being tested, match this selector, else `false`.
+    ///
+    pub fn matches<S: AsRef<str>>(&self, labels: &[S]) -> bool {
+        let mut states = self.start();
+        for label in labels {
+            states = self.step(&states, label.as_ref());
+        }
+        self.is_match(&states)
+    }
+
+    // The selector is matched as a simple NFA, where state `i` means that the first `i` segments
+    // have been matched; this allows matching to proceed one label at a time while walking a tree.
+
+    fn start(&self) -> Vec<bool> {
+        let mut states = vec![false; self.segments.len() + 1];
+        states[0] = true;
+        self.close(&mut states);
+        states
+    }
+
+    fn step(&self, states: &[bool], label: &str) -> Vec<bool> {
+        let mut next = vec![false; states.len()];
+        for (i, segment) in self.segments.iter().enumerate() {
+            if states[i] {
+                match segment {
+                    Segment::AnyDepth => next[i] = true,
+                    Segment::Pattern(pattern) => {
+                        if glob_match(pattern, label) {
+                            next[i + 1] = true;
+                        }
+                    }
+                }
+            }
+        }
+        self.close(&mut next);
+        next
+    }
+
+    fn close(&self, states: &mut [bool]) {
+        for (i, segment) in self.segments.iter().enumerate() {
+            if states[i] && *segment == Segment::AnyDepth {
+                states[i + 1] = true;
+            }
+        }
+    }
+
+    fn is_match(&self, states: &[bool]) -> bool {
+        states[self.segments.len()]
+    }
+
+    fn is_dead(&self, states: &[bool]) -> bool {
+        !states.iter().any(|state| *state)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return every node selected by the provided selector, along with its index path, in the
+    /// order the nodes are written.
+    ///
+    pub fn select(&self, selector: &Selector) -> Vec<(Vec<usize>, &TreeNode<T>)> {
+        let mut selected = Vec::new();
+        let mut work = vec![(Vec::new(), self, selector.start())];
+        while let Some((path, node, states)) = work.pop() {
+            let states = selector.step(&states, &node.label());
+            if selector.is_dead(&states) {
+                continue;
+            }
+            for (index, child) in node.children.iter().enumerate().rev() {
+                let mut child_path = path.clone();
+                child_path.push(index);
+                work.push((child_path, child, states.clone()));
+            }
+            if selector.is_match(&states) {
+                selected.push((path, node));
+            }
+        }
+        selected
+    }
+
+    ///
+    /// Return a string containing the generated tree text formatted according to the provided
+    /// format settings, including only the nodes selected by the provided selector and their
+    /// ancestors. The root is always written, even if no node is selected.
+    ///
+    pub fn to_string_with_selector(
+        &self,
+        format: &TreeFormatting,
+        selector: &Selector,
+    ) -> Result<String> {
+        let tree = selected_tree(self, selector);
+        tree.render_to_string(format, |mut w| {
+            write_tree_inner(&tree, &mut w, format, &|node| format.node_label(node))
+        })
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` with the provided
+    /// format settings, including only the nodes selected by the provided selector and their
+    /// ancestors. The root is always written, even if no node is selected.
+    ///
+    pub fn write_with_selector(
+        &self,
+        to_writer: &mut impl Write,
+        format: &TreeFormatting,
+        selector: &Selector,
+    ) -> Result<()> {
+        let tree = selected_tree(self, selector);
+        tree.render_to(&mut IoWrite(to_writer), format, |mut w| {
+            write_tree_inner(&tree, &mut w, format, &|node| format.node_label(node))
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    AnyDepth,
+    Pattern(String),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Crate Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Match a label against a simple glob pattern, where `'*'` matches any sequence of
+/// characters and `'?'` matches any single character.
+///
+pub(crate) fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    backtrack = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return a tree, borrowing the data of the provided tree, that contains only the selected
+/// nodes and their ancestors. The tree is built without recursion, so that very deep trees do
+/// not overflow the stack.
+///
+fn selected_tree<'a, T>(node: &'a TreeNode<T>, selector: &Selector) -> TreeNode<&'a T>
+where
+    T: Display,
+{
+    let keep: HashSet<Vec<usize>> = node
+        .select(selector)
+        .into_iter()
+        .flat_map(|(path, _)| (1..=path.len()).map(move |len| path[..len].to_vec()))
+        .collect();
+
+    let mut path = Vec::new();
+    let mut work = vec![(node, borrowed_node(node), 0)];
+    loop {
+        let (source, _, next_child) = work.last_mut().unwrap();
+        if let Some(child) = source.children.get(*next_child) {
+            path.push(*next_child);
+            *next_child += 1;
+            if keep.contains(&path) {
+                work.push((child, borrowed_node(child), 0));
+            } else {
+                let _ = path.pop();
+            }
+        } else {
+            let (_, built, _) = work.pop().unwrap();
+            match work.last_mut() {
+                Some((_, parent, _)) => {
+                    parent.push_node(built);
+                    let _ = path.pop();
+                }
+                None => return built,
+            }
+        }
+    }
+}
+
+fn borrowed_node<T>(node: &TreeNode<T>) -> TreeNode<&T>
+where
+    T: Display,
+{
+    let mut borrowed = TreeNode::new(&node.data);
+    borrowed.set_collapsed(node.is_collapsed());
+    borrowed.set_visibility(node.visibility());
+    borrowed
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.rs", "lib.rs"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("test_*.rs", "test_fs.rs"));
+        assert!(glob_match("?.txt", "a.txt"));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(!glob_match("*.rs", "lib.rs.bak"));
+        assert!(!glob_match("?.txt", "ab.txt"));
+        assert!(!glob_match("target", "targets"));
+    }
+
+    #[test]
+    fn test_selector_matches() {
+        let selector = Selector::new("/root/**/b/");
+        assert_eq!(selector.to_string(), "root/**/b");
+        assert!(selector.matches(&["root", "b"]));
+        assert!(selector.matches(&["root", "a", "a", "b"]));
+        assert!(!selector.matches(&["root", "b", "a"]));
+        assert!(!selector.matches(&["other", "b"]));
+
+        let selector = Selector::new("root/**");
+        assert!(selector.matches(&["root"]));
+        assert!(selector.matches(&["root", "a", "b"]));
+
+        assert!(!Selector::new("").matches(&["root"]));
+    }
+}
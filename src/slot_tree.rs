@@ -0,0 +1,337 @@
+/*!
+A mutable tree, [`SlotTree`](struct.SlotTree.html), where each node is addressed by a
+[`SlotId`](struct.SlotId.html) handle that remains valid across any change to the tree other
+than the removal of the node itself; for applications, such as editors, that hold references to
+nodes while the tree changes.
+
+Nodes are stored in slots, each with a generation that is incremented when its node is removed,
+so that a handle to a removed node is never confused with a handle to a later node stored in the
+same slot. A `SlotTree` is converted from a [`TreeNode`](struct.TreeNode.html) to be changed, and
+back into a `TreeNode` to be written.
+
+# Example
+
+```rust
+use text_trees::{SlotTree, StringTreeNode};
+
+let mut tree = SlotTree::new("root".to_string());
+let root = tree.root();
+let a = tree.push(root, "a".to_string()).unwrap();
+let b = tree.push(root, "b".to_string()).unwrap();
+let a1 = tree.push(a, "a1".to_string()).unwrap();
+
+let removed = tree.remove(a).unwrap();
+assert_eq!(removed.to_string(), "a\n'-- a1\n");
+assert!(tree.get(a1).is_none());
+assert_eq!(tree.get(b), Some(&"b".to_string()));
+
+*tree.get_mut(b).unwrap() = "c".to_string();
+let tree: StringTreeNode = tree.into();
+assert_eq!(tree.to_string(), "root\n'-- c\n");
+```
+*/
+
+use crate::{TreeNode, Visibility};
+use std::fmt::Display;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A handle to a node of a [`SlotTree`](struct.SlotTree.html); this is valid until the node is
+/// removed, after which it refers to no node, even if the node's slot is reused.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SlotId {
+    index: usize,
+    generation: u64,
+}
+
+///
+/// A tree whose nodes are addressed by stable [`SlotId`](struct.SlotId.html) handles, rather than
+/// by reference or by path.
+///
+#[derive(Clone, Debug)]
+pub struct SlotTree<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+    root: SlotId,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T> SlotTree<T> {
+    /// Construct a new tree with a single root node with the provided data value.
+    pub fn new(data: T) -> Self {
+        let mut tree = Self {
+            slots: Default::default(),
+            free: Default::default(),
+            root: SlotId {
+                index: 0,
+                generation: 0,
+            },
+        };
+        tree.root = tree.allocate(Entry::new(data, None));
+        tree
+    }
+
+    /// Return the handle of the root node, which cannot be removed.
+    pub fn root(&self) -> SlotId {
+        self.root
+    }
+
+    /// Return the number of nodes in this tree, including the root.
+    pub fn node_count(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Return `true` if the provided handle refers to a node of this tree.
+    pub fn contains(&self, id: SlotId) -> bool {
+        self.entry(id).is_some()
+    }
+
+    /// Return a reference to the data item of the node, if the handle refers to a node.
+    pub fn get(&self, id: SlotId) -> Option<&T> {
+        self.entry(id).map(|entry| &entry.data)
+    }
+
+    /// Return a mutable reference to the data item of the node, if the handle refers to a node.
+    pub fn get_mut(&mut self, id: SlotId) -> Option<&mut T> {
+        self.entry_mut(id).map(|entry| &mut entry.data)
+    }
+
+    /// Return the handle of the node's parent, if the handle refers to a node that is not the root.
+    pub fn parent(&self, id: SlotId) -> Option<SlotId> {
+        self.entry(id).and_then(|entry| entry.parent)
+    }
+
+    ///
+    /// Return the handles of the node's children, in order; this is empty if the handle does
+    /// not refer to a node.
+    ///
+    pub fn children(&self, id: SlotId) -> &[SlotId] {
+        self.entry(id)
+            .map(|entry| entry.children.as_slice())
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Add a new node with the provided data value as the last child of the parent node,
+    /// returning its handle, or `None` if the parent handle does not refer to a node.
+    ///
+    pub fn push(&mut self, parent: SlotId, data: T) -> Option<SlotId> {
+        let index = self.entry(parent)?.children.len();
+        self.insert(parent, index, data)
+    }
+
+    ///
+    /// Add a new node with the provided data value as the child at `index` of the parent node,
+    /// returning its handle, or `None` if the parent handle does not refer to a node or `index`
+    /// is greater than the number of its children.
+    ///
+    pub fn insert(&mut self, parent: SlotId, index: usize, data: T) -> Option<SlotId> {
+        if index > self.entry(parent)?.children.len() {
+            return None;
+        }
+        let id = self.allocate(Entry::new(data, Some(parent)));
+        self.entry_mut(parent).unwrap().children.insert(index, id);
+        Some(id)
+    }
+
+    ///
+    /// Remove the node, and all its descendants, from this tree returning them as a new
+    /// `TreeNode`; the handles of the removed nodes no longer refer to any node. This returns
+    /// `None` if the handle does not refer to a node, or refers to the root.
+    ///
+    pub fn remove(&mut self, id: SlotId) -> Option<TreeNode<T>>
+    where
+        T: Display,
+    {
+        let parent = self.parent(id)?;
+        self.entry_mut(parent)
+            .unwrap()
+            .children
+            .retain(|child| *child != id);
+        Some(self.take_subtree(id))
+    }
+
+    fn entry(&self, id: SlotId) -> Option<&Entry<T>> {
+        self.slots
+            .get(id.index)
+            .filter(|slot| slot.generation == id.generation)
+            .and_then(|slot| slot.entry.as_ref())
+    }
+
+    fn entry_mut(&mut self, id: SlotId) -> Option<&mut Entry<T>> {
+        self.slots
+            .get_mut(id.index)
+            .filter(|slot| slot.generation == id.generation)
+            .and_then(|slot| slot.entry.as_mut())
+    }
+
+    fn allocate(&mut self, entry: Entry<T>) -> SlotId {
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index];
+                slot.entry = Some(entry);
+                SlotId {
+                    index,
+                    generation: slot.generation,
+                }
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    entry: Some(entry),
+                });
+                SlotId {
+                    index: self.slots.len() - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    fn release(&mut self, id: SlotId) -> Entry<T> {
+        let slot = &mut self.slots[id.index];
+        slot.generation += 1;
+        self.free.push(id.index);
+        slot.entry.take().unwrap()
+    }
+
+    ///
+    /// Release the node, and all its descendants, returning them as a `TreeNode`; this is built
+    /// without recursion, so that very deep trees do not overflow the stack.
+    ///
+    fn take_subtree(&mut self, id: SlotId) -> TreeNode<T>
+    where
+        T: Display,
+    {
+        let (node, children) = self.release(id).into_node();
+        let mut stack = vec![(node, children.into_iter())];
+        loop {
+            let next = stack.last_mut().unwrap().1.next();
+            match next {
+                Some(child) => {
+                    let (node, children) = self.release(child).into_node();
+                    stack.push((node, children.into_iter()));
+                }
+                None => {
+                    let (node, _) = stack.pop().unwrap();
+                    match stack.last_mut() {
+                        Some((parent, _)) => parent.children.push(node),
+                        None => return node,
+                    }
+                }
+            }
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> From<TreeNode<T>> for SlotTree<T>
+where
+    T: Display,
+{
+    fn from(node: TreeNode<T>) -> Self {
+        let (entry, children) = Entry::from_node(node, None);
+        let mut tree = Self {
+            slots: Default::default(),
+            free: Default::default(),
+            root: SlotId {
+                index: 0,
+                generation: 0,
+            },
+        };
+        tree.root = tree.allocate(entry);
+        let mut stack = vec![(tree.root, children.into_iter())];
+        while let Some((parent, children)) = stack.last_mut() {
+            let parent = *parent;
+            match children.next() {
+                Some(child) => {
+                    let (entry, children) = Entry::from_node(child, Some(parent));
+                    let id = tree.allocate(entry);
+                    tree.entry_mut(parent).unwrap().children.push(id);
+                    stack.push((id, children.into_iter()));
+                }
+                None => {
+                    let _ = stack.pop();
+                }
+            }
+        }
+        tree
+    }
+}
+
+impl<T> From<SlotTree<T>> for TreeNode<T>
+where
+    T: Display,
+{
+    fn from(mut tree: SlotTree<T>) -> Self {
+        let root = tree.root;
+        tree.take_subtree(root)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Debug)]
+struct Slot<T> {
+    generation: u64,
+    entry: Option<Entry<T>>,
+}
+
+#[derive(Clone, Debug)]
+struct Entry<T> {
+    data: T,
+    parent: Option<SlotId>,
+    children: Vec<SlotId>,
+    collapsed: bool,
+    visibility: Visibility,
+}
+
+impl<T> Entry<T> {
+    fn new(data: T, parent: Option<SlotId>) -> Self {
+        Self {
+            data,
+            parent,
+            children: Default::default(),
+            collapsed: false,
+            visibility: Visibility::Visible,
+        }
+    }
+
+    fn from_node(node: TreeNode<T>, parent: Option<SlotId>) -> (Self, Vec<TreeNode<T>>)
+    where
+        T: Display,
+    {
+        let TreeNode {
+            data,
+            children,
+            collapsed,
+            visibility,
+        } = node;
+        let entry = Self {
+            collapsed,
+            visibility,
+            ..Self::new(data, parent)
+        };
+        (entry, children.into_iter().collect())
+    }
+
+    fn into_node(self) -> (TreeNode<T>, Vec<SlotId>)
+    where
+        T: Display,
+    {
+        let mut node = TreeNode::new(self.data);
+        node.collapsed = self.collapsed;
+        node.visibility = self.visibility;
+        (node, self.children)
+    }
+}
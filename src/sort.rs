@@ -0,0 +1,165 @@
+/*!
+Sorting the children of nodes in place, with ready-made comparators for the orders most often
+wanted in file and size trees.
+
+The comparators compare node data by label, as written with `Display`, or by a numeric value,
+and may be passed directly to [`sort_children_by`](struct.TreeNode.html#method.sort_children_by)
+or [`sort_tree_by`](struct.TreeNode.html#method.sort_tree_by). Each is a total order, so labels
+that compare as equal under the order, such as `"a"` and `"A"` when ignoring case, are still
+sorted consistently.
+
+# Example
+
+```rust
+use text_trees::{natural_order, StringTreeNode};
+
+let mut tree = StringTreeNode::with_children(
+    "logs".to_string(),
+    vec!["file10".to_string(), "file2".to_string(), "file1".to_string()].into_iter(),
+);
+tree.sort_tree_by(natural_order);
+
+assert_eq!(tree.to_string(), "logs\n+-- file1\n+-- file2\n'-- file10\n");
+```
+*/
+
+use crate::{NodeValue, TreeNode};
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::iter::Peekable;
+use std::str::Chars;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Compare the labels of two nodes in natural order, where runs of ASCII digits are compared by
+/// their numeric value, so that `"file2"` sorts before `"file10"`. Where two runs have the same
+/// value, the run with fewer leading zeros sorts first.
+///
+pub fn natural_order<T>(a: &T, b: &T) -> Ordering
+where
+    T: Display,
+{
+    let (a, b) = (a.to_string(), b.to_string());
+    natural_cmp(&a, &b).then_with(|| a.cmp(&b))
+}
+
+///
+/// Compare the labels of two nodes ignoring case, so that `"apple"` sorts before `"Banana"`.
+///
+pub fn case_insensitive_order<T>(a: &T, b: &T) -> Ordering
+where
+    T: Display,
+{
+    let (a, b) = (a.to_string(), b.to_string());
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .cmp(b.chars().flat_map(char::to_lowercase))
+        .then_with(|| a.cmp(&b))
+}
+
+///
+/// Return a comparator that orders nodes by the provided numeric value, largest first, such as
+/// the size of each file. Values that are `NaN` sort after all others.
+///
+/// ```rust
+/// use text_trees::{by_value_descending, TreeNode};
+///
+/// let mut tree = TreeNode::with_children(0, vec![2, 30, 4].into_iter());
+/// tree.sort_children_by(by_value_descending(|n: &i32| *n as f64));
+///
+/// assert_eq!(tree.to_string(), "0\n+-- 30\n+-- 4\n'-- 2\n");
+/// ```
+///
+pub fn by_value_descending<T>(value: impl NodeValue<T>) -> impl Fn(&T, &T) -> Ordering {
+    move |a, b| {
+        let (a, b) = (value.value(a), value.value(b));
+        match (a.is_nan(), b.is_nan()) {
+            (false, false) => b.partial_cmp(&a).unwrap(),
+            (a_nan, b_nan) => a_nan.cmp(&b_nan),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Sort the children of this node, but not their descendants, by comparing their data with
+    /// `compare`. The sort is stable, children that compare as equal keep their order.
+    ///
+    pub fn sort_children_by(&mut self, mut compare: impl FnMut(&T, &T) -> Ordering) {
+        self.children.sort_by(|a, b| compare(&a.data, &b.data));
+    }
+
+    ///
+    /// Sort the children of every node in this tree by comparing their data with `compare`, as
+    /// [`sort_children_by`](struct.TreeNode.html#method.sort_children_by) does for a single
+    /// node. The tree is traversed without recursion, so very deep trees may be sorted safely.
+    ///
+    pub fn sort_tree_by(&mut self, mut compare: impl FnMut(&T, &T) -> Ordering) {
+        let mut work: Vec<&mut TreeNode<T>> = vec![self];
+        while let Some(node) = work.pop() {
+            node.sort_children_by(&mut compare);
+            work.extend(node.children.iter_mut());
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+    let mut zeros = Ordering::Equal;
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return zeros,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (x_zeros, x_digits) = digit_run(&mut a);
+                let (y_zeros, y_digits) = digit_run(&mut b);
+                let order = x_digits
+                    .len()
+                    .cmp(&y_digits.len())
+                    .then_with(|| x_digits.cmp(&y_digits));
+                if order != Ordering::Equal {
+                    return order;
+                }
+                zeros = zeros.then(x_zeros.cmp(&y_zeros));
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                let _ = a.next();
+                let _ = b.next();
+            }
+        }
+    }
+}
+
+///
+/// Consume a run of ASCII digits, returning the number of leading zeros and the remaining
+/// digits.
+///
+fn digit_run(chars: &mut Peekable<Chars<'_>>) -> (usize, String) {
+    let mut zeros = 0;
+    while chars.next_if_eq(&'0').is_some() {
+        zeros += 1;
+    }
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        digits.push(c);
+    }
+    (zeros, digits)
+}
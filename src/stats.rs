@@ -0,0 +1,101 @@
+/*!
+Aggregate statistics describing the shape of a tree.
+
+All the statistics are gathered in a single traversal of the tree, by
+[`stats`](struct.TreeNode.html#method.stats), and the traversal does not recurse, so very deep
+trees may be measured safely. Unlike the [`RenderStats`](struct.RenderStats.html) returned when
+writing a tree, collapsed nodes are not taken into account.
+*/
+
+use crate::TreeNode;
+use std::fmt::Display;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Statistics describing the shape of a tree, as returned by
+/// [`stats`](struct.TreeNode.html#method.stats).
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TreeStats {
+    /// The number of nodes in the tree, including the root.
+    pub nodes: usize,
+    /// The number of nodes with no children.
+    pub leaves: usize,
+    /// The number of edges on the longest path from the root to a leaf; a tree with only a root
+    /// node has height `0`.
+    pub height: usize,
+    /// The greatest number of nodes at any single depth.
+    pub widest_level: usize,
+    /// The greatest number of children of any single node, that is the most siblings.
+    pub max_children: usize,
+    /// The average number of children of the nodes that have children; `0.0` if no node has
+    /// children.
+    pub average_branching: f64,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return statistics describing the shape of this tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use text_trees::StringTreeNode;
+    ///
+    /// let tree = StringTreeNode::with_child_nodes(
+    ///     "root".to_string(),
+    ///     vec![
+    ///         StringTreeNode::with_children(
+    ///             "Parent".to_string(),
+    ///             vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+    ///         ),
+    ///         "Uncle".into(),
+    ///     ]
+    ///     .into_iter(),
+    /// );
+    ///
+    /// let stats = tree.stats();
+    /// assert_eq!(stats.nodes, 5);
+    /// assert_eq!(stats.leaves, 3);
+    /// assert_eq!(stats.height, 2);
+    /// assert_eq!(stats.average_branching, 2.0);
+    /// ```
+    ///
+    pub fn stats(&self) -> TreeStats {
+        let mut stats = TreeStats::default();
+        let mut level_counts: Vec<usize> = Vec::new();
+        let mut internal_nodes = 0;
+        let mut work = vec![(self, 0)];
+        while let Some((node, depth)) = work.pop() {
+            stats.nodes += 1;
+            if depth == level_counts.len() {
+                level_counts.push(0);
+            }
+            level_counts[depth] += 1;
+            let children = node.children.len();
+            if children == 0 {
+                stats.leaves += 1;
+            } else {
+                internal_nodes += 1;
+                stats.max_children = stats.max_children.max(children);
+                work.extend(node.children.iter().map(|child| (child, depth + 1)));
+            }
+        }
+        stats.height = level_counts.len() - 1;
+        stats.widest_level = level_counts.into_iter().max().unwrap_or_default();
+        if internal_nodes > 0 {
+            stats.average_branching = (stats.nodes - 1) as f64 / internal_nodes as f64;
+        }
+        stats
+    }
+}
@@ -0,0 +1,105 @@
+/*!
+[Proptest](https://proptest-rs.github.io/proptest/) strategies for generating random trees.
+
+The [`tree_strategy`](fn.tree_strategy.html) function combines a strategy for the data of each
+node with bounds on the shape of the tree, and
+[`string_tree_strategy`](fn.string_tree_strategy.html) provides trees with short generated
+labels. Generated trees shrink towards fewer, and smaller, nodes.
+
+# Example
+
+```rust
+use proptest::prelude::*;
+use proptest::test_runner::TestRunner;
+use text_trees::{string_tree_strategy, TreeStrategyOptions};
+
+let mut runner = TestRunner::default();
+runner
+    .run(
+        &string_tree_strategy(&TreeStrategyOptions::default()),
+        |tree| {
+            prop_assert_eq!(tree.to_string().lines().count(), 1 + tree.descendant_count());
+            Ok(())
+        },
+    )
+    .unwrap();
+```
+
+Within a test the strategies are more commonly used with the `proptest!` macro, as in
+`tree in string_tree_strategy(&options)`.
+*/
+
+use crate::TreeNode;
+use proptest::collection::vec;
+use proptest::strategy::{BoxedStrategy, Strategy};
+use std::fmt::{Debug, Display};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The bounds on the shape of the trees generated by
+/// [`tree_strategy`](fn.tree_strategy.html).
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeStrategyOptions {
+    /// The greatest depth of a generated tree, a tree of only a root node has depth `0`.
+    pub max_depth: u32,
+    /// The greatest number of children of any generated node.
+    pub max_children: usize,
+    /// The number of nodes a generated tree should aim for; this is a target rather than a
+    /// bound, but it limits the depth of recursion once reached.
+    pub desired_size: u32,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return a strategy that generates trees whose data is generated by the provided strategy,
+/// with a shape within the provided bounds.
+///
+pub fn tree_strategy<S>(data: S, options: &TreeStrategyOptions) -> BoxedStrategy<TreeNode<S::Value>>
+where
+    S: Strategy + Clone + 'static,
+    S::Value: Display + Debug,
+{
+    let max_children = options.max_children;
+    data.clone()
+        .prop_map(TreeNode::new)
+        .prop_recursive(
+            options.max_depth,
+            options.desired_size,
+            max_children as u32,
+            move |inner| {
+                (data.clone(), vec(inner, 0..=max_children)).prop_map(|(data, children)| {
+                    TreeNode::with_child_nodes(data, children.into_iter())
+                })
+            },
+        )
+        .boxed()
+}
+
+///
+/// Return a strategy that generates trees of short labels, starting with a letter and
+/// containing letters, digits, spaces, and underscores, with a shape within the provided bounds.
+///
+pub fn string_tree_strategy(options: &TreeStrategyOptions) -> BoxedStrategy<TreeNode<String>> {
+    tree_strategy("[a-zA-Z][a-zA-Z0-9_ ]{0,11}", options)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Default for TreeStrategyOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 4,
+            max_children: 5,
+            desired_size: 64,
+        }
+    }
+}
@@ -0,0 +1,234 @@
+/*!
+Writing trees as tables, where the tree occupies the first column and further columns, each with
+a header, are aligned to its right; the view of `exa --tree --long`, or of the variables in a
+debugger.
+
+Each column is a [`TableColumn`](trait.TableColumn.html), which provides the header and the
+text of the column's cell for each node, and is implemented for a pair of a header and a
+closure. The width of every column, including that of the tree, is the display width of its
+widest cell or header, so the entire tree is laid out before any output is written.
+
+# Example
+
+```rust
+use text_trees::{StringTreeNode, TreeFormatting, TreeTable};
+
+let tree = StringTreeNode::with_children(
+    "src".to_string(),
+    vec!["lib.rs".to_string(), "main.rs".to_string()].into_iter(),
+);
+let table = TreeTable::new("Name")
+    .with_column(("Kind", |label: &String| {
+        if label.contains('.') { "file" } else { "dir" }.to_string()
+    }))
+    .with_column(("Length", |label: &String| label.len().to_string()));
+
+let result = tree.to_string_with_table(&TreeFormatting::default(), &table).unwrap();
+assert_eq!(
+    result,
+    "Name         Kind  Length\nsrc          dir   3\n+-- lib.rs   file  6\n'-- main.rs  file  7\n"
+);
+```
+*/
+
+use crate::{
+    classic, collect_rows, AlignedColumns, ClipLines, ColumnAlignment, IoWrite, TreeFormatting,
+    TreeNode, TreeOrientation,
+};
+use std::fmt::{self, Debug, Display, Formatter, Write as _};
+use std::io::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A column of a [`TreeTable`](struct.TreeTable.html), providing the column's header and the
+/// text of its cell for each node.
+///
+/// This trait is implemented for any pair of a header, which implements `Display`, and a
+/// closure of the form `Fn(&T) -> String`.
+///
+pub trait TableColumn<T> {
+    /// Return the header written above this column.
+    fn header(&self) -> String;
+
+    /// Return the text of this column's cell for the provided node data.
+    fn cell(&self, data: &T) -> String;
+}
+
+///
+/// The columns, and options, of a table written by
+/// [`write_with_table`](struct.TreeNode.html#method.write_with_table).
+///
+pub struct TreeTable<'a, T> {
+    /// The header written above the tree column.
+    pub tree_header: String,
+    /// The string written between each column, default is two spaces.
+    pub separator: String,
+    /// If set, a rule of this character is written below the header of each column.
+    pub rule: Option<char>,
+    columns: Vec<Box<dyn TableColumn<T> + 'a>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T, S, F> TableColumn<T> for (S, F)
+where
+    S: Display,
+    F: Fn(&T) -> String,
+{
+    fn header(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn cell(&self, data: &T) -> String {
+        (self.1)(data)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> Debug for TreeTable<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TreeTable")
+            .field("tree_header", &self.tree_header)
+            .field("separator", &self.separator)
+            .field("rule", &self.rule)
+            .field("headers", &self.headers())
+            .finish()
+    }
+}
+
+impl<'a, T> TreeTable<'a, T> {
+    /// Construct a new table, with no columns other than the tree, which has the provided header.
+    pub fn new(tree_header: impl Into<String>) -> Self {
+        Self {
+            tree_header: tree_header.into(),
+            separator: "  ".to_string(),
+            rule: None,
+            columns: Default::default(),
+        }
+    }
+
+    /// Return this table with the provided column added after any existing columns.
+    pub fn with_column(mut self, column: impl TableColumn<T> + 'a) -> Self {
+        self.columns.push(Box::new(column));
+        self
+    }
+
+    /// Return the headers of this table's columns, not including the tree column.
+    pub fn headers(&self) -> Vec<String> {
+        self.columns.iter().map(|column| column.header()).collect()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return a string containing the generated tree text formatted according to the provided
+    /// format settings, as the first column of the provided table.
+    ///
+    pub fn to_string_with_table(
+        &self,
+        format: &TreeFormatting,
+        table: &TreeTable<'_, T>,
+    ) -> Result<String> {
+        self.render_to_string(format, |mut w| {
+            write_tree_with_table(self, &mut w, format, table)
+        })
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` with the provided
+    /// format settings, as the first column of the provided table; a header row is written
+    /// first and the cells of each node are written on the node's line, aligned according to
+    /// the [`column_formats`](struct.TreeFormatting.html#structfield.column_formats) formatting
+    /// option.
+    ///
+    /// _Note_: the `Classic` and `OrgChart` orientations do not have a line per node, and so no
+    /// header is written and each node's cells are written after its label.
+    ///
+    pub fn write_with_table(
+        &self,
+        to_writer: &mut impl Write,
+        format: &TreeFormatting,
+        table: &TreeTable<'_, T>,
+    ) -> Result<()> {
+        self.render_to(&mut IoWrite(to_writer), format, |mut w| {
+            write_tree_with_table(self, &mut w, format, table)
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn write_tree_with_table<T>(
+    node: &TreeNode<T>,
+    w: &mut impl fmt::Write,
+    format: &TreeFormatting,
+    table: &TreeTable<'_, T>,
+) -> fmt::Result
+where
+    T: Display,
+{
+    let cells_of = |node: &TreeNode<T>| -> Vec<String> {
+        table
+            .columns
+            .iter()
+            .map(|column| column.cell(node.data()))
+            .collect()
+    };
+    let mut w = ClipLines::new(w, format);
+
+    if format.orientation != TreeOrientation::TopDown {
+        classic::write_tree(node, &mut w, format, &|node| {
+            let mut cells = vec![format.node_label(node)];
+            cells.extend(cells_of(node));
+            // Only clipped, as there is no column in which to align the cells.
+            AlignedColumns::new(&format.column_formats, ColumnAlignment::Left).measure(&mut cells);
+            cells.join(&table.separator)
+        })?;
+        return w.finish();
+    }
+
+    // Each row is the root connector line, if any, and the cells, the first being the tree column.
+    let mut columns = AlignedColumns::new(&format.column_formats, ColumnAlignment::Left);
+    let mut header = vec![table.tree_header.clone()];
+    header.extend(table.headers());
+    columns.measure(&mut header);
+    let mut rows: Vec<(Option<String>, Vec<String>)> = vec![(None, header)];
+    for (mut row, node) in collect_rows(node, format) {
+        let connector = row.connector.take();
+        let label = row.repeated_label(format.node_label(node));
+        let mut cells = vec![format!("{}{}", row, label)];
+        cells.extend(cells_of(node));
+        columns.measure(&mut cells);
+        rows.push((connector, cells));
+    }
+    if let Some(rule) = table.rule {
+        let rules = columns
+            .widths()
+            .iter()
+            .map(|width| rule.to_string().repeat(*width))
+            .collect();
+        rows.insert(1, (None, rules));
+    }
+
+    for (connector, cells) in rows {
+        if let Some(connector) = connector {
+            writeln!(w, "{}", connector)?;
+        }
+        columns.write_row(&mut w, cells, &table.separator)?;
+        writeln!(w)?;
+    }
+    w.finish()
+}
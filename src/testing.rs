@@ -0,0 +1,330 @@
+/*!
+Support for testing code that produces trees.
+
+The [`assert_tree_eq!`](../macro.assert_tree_eq.html) macro compares two trees and, if they
+differ, panics with a message containing both trees written with the crate's own formatting and
+a list of the differences between them, each identified by its index path. This is far easier to
+read than the `Debug` representation of the two trees.
+
+The [`assert_renders_to!`](../macro.assert_renders_to.html) macro compares the output of a tree
+with an expected string, and the
+[`assert_renders_to_snapshot!`](../macro.assert_renders_to_snapshot.html) macro with the content
+of a snapshot file. Both outputs are first [`normalize`](fn.normalize.html)d so that trailing
+whitespace, and the leading and trailing line breaks of a raw string literal, do not cause a
+comparison to fail.
+
+# Example
+
+```rust
+use text_trees::{assert_tree_eq, StringTreeNode};
+
+let actual = StringTreeNode::with_children(
+    "root".to_string(),
+    vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+);
+let expected = StringTreeNode::with_children(
+    "root".to_string(),
+    vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+);
+
+assert_tree_eq!(actual, expected);
+```
+*/
+
+use crate::{TreeFormatting, TreeNode};
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Assert that two trees are equal, as determined by `PartialEq`; on failure the panic message
+/// contains both trees, as they are written with the default formatting, and the differences
+/// between them as returned by [`structural_diff`](testing/fn.structural_diff.html). An optional
+/// message, with format arguments, may follow the two trees.
+///
+#[macro_export]
+macro_rules! assert_tree_eq {
+    ($actual:expr, $expected:expr $(,)?) => {
+        $crate::testing::assert_tree_eq(&$actual, &$expected, None)
+    };
+    ($actual:expr, $expected:expr, $($arg:tt)+) => {
+        $crate::testing::assert_tree_eq(&$actual, &$expected, Some(format!($($arg)+)))
+    };
+}
+
+///
+/// Assert that a tree, written with the provided format settings, produces the expected text;
+/// both are [`normalize`](testing/fn.normalize.html)d before they are compared. An optional
+/// message, with format arguments, may follow the expected text.
+///
+/// # Example
+///
+/// ```rust
+/// use text_trees::{assert_renders_to, StringTreeNode, TreeFormatting};
+///
+/// let tree = StringTreeNode::with_children(
+///     "root".to_string(),
+///     vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+/// );
+///
+/// assert_renders_to!(
+///     tree,
+///     TreeFormatting::default(),
+///     r#"
+/// root
+/// +-- Child 1
+/// '-- Child 2
+/// "#
+/// );
+/// ```
+///
+#[macro_export]
+macro_rules! assert_renders_to {
+    ($tree:expr, $format:expr, $expected:expr $(,)?) => {
+        $crate::testing::assert_renders_to(&$tree, &$format, $expected, None)
+    };
+    ($tree:expr, $format:expr, $expected:expr, $($arg:tt)+) => {
+        $crate::testing::assert_renders_to(&$tree, &$format, $expected, Some(format!($($arg)+)))
+    };
+}
+
+///
+/// Assert that a tree, written with the provided format settings, produces the text held in the
+/// snapshot file at the provided path, as
+/// [`assert_renders_to_snapshot`](testing/fn.assert_renders_to_snapshot.html) describes.
+///
+#[macro_export]
+macro_rules! assert_renders_to_snapshot {
+    ($tree:expr, $format:expr, $path:expr $(,)?) => {
+        $crate::testing::assert_renders_to_snapshot(&$tree, &$format, $path)
+    };
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return a description of each difference between the two trees, in their labels, their
+/// collapsed state, their visibility, or their children, in the order the nodes are written; an
+/// empty list denotes that the trees are equal. Each difference is prefixed with the index path
+/// of the node that differs, for example:
+///
+/// ```text
+/// at [1]: "Parent" != "Parent 2"
+/// at [1]: collapsed true != false
+/// at [1, 2]: only in actual "Child 3"
+/// ```
+///
+pub fn structural_diff<T>(actual: &TreeNode<T>, expected: &TreeNode<T>) -> Vec<String>
+where
+    T: Display + PartialEq,
+{
+    let mut differences: Vec<(Vec<usize>, String)> = Vec::new();
+    let mut work = vec![(Vec::new(), actual, expected)];
+    while let Some((path, actual, expected)) = work.pop() {
+        if actual.data != expected.data {
+            differences.push((
+                path.clone(),
+                format!("{:?} != {:?}", actual.label(), expected.label()),
+            ));
+        }
+        if actual.collapsed != expected.collapsed {
+            differences.push((
+                path.clone(),
+                format!("collapsed {} != {}", actual.collapsed, expected.collapsed),
+            ));
+        }
+        if actual.visibility != expected.visibility {
+            differences.push((
+                path.clone(),
+                format!(
+                    "visibility {:?} != {:?}",
+                    actual.visibility, expected.visibility
+                ),
+            ));
+        }
+        let common = actual.children.len().min(expected.children.len());
+        for (index, child) in actual.children.iter().enumerate().skip(common) {
+            differences.push((
+                child_path(&path, index),
+                format!("only in actual {:?}", child.label()),
+            ));
+        }
+        for (index, child) in expected.children.iter().enumerate().skip(common) {
+            differences.push((
+                child_path(&path, index),
+                format!("only in expected {:?}", child.label()),
+            ));
+        }
+        for index in (0..common).rev() {
+            work.push((
+                child_path(&path, index),
+                &actual.children[index],
+                &expected.children[index],
+            ));
+        }
+    }
+    // Extra children are found before the descendants of the common children, sorting by path
+    // returns the differences in the order the nodes are written.
+    differences.sort_by(|(a, _), (b, _)| a.cmp(b));
+    differences
+        .into_iter()
+        .map(|(path, difference)| format!("at {:?}: {}", path, difference))
+        .collect()
+}
+
+///
+/// The implementation of [`assert_tree_eq!`](../macro.assert_tree_eq.html), panics if the two
+/// trees are not equal.
+///
+#[track_caller]
+pub fn assert_tree_eq<T>(actual: &TreeNode<T>, expected: &TreeNode<T>, message: Option<String>)
+where
+    T: Display + PartialEq,
+{
+    if actual != expected {
+        panic!(
+            "assertion failed: `(actual == expected)`{}\n\nactual:\n{}\nexpected:\n{}\ndifferences:\n{}\n",
+            message.map(|m| format!(": {}", m)).unwrap_or_default(),
+            actual,
+            expected,
+            structural_diff(actual, expected)
+                .iter()
+                .map(|difference| format!("  {}", difference))
+                .collect::<Vec<String>>()
+                .join("\n")
+        );
+    }
+}
+
+///
+/// Return the provided tree text normalized for comparison: trailing whitespace is removed from
+/// each line, a single leading line break is removed, and any trailing line breaks are replaced
+/// with exactly one. This allows expected output to be written as a raw string literal
+/// starting on the line after its opening quote; leading whitespace is kept, so the literal's
+/// lines must not be indented.
+///
+pub fn normalize(text: &str) -> String {
+    let text = text
+        .strip_prefix("\r\n")
+        .or_else(|| text.strip_prefix('\n'))
+        .unwrap_or(text);
+    let mut normalized: String = text
+        .lines()
+        .map(|line| format!("{}\n", line.trim_end()))
+        .collect();
+    while normalized.ends_with("\n\n") {
+        let _ = normalized.pop();
+    }
+    if normalized == "\n" {
+        normalized.clear();
+    }
+    normalized
+}
+
+///
+/// The implementation of [`assert_renders_to!`](../macro.assert_renders_to.html), panics if the
+/// normalized output of the tree is not equal to the normalized expected text.
+///
+#[track_caller]
+pub fn assert_renders_to<T>(
+    tree: &TreeNode<T>,
+    format: &TreeFormatting,
+    expected: &str,
+    message: Option<String>,
+) where
+    T: Display,
+{
+    let actual = render(tree, format);
+    let expected = normalize(expected);
+    if actual != expected {
+        panic!(
+            "assertion failed: `(actual == expected)`{}\n\nactual:\n{}\nexpected:\n{}",
+            message.map(|m| format!(": {}", m)).unwrap_or_default(),
+            actual,
+            expected
+        );
+    }
+}
+
+///
+/// The implementation of
+/// [`assert_renders_to_snapshot!`](../macro.assert_renders_to_snapshot.html), panics if the
+/// normalized output of the tree is not equal to the normalized content of the snapshot file at
+/// the provided path.
+///
+/// If the environment variable `TEXT_TREES_UPDATE_SNAPSHOTS` is set to `1` the output is
+/// written to the file, creating any missing parent directories, and the assertion passes; the
+/// new snapshot should be reviewed before it is committed. Otherwise a missing snapshot file
+/// fails the assertion, with the output in the panic message, so that a snapshot that was never
+/// committed, or a wrong path, does not pass unnoticed.
+///
+#[track_caller]
+pub fn assert_renders_to_snapshot<T>(
+    tree: &TreeNode<T>,
+    format: &TreeFormatting,
+    path: impl AsRef<Path>,
+) where
+    T: Display,
+{
+    let path = path.as_ref();
+    let actual = render(tree, format);
+    let update = std::env::var(UPDATE_SNAPSHOTS).is_ok_and(|value| value == "1");
+    if update {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap_or_else(|e| {
+                panic!("could not create snapshot directory {:?}: {}", parent, e)
+            });
+        }
+        fs::write(path, &actual)
+            .unwrap_or_else(|e| panic!("could not write snapshot {:?}: {}", path, e));
+        return;
+    }
+    if !path.exists() {
+        panic!(
+            "assertion failed: snapshot {:?} does not exist, set {}=1 to create it\n\nactual:\n{}",
+            path, UPDATE_SNAPSHOTS, actual
+        );
+    }
+    let expected = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("could not read snapshot {:?}: {}", path, e));
+    let expected = normalize(&expected);
+    if actual != expected {
+        panic!(
+            "assertion failed: output does not match snapshot {:?}, set {}=1 to update it\n\nactual:\n{}\nexpected:\n{}",
+            path, UPDATE_SNAPSHOTS, actual, expected
+        );
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+const UPDATE_SNAPSHOTS: &str = "TEXT_TREES_UPDATE_SNAPSHOTS";
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+#[track_caller]
+fn render<T>(tree: &TreeNode<T>, format: &TreeFormatting) -> String
+where
+    T: Display,
+{
+    let mut buffer = String::new();
+    tree.write_with_format_fmt(&mut buffer, format)
+        .unwrap_or_else(|e| panic!("could not write tree: {}", e));
+    normalize(&buffer)
+}
+
+fn child_path(path: &[usize], index: usize) -> Vec<usize> {
+    let mut child_path = path.to_vec();
+    child_path.push(index);
+    child_path
+}
@@ -0,0 +1,205 @@
+/*!
+Conversion of values into trees of labels, see [`ToTreeNode`](trait.ToTreeNode.html).
+*/
+
+use crate::{StringTreeNode, TreeNode};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::Display;
+use std::rc::Rc;
+use std::sync::Arc;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Implemented by types that can represent themselves as a tree of labels. This is implemented
+/// for the primitive types, strings, and the common containers; with the `derive` feature it
+/// may be derived for structs and enums with `#[derive(DisplayTree)]`.
+///
+/// # Example
+///
+/// ```rust
+/// use text_trees::{StringTreeNode, ToTreeNode};
+///
+/// struct Server {
+///     host: String,
+///     ports: Vec<u16>,
+/// }
+///
+/// impl ToTreeNode for Server {
+///     fn to_tree_node(&self) -> StringTreeNode {
+///         StringTreeNode::with_child_nodes(
+///             "Server".to_string(),
+///             vec![
+///                 self.host.to_tree_node_named("host"),
+///                 self.ports.to_tree_node_named("ports"),
+///             ]
+///             .into_iter(),
+///         )
+///     }
+/// }
+///
+/// let server = Server { host: "localhost".to_string(), ports: vec![80, 443] };
+/// assert_eq!(
+///     server.to_tree_node().to_string(),
+///     "Server\n+-- host: localhost\n'-- ports\n    +-- 80\n    '-- 443\n"
+/// );
+/// ```
+///
+pub trait ToTreeNode {
+    /// Return a tree representing this value.
+    fn to_tree_node(&self) -> StringTreeNode;
+
+    /// Return a tree representing this value as a named member of some parent, such as a struct
+    /// field. By default the node's label is prefixed with the name, as `name: label`.
+    fn to_tree_node_named(&self, name: &str) -> StringTreeNode {
+        let mut node = self.to_tree_node();
+        node.data = format!("{}: {}", name, node.data);
+        node
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+macro_rules! display_leaf {
+    ($($t:ty),*) => {
+        $(
+            impl ToTreeNode for $t {
+                fn to_tree_node(&self) -> StringTreeNode {
+                    TreeNode::new(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+display_leaf!(
+    bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, str,
+    String
+);
+
+macro_rules! delegate {
+    ($($t:ident),*) => {
+        $(
+            impl<T> ToTreeNode for $t<T>
+            where
+                T: ToTreeNode + ?Sized,
+            {
+                fn to_tree_node(&self) -> StringTreeNode {
+                    self.as_ref().to_tree_node()
+                }
+
+                fn to_tree_node_named(&self, name: &str) -> StringTreeNode {
+                    self.as_ref().to_tree_node_named(name)
+                }
+            }
+        )*
+    };
+}
+
+delegate!(Box, Rc, Arc);
+
+impl<T> ToTreeNode for &T
+where
+    T: ToTreeNode + ?Sized,
+{
+    fn to_tree_node(&self) -> StringTreeNode {
+        (*self).to_tree_node()
+    }
+
+    fn to_tree_node_named(&self, name: &str) -> StringTreeNode {
+        (*self).to_tree_node_named(name)
+    }
+}
+
+impl<T> ToTreeNode for Option<T>
+where
+    T: ToTreeNode,
+{
+    fn to_tree_node(&self) -> StringTreeNode {
+        match self {
+            None => TreeNode::new("None".to_string()),
+            Some(value) => value.to_tree_node(),
+        }
+    }
+
+    fn to_tree_node_named(&self, name: &str) -> StringTreeNode {
+        match self {
+            None => TreeNode::new(format!("{}: None", name)),
+            Some(value) => value.to_tree_node_named(name),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+macro_rules! sequence {
+    ($($t:ty),*) => {
+        $(
+            impl<T> ToTreeNode for $t
+            where
+                T: ToTreeNode,
+            {
+                fn to_tree_node(&self) -> StringTreeNode {
+                    sequence_node("[]".to_string(), self.iter())
+                }
+
+                fn to_tree_node_named(&self, name: &str) -> StringTreeNode {
+                    sequence_node(name.to_string(), self.iter())
+                }
+            }
+        )*
+    };
+}
+
+sequence!([T], Vec<T>, VecDeque<T>);
+
+macro_rules! mapping {
+    ($($t:ident),*) => {
+        $(
+            impl<K, V> ToTreeNode for $t<K, V>
+            where
+                K: Display,
+                V: ToTreeNode,
+            {
+                fn to_tree_node(&self) -> StringTreeNode {
+                    mapping_node("{}".to_string(), self.iter())
+                }
+
+                fn to_tree_node_named(&self, name: &str) -> StringTreeNode {
+                    mapping_node(name.to_string(), self.iter())
+                }
+            }
+        )*
+    };
+}
+
+mapping!(BTreeMap, HashMap);
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn sequence_node<'a, T>(label: String, elements: impl Iterator<Item = &'a T>) -> StringTreeNode
+where
+    T: ToTreeNode + 'a,
+{
+    TreeNode::with_child_nodes(label, elements.map(ToTreeNode::to_tree_node))
+}
+
+fn mapping_node<'a, K, V>(
+    label: String,
+    members: impl Iterator<Item = (&'a K, &'a V)>,
+) -> StringTreeNode
+where
+    K: Display + 'a,
+    V: ToTreeNode + 'a,
+{
+    TreeNode::with_child_nodes(
+        label,
+        members.map(|(key, value)| value.to_tree_node_named(&key.to_string())),
+    )
+}
@@ -0,0 +1,99 @@
+/*!
+Rendering of [toml](https://docs.rs/toml) values as trees, available with the `toml` feature.
+Tables become branches of the tree and keys with scalar values become leaves, using the same
+[`ValueTreeOptions`](struct.ValueTreeOptions.html) as the JSON renderer.
+
+# Example
+
+```rust
+use text_trees::{toml_tree, ValueTreeOptions};
+
+let config: toml::Value = toml::from_str(r#"
+[server]
+host = "localhost"
+port = 8080
+"#).unwrap();
+let tree = toml_tree(&config, &ValueTreeOptions {
+    root_label: "config".to_string(),
+    ..Default::default()
+});
+
+assert_eq!(
+    tree.to_string(),
+    r#"config
+'-- server
+    +-- host: "localhost"
+    '-- port: 8080
+"#
+);
+```
+*/
+
+use crate::value_tree::{string_tree, write_value_tree, TreeValue, ValueShape};
+use crate::{StringTreeNode, TreeFormatting, ValueTreeOptions};
+use std::io::{Result, Write};
+use toml::Value;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return a tree representing the provided TOML value, according to the provided options. A
+/// parsed document is represented by the `Value::Table` variant.
+///
+pub fn toml_tree(value: &Value, options: &ValueTreeOptions) -> StringTreeNode {
+    string_tree(value, options)
+}
+
+///
+/// Write the tree representing the provided TOML value to the provided implementation of
+/// `std::io::Write` with the provided format settings and options.
+///
+pub fn write_toml_value(
+    value: &Value,
+    to_writer: &mut impl Write,
+    format: &TreeFormatting,
+    options: &ValueTreeOptions,
+) -> Result<()> {
+    write_value_tree(value, to_writer, format, options)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl From<&Value> for StringTreeNode {
+    fn from(v: &Value) -> Self {
+        toml_tree(v, &Default::default())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl TreeValue for Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Integer(_) => "integer",
+            Value::Float(_) => "float",
+            Value::Boolean(_) => "boolean",
+            Value::Datetime(_) => "datetime",
+            Value::Array(_) => "array",
+            Value::Table(_) => "table",
+        }
+    }
+
+    fn shape(&self) -> ValueShape<'_, Self> {
+        match self {
+            Value::Array(elements) => ValueShape::Sequence(elements.iter().collect()),
+            Value::Table(members) => ValueShape::Mapping(
+                members
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value))
+                    .collect(),
+            ),
+            scalar => ValueShape::Scalar(scalar.to_string()),
+        }
+    }
+}
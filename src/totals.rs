@@ -0,0 +1,552 @@
+/*!
+Writing trees with a column of totals, where the total of each node is its own value plus the
+totals of all of its children; the `du` style of view.
+
+Totals are computed from every node in the tree, including the descendants of collapsed and
+hidden nodes, as those nodes still contribute to the total of the ancestors that are written.
+The column is aligned, in the same way as badges written after labels, so the entire tree is
+laid out before any output is written.
+
+# Example
+
+```rust
+use text_trees::{StringTreeNode, TotalsOptions, TreeFormatting};
+
+let tree = StringTreeNode::with_child_nodes(
+    "src 0".to_string(),
+    vec![
+        StringTreeNode::with_children(
+            "bin 0".to_string(),
+            vec!["main.rs 10".to_string()].into_iter(),
+        ),
+        "lib.rs 32".into(),
+    ]
+    .into_iter(),
+);
+let size = |label: &String| label.rsplit(' ').next().unwrap().parse().unwrap_or(0.0);
+
+let result = tree
+    .to_string_with_totals(&TreeFormatting::default(), &TotalsOptions::default(), &size)
+    .unwrap();
+assert_eq!(
+    result,
+    "src 0              42\n+-- bin 0          10\n|   '-- main.rs 10 10\n'-- lib.rs 32      32\n"
+);
+```
+*/
+
+use crate::{
+    classic, collect_rows, repeated_leaf_runs, AlignedColumns, ClipLines, ColumnAlignment, IoWrite,
+    TreeFormatting, TreeNode, TreeOrientation,
+};
+use std::collections::HashMap;
+use std::fmt::{self, Display, Write as _};
+use std::io::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Provides the numeric value of a node's data, such as the size of a file or the time spent in
+/// a function, from which the totals of each node are computed.
+///
+/// This trait is implemented for any closure of the form `Fn(&T) -> f64`.
+///
+pub trait NodeValue<T> {
+    /// Return the value of the provided node data, not including any of its children.
+    fn value(&self, data: &T) -> f64;
+}
+
+///
+/// The options controlling how the totals column is written by
+/// [`write_with_totals`](struct.TreeNode.html#method.write_with_totals).
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct TotalsOptions {
+    /// The number of digits written after the decimal point of each total, default is `0`.
+    pub precision: usize,
+    /// The string written between the widest label and the totals column, and between each
+    /// column, default is a single space.
+    pub separator: String,
+    /// If `true`, a column is written after the totals with each node's total as a percentage,
+    /// to one decimal place, of its parent's total; the root's is `100.0%`. Where the parent's
+    /// total is zero, as is the root's own total for a tree of zeros, the percentage is written
+    /// as `-`.
+    pub percentages: bool,
+    /// If set, a column is written after the totals, and any percentages, with a bar whose
+    /// length is proportional to each node's total.
+    pub bar: Option<BarOptions>,
+    /// If set, either the labels or the bars are colored according to each node's total, on a
+    /// scale from the smallest total in the tree to the largest.
+    pub color: Option<ColorScale>,
+}
+
+///
+/// The options controlling the bar column written by
+/// [`write_with_totals`](struct.TreeNode.html#method.write_with_totals), for example:
+///
+/// ```text
+/// /                   3260 ▇▇▇▇▇▇▇▇▇▇
+/// +-- bin              180 ▇▁▁▁▁▁▁▁▁▁
+/// '-- usr             2056 ▇▇▇▇▇▇▁▁▁▁
+/// ```
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct BarOptions {
+    /// The width of the bar in characters, default is `10`.
+    pub width: usize,
+    /// The total that a full bar represents, default is `BarScale::Global`.
+    pub scale: BarScale,
+    /// The character used for the filled part of the bar, default is `'▇'`.
+    pub filled: char,
+    /// The character used for the empty part of the bar, default is `'▁'`.
+    pub empty: char,
+}
+
+///
+/// A color ramp, from the color of the smallest total to that of the largest, through any
+/// intermediate colors; colors are written as ANSI 24-bit color escape sequences.
+///
+/// _Note_: the escape sequences are not taken into account by the `max_line_width` formatting
+/// option, and colors are not written in the `Classic` and `OrgChart` orientations as their layout
+/// relies on the width of each label.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorScale {
+    /// The part of each line that is colored.
+    pub target: ColorTarget,
+    /// The colors of the ramp, as red, green, and blue components; if empty nothing is colored.
+    pub stops: Vec<(u8, u8, u8)>,
+}
+
+///
+/// Denotes the part of each line colored by a [`ColorScale`](struct.ColorScale.html).
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorTarget {
+    /// The label of each node is colored.
+    Labels,
+    /// The bar of each node is colored, this requires the `bar` option to be set.
+    Bars,
+}
+
+///
+/// Denotes the total that a full bar represents.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BarScale {
+    /// A full bar is the largest total in the tree, usually that of the root.
+    Global,
+    /// A full bar is the largest total of the nodes at the same depth, so that the nodes at
+    /// each level of the tree may be compared with each other.
+    PerLevel,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<T, F> NodeValue<T> for F
+where
+    F: Fn(&T) -> f64,
+{
+    fn value(&self, data: &T) -> f64 {
+        self(data)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Default for TotalsOptions {
+    fn default() -> Self {
+        Self {
+            precision: 0,
+            separator: " ".to_string(),
+            percentages: false,
+            bar: None,
+            color: None,
+        }
+    }
+}
+
+impl Default for BarOptions {
+    fn default() -> Self {
+        Self {
+            width: 10,
+            scale: BarScale::Global,
+            filled: '▇',
+            empty: '▁',
+        }
+    }
+}
+
+impl BarOptions {
+    ///
+    /// Return the bar for `total`, where a full bar represents `full`; negative totals are
+    /// written as an empty bar.
+    ///
+    fn bar(&self, total: f64, full: f64) -> String {
+        let filled = if full > 0.0 && total > 0.0 {
+            ((total / full).min(1.0) * self.width as f64).round() as usize
+        } else {
+            0
+        };
+        let mut bar = String::with_capacity(self.width * 3);
+        bar.extend(std::iter::repeat(self.filled).take(filled));
+        bar.extend(std::iter::repeat(self.empty).take(self.width - filled));
+        bar
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl ColorScale {
+    /// Construct a ramp from green, through yellow, to red, applied to the provided target.
+    pub fn green_to_red(target: ColorTarget) -> Self {
+        Self {
+            target,
+            stops: vec![(0, 200, 0), (230, 210, 0), (220, 0, 0)],
+        }
+    }
+
+    ///
+    /// Return the color at `fraction`, between `0.0` and `1.0`, along the ramp; interpolating
+    /// between the two closest stops. `None` is returned if the ramp has no stops.
+    ///
+    pub fn color(&self, fraction: f64) -> Option<(u8, u8, u8)> {
+        let last = self.stops.len().checked_sub(1)?;
+        let position = fraction.clamp(0.0, 1.0) * last as f64;
+        let index = (position.floor() as usize).min(last.saturating_sub(1));
+        let (from, to) = (self.stops[index], self.stops[(index + 1).min(last)]);
+        let within = position - index as f64;
+        let mix =
+            |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * within).round() as u8;
+        Some((mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2)))
+    }
+
+    ///
+    /// Return the provided text wrapped in the escape sequences for the color at `fraction`, or
+    /// the text unchanged if the ramp has no stops.
+    ///
+    pub fn paint(&self, text: &str, fraction: f64) -> String {
+        match self.color(fraction) {
+            Some((red, green, blue)) => {
+                format!("\x1b[38;2;{};{};{}m{}\x1b[0m", red, green, blue, text)
+            }
+            None => text.to_string(),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Return the total of this node, its own value plus the totals of all of its children.
+    ///
+    pub fn total(&self, value: &impl NodeValue<T>) -> f64 {
+        let mut total = 0.0;
+        let mut work: Vec<&TreeNode<T>> = vec![self];
+        while let Some(node) = work.pop() {
+            total += value.value(&node.data);
+            work.extend(node.children.iter());
+        }
+        total
+    }
+
+    ///
+    /// Return a string containing the generated tree text formatted according to the provided
+    /// format settings, with each node's total written in a right-aligned column.
+    ///
+    /// _Note_: the `Classic` and `OrgChart` orientations do not have a column for totals, and so
+    /// each total is written after the node's label.
+    ///
+    pub fn to_string_with_totals(
+        &self,
+        format: &TreeFormatting,
+        options: &TotalsOptions,
+        value: &impl NodeValue<T>,
+    ) -> Result<String> {
+        self.render_to_string(format, |mut w| {
+            write_tree_with_totals(self, &mut w, format, options, value)
+        })
+    }
+
+    ///
+    /// Write this tree to the provided implementation of `std::io::Write` with the provided
+    /// format settings, with each node's total written in a right-aligned column.
+    ///
+    pub fn write_with_totals(
+        &self,
+        to_writer: &mut impl Write,
+        format: &TreeFormatting,
+        options: &TotalsOptions,
+        value: &impl NodeValue<T>,
+    ) -> Result<()> {
+        self.render_to(&mut IoWrite(to_writer), format, |mut w| {
+            write_tree_with_totals(self, &mut w, format, options, value)
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A row written with totals, where widths are measured before any color is applied.
+///
+#[derive(Clone, Debug)]
+struct TotalsRow {
+    /// The root connector line written before this row, if any.
+    connector: Option<String>,
+    prefix: String,
+    label: String,
+    /// The cells of the row, the first being the prefix and label, as they are aligned.
+    cells: Vec<String>,
+    /// The position of the node's total between the smallest and largest totals.
+    fraction: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct NodeTotal {
+    total: f64,
+    parent_total: f64,
+    /// The depth of the node, the root has depth `0`.
+    depth: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return the total of every node in the tree, keyed by the node's address. Nodes are listed
+/// breadth first, with the index of their parent, so that the totals may be summed in reverse
+/// order without recursion.
+///
+fn node_totals<T>(node: &TreeNode<T>, value: &impl NodeValue<T>) -> HashMap<usize, NodeTotal>
+where
+    T: Display,
+{
+    let mut nodes: Vec<(&TreeNode<T>, usize, usize)> = vec![(node, 0, 0)];
+    let mut index = 0;
+    while index < nodes.len() {
+        let (parent, _, depth) = nodes[index];
+        nodes.extend(
+            parent
+                .children
+                .iter()
+                .map(|child| (child, index, depth + 1)),
+        );
+        index += 1;
+    }
+
+    let mut totals: Vec<f64> = nodes
+        .iter()
+        .map(|(node, _, _)| value.value(&node.data))
+        .collect();
+    for index in (1..nodes.len()).rev() {
+        totals[nodes[index].1] += totals[index];
+    }
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(index, (node, parent, depth))| {
+            (
+                address(node),
+                NodeTotal {
+                    total: totals[index],
+                    parent_total: totals[*parent],
+                    depth: *depth,
+                },
+            )
+        })
+        .collect()
+}
+
+fn write_tree_with_totals<T>(
+    node: &TreeNode<T>,
+    w: &mut impl fmt::Write,
+    format: &TreeFormatting,
+    options: &TotalsOptions,
+    value: &impl NodeValue<T>,
+) -> fmt::Result
+where
+    T: Display,
+{
+    let totals = node_totals(node, value);
+    let run_totals = if format.orientation == TreeOrientation::TopDown {
+        run_totals(node, format, &totals)
+    } else {
+        HashMap::new()
+    };
+    // The total written for a node, which for a row standing for a run of repeated leaves is the
+    // total of the run.
+    let total_of = |node: &TreeNode<T>| {
+        let address = address(node);
+        run_totals
+            .get(&address)
+            .copied()
+            .unwrap_or_else(|| totals[&address])
+    };
+    let mut level_maxima: Vec<f64> = Vec::new();
+    for node_total in totals.values().chain(run_totals.values()) {
+        if node_total.depth >= level_maxima.len() {
+            level_maxima.resize(node_total.depth + 1, f64::MIN);
+        }
+        let maximum = &mut level_maxima[node_total.depth];
+        *maximum = maximum.max(node_total.total);
+    }
+    let global_maximum = level_maxima.iter().cloned().fold(f64::MIN, f64::max);
+    let global_minimum = totals
+        .values()
+        .chain(run_totals.values())
+        .map(|node_total| node_total.total)
+        .fold(f64::MAX, f64::min);
+    let fraction_of = |node: &TreeNode<T>| {
+        let range = global_maximum - global_minimum;
+        if range > 0.0 {
+            (total_of(node).total - global_minimum) / range
+        } else {
+            0.0
+        }
+    };
+
+    let columns_of = |node: &TreeNode<T>| {
+        let NodeTotal {
+            total,
+            parent_total,
+            depth,
+        } = total_of(node);
+        let mut columns = vec![format!("{:.*}", options.precision, total)];
+        if options.percentages {
+            columns.push(if parent_total == 0.0 {
+                "-".to_string()
+            } else {
+                format!("{:.1}%", total / parent_total * 100.0)
+            });
+        }
+        if let Some(bar) = &options.bar {
+            columns.push(match bar.scale {
+                BarScale::Global => bar.bar(total, global_maximum),
+                BarScale::PerLevel => bar.bar(total, level_maxima[depth]),
+            });
+        }
+        columns
+    };
+    let mut w = ClipLines::new(w, format);
+
+    if format.orientation != TreeOrientation::TopDown {
+        classic::write_tree(node, &mut w, format, &|node| {
+            let mut cells = vec![format.node_label(node)];
+            cells.extend(columns_of(node));
+            // Only clipped, as there is no column in which to align the cells.
+            AlignedColumns::new(&format.column_formats, ColumnAlignment::Right).measure(&mut cells);
+            cells.join(" ")
+        })?;
+        return w.finish();
+    }
+
+    let mut columns = AlignedColumns::new(&format.column_formats, ColumnAlignment::Right);
+    let rows: Vec<TotalsRow> = collect_rows(node, format)
+        .into_iter()
+        .map(|(mut row, node)| {
+            let prefix = row.to_string();
+            let label = row.repeated_label(format.node_label(node));
+            let mut cells = vec![format!("{}{}", prefix, label)];
+            cells.extend(columns_of(node));
+            columns.measure(&mut cells);
+            TotalsRow {
+                connector: row.connector.take(),
+                prefix,
+                label,
+                cells,
+                fraction: fraction_of(node),
+            }
+        })
+        .collect();
+
+    let bar_column = options.bar.as_ref().map(|_| columns.widths().len() - 1);
+    for TotalsRow {
+        connector,
+        prefix,
+        label,
+        cells,
+        fraction,
+    } in rows
+    {
+        if let Some(connector) = connector {
+            writeln!(w, "{}", connector)?;
+        }
+        columns.write_styled_row(
+            &mut w,
+            cells,
+            &options.separator,
+            |index, cell| match &options.color {
+                Some(scale) if scale.target == ColorTarget::Labels && index == 0 => {
+                    format!("{}{}", prefix, scale.paint(&label, fraction))
+                }
+                Some(scale) if scale.target == ColorTarget::Bars && bar_column == Some(index) => {
+                    scale.paint(&cell, fraction)
+                }
+                _ => cell,
+            },
+        )?;
+        writeln!(w)?;
+    }
+    w.finish()
+}
+
+///
+/// Return the total of each run of repeated leaves written as a single row, keyed by the address
+/// of the first node of the run, if the format collapses repeated leaves.
+///
+fn run_totals<T>(
+    node: &TreeNode<T>,
+    format: &TreeFormatting,
+    totals: &HashMap<usize, NodeTotal>,
+) -> HashMap<usize, NodeTotal>
+where
+    T: Display,
+{
+    let mut run_totals = HashMap::new();
+    if !format.collapse_repeated_leaves {
+        return run_totals;
+    }
+    let mut work: Vec<&TreeNode<T>> = vec![node];
+    while let Some(node) = work.pop() {
+        let children: Vec<&TreeNode<T>> = node.visible_children().collect();
+        for (first, run) in
+            repeated_leaf_runs(children.iter().copied()).filter(|(_, run)| run.len() > 1)
+        {
+            let first = address(first);
+            let total = children[run]
+                .iter()
+                .map(|child| totals[&address(child)].total)
+                .sum();
+            let _ = run_totals.insert(
+                first,
+                NodeTotal {
+                    total,
+                    ..totals[&first]
+                },
+            );
+        }
+        work.extend(children);
+    }
+    run_totals
+}
+
+#[inline]
+fn address<T>(node: &TreeNode<T>) -> usize
+where
+    T: Display,
+{
+    let node: *const TreeNode<T> = node;
+    node as usize
+}
@@ -0,0 +1,133 @@
+/*!
+A single abstraction over the two kinds of writer, `std::io::Write` and `std::fmt::Write`, so
+that a tree may be written to either with one method,
+[`write_to`](struct.TreeNode.html#method.write_to).
+
+As a type may implement both `std::io::Write` and `std::fmt::Write`, the
+[`TreeWrite`](trait.TreeWrite.html) trait is implemented for the adapters
+[`IoWrite`](struct.IoWrite.html) and [`FmtWrite`](struct.FmtWrite.html), which wrap a writer,
+or a mutable reference to one, and for `String`. New kinds of output need only implement
+`TreeWrite` to be usable with `write_to`.
+
+Every method that writes a tree, whether it returns a string or writes to an `std::io::Write`,
+does so through `TreeWrite`, so that the limits of the format are checked, and output is flushed,
+in the same way for each of them.
+
+# Example
+
+```rust
+use text_trees::{FmtWrite, IoWrite, StringTreeNode, TreeFormatting};
+
+let tree = StringTreeNode::with_children("root".to_string(), vec!["a".to_string()].into_iter());
+let format = TreeFormatting::default();
+
+let mut bytes: Vec<u8> = Vec::new();
+tree.write_to(&mut IoWrite(&mut bytes), &format).unwrap();
+
+let mut text = String::new();
+tree.write_to(&mut FmtWrite(&mut text), &format).unwrap();
+
+assert_eq!(String::from_utf8(bytes).unwrap(), text);
+```
+*/
+
+use crate::{write_io, write_tree_inner, FlushPolicy, TreeFormatting, TreeNode};
+use std::fmt::{self, Display};
+use std::io::{self, Result};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A destination for tree text, implemented for writers of either `std::io::Write` or
+/// `std::fmt::Write`.
+///
+pub trait TreeWrite {
+    ///
+    /// Write the text produced by `render`, which is given an implementation of
+    /// `std::fmt::Write` that writes to this destination, flushing the destination according to
+    /// `flush_policy` where it can be flushed. Any error from the destination is returned, rather
+    /// than the `fmt::Error` seen by `render`.
+    ///
+    fn write_text<F>(&mut self, flush_policy: FlushPolicy, render: F) -> Result<()>
+    where
+        F: FnOnce(&mut dyn fmt::Write) -> fmt::Result;
+}
+
+///
+/// Adapts an implementation of `std::io::Write` to [`TreeWrite`](trait.TreeWrite.html); output
+/// is buffered internally, as it is by all the methods that write to an `std::io::Write`, and
+/// flushed according to the `flush_policy` formatting option.
+///
+/// As a [`LineSink`](trait.LineSink.html) each line is written directly to the wrapped writer,
+/// which is not buffered; wrap a writer such as a `File`, or `Stdout`, in a `BufWriter` to avoid
+/// a write to the underlying writer for each line.
+///
+#[derive(Debug)]
+pub struct IoWrite<W>(pub W)
+where
+    W: io::Write;
+
+///
+/// Adapts an implementation of `std::fmt::Write` to [`TreeWrite`](trait.TreeWrite.html); a
+/// `fmt::Error` is returned as an `std::io::Error` of kind `Other`.
+///
+#[derive(Debug)]
+pub struct FmtWrite<W>(pub W)
+where
+    W: fmt::Write;
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<W> TreeWrite for IoWrite<W>
+where
+    W: io::Write,
+{
+    fn write_text<F>(&mut self, flush_policy: FlushPolicy, render: F) -> Result<()>
+    where
+        F: FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+    {
+        write_io(&mut self.0, flush_policy, |w| render(w))
+    }
+}
+
+impl<W> TreeWrite for FmtWrite<W>
+where
+    W: fmt::Write,
+{
+    fn write_text<F>(&mut self, _: FlushPolicy, render: F) -> Result<()>
+    where
+        F: FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+    {
+        render(&mut self.0).map_err(io::Error::other)
+    }
+}
+
+impl TreeWrite for String {
+    fn write_text<F>(&mut self, flush_policy: FlushPolicy, render: F) -> Result<()>
+    where
+        F: FnOnce(&mut dyn fmt::Write) -> fmt::Result,
+    {
+        FmtWrite(self).write_text(flush_policy, render)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl<T> TreeNode<T>
+where
+    T: Display,
+{
+    ///
+    /// Write this tree to the provided [`TreeWrite`](trait.TreeWrite.html) destination, of
+    /// either kind, with the provided format settings.
+    ///
+    pub fn write_to(&self, to_writer: &mut impl TreeWrite, format: &TreeFormatting) -> Result<()> {
+        self.render_to(to_writer, format, |mut w| {
+            write_tree_inner(self, &mut w, format, &|node| format.node_label(node))
+        })
+    }
+}
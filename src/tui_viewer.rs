@@ -0,0 +1,476 @@
+/*!
+An interactive terminal viewer for trees, available with the `tui` feature.
+
+The [`view_interactive`](fn.view_interactive.html) function takes over the terminal and shows the
+tree, initially with only the root's children visible, and the following keys:
+
+| Key                       | Action                                             |
+|---------------------------|----------------------------------------------------|
+| `↑`/`k`, `↓`/`j`          | Move the selection up, or down, one line.          |
+| `PgUp`, `PgDn`            | Move the selection up, or down, one page.          |
+| `Home`/`g`, `End`/`G`     | Move the selection to the first, or last, line.    |
+| `Enter`/`Space`           | Expand, or collapse, the selected node.            |
+| `→`/`l`                   | Expand the selected node.                          |
+| `←`/`h`                   | Collapse the selected node, or select its parent.  |
+| `/`                       | Search for a label, `Enter` to finish the search.  |
+| `n`, `N`                  | Select the next, or previous, search match.        |
+| `q`/`Esc`                 | Quit the viewer.                                   |
+
+Collapsed nodes are written using the [`collapsed`](struct.TreeNode.html#method.set_collapsed)
+support, with the count of their hidden descendants. Searching matches all nodes, not only those
+visible, and expands the ancestors of a matching node.
+
+The tree is always written in the `TopDown` orientation, whatever the orientation of the format
+provided, so that each selectable node has a line of its own.
+*/
+
+use crate::{Rows, StringTreeNode, TreeFormatting, TreeNode, TreeOrientation, Visibility};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::Result;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Show the tree in an interactive viewer, using the default format, until the user quits.
+///
+pub fn view_interactive<T>(tree: &TreeNode<T>) -> Result<()>
+where
+    T: Display,
+{
+    view_interactive_with_format(tree, &TreeFormatting::dir_tree(Default::default()))
+}
+
+///
+/// Show the tree in an interactive viewer, formatted according to the provided format settings,
+/// until the user quits. An error is returned, before the terminal is changed, if the tree
+/// exceeds the `max_nodes` or `max_depth` limits of the format.
+///
+pub fn view_interactive_with_format<T>(tree: &TreeNode<T>, format: &TreeFormatting) -> Result<()>
+where
+    T: Display,
+{
+    tree.check_limits(format)?;
+    let mut viewer = Viewer::new(tree, format.clone());
+    let mut terminal = ratatui::try_init()?;
+    let result = viewer.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct Viewer {
+    tree: StringTreeNode,
+    format: TreeFormatting,
+    /// The index path of each selectable node, in the order they are written.
+    visible: Vec<Vec<usize>>,
+    /// The index of the line written for each node in `visible`.
+    visible_lines: Vec<usize>,
+    selected: usize,
+    /// The index of the first line shown.
+    offset: usize,
+    page_height: usize,
+    search: Option<String>,
+    query: String,
+    quit: bool,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Viewer {
+    fn new<T>(tree: &TreeNode<T>, format: TreeFormatting) -> Self
+    where
+        T: Display,
+    {
+        let mut tree = tree.to_string_tree();
+        for child in tree.children.iter_mut() {
+            collapse_all(child);
+        }
+        let mut viewer = Self {
+            tree,
+            format: TreeFormatting {
+                orientation: TreeOrientation::TopDown,
+                ..format
+            },
+            visible: Default::default(),
+            visible_lines: Default::default(),
+            selected: 0,
+            offset: 0,
+            page_height: 1,
+            search: None,
+            query: Default::default(),
+            quit: false,
+        };
+        viewer.update_visible();
+        viewer
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        while !self.quit {
+            let _ = terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key.code);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame<'_>) {
+        let [body, status] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        self.page_height = (body.height as usize).max(1);
+        self.scroll_to_selected();
+
+        frame.render_widget(Paragraph::new(self.window()), body);
+
+        let status_line = match &self.search {
+            Some(search) => Line::from(format!("/{}", search)),
+            None => Line::from(format!(
+                " {}/{}  Enter: toggle  /: search  n/N: next/previous  q: quit",
+                self.selected + 1,
+                self.visible.len()
+            ))
+            .style(Style::new().reversed()),
+        };
+        frame.render_widget(status_line, status);
+    }
+
+    fn handle_key(&mut self, key: KeyCode) {
+        if let Some(search) = &mut self.search {
+            match key {
+                KeyCode::Enter => {
+                    self.query = self.search.take().unwrap_or_default();
+                    self.find(true, true);
+                }
+                KeyCode::Esc => self.search = None,
+                KeyCode::Backspace => {
+                    let _ = search.pop();
+                }
+                KeyCode::Char(c) => search.push(c),
+                _ => {}
+            }
+            return;
+        }
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => self.move_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_by(1),
+            KeyCode::PageUp => self.move_by(-(self.page_height as isize)),
+            KeyCode::PageDown => self.move_by(self.page_height as isize),
+            KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => self.selected = self.visible.len() - 1,
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                let collapsed = self.selected_node().is_collapsed();
+                self.set_selected_collapsed(!collapsed);
+            }
+            KeyCode::Right | KeyCode::Char('l') => self.set_selected_collapsed(false),
+            KeyCode::Left | KeyCode::Char('h') => {
+                let node = self.selected_node();
+                if node.has_children() && !node.is_collapsed() {
+                    self.set_selected_collapsed(true);
+                } else if let Some((_, parent)) = self.visible[self.selected].split_last() {
+                    let parent = parent.to_vec();
+                    self.select_path(&parent);
+                }
+            }
+            KeyCode::Char('/') => self.search = Some(String::new()),
+            KeyCode::Char('n') => self.find(true, false),
+            KeyCode::Char('N') => self.find(false, false),
+            _ => {}
+        }
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        let last = self.visible.len() as isize - 1;
+        self.selected = (self.selected as isize + delta).clamp(0, last) as usize;
+    }
+
+    fn scroll_to_selected(&mut self) {
+        let selected = self.visible_lines[self.selected];
+        if selected < self.offset {
+            self.offset = selected;
+        } else if selected >= self.offset + self.page_height {
+            self.offset = selected + 1 - self.page_height;
+        }
+    }
+
+    ///
+    /// Return the lines shown, from `offset`, with the line of the selected node highlighted.
+    ///
+    fn window(&self) -> Vec<Line<'static>> {
+        let selected = self.visible_lines[self.selected];
+        self.tree
+            .lines_with_format(&self.format)
+            .skip(self.offset)
+            .take(self.page_height)
+            .zip(self.offset..)
+            .map(|(line, index)| {
+                let line = Line::from(line);
+                if index == selected {
+                    line.style(Style::new().reversed())
+                } else {
+                    line
+                }
+            })
+            .collect()
+    }
+
+    fn selected_node(&self) -> &StringTreeNode {
+        node_at(&self.tree, &self.visible[self.selected])
+    }
+
+    fn set_selected_collapsed(&mut self, collapsed: bool) {
+        let path = self.visible[self.selected].clone();
+        let node = node_at_mut(&mut self.tree, &path);
+        if node.has_children() {
+            node.set_collapsed(collapsed);
+            self.update_visible();
+        }
+    }
+
+    fn select_path(&mut self, path: &[usize]) {
+        let mut node = &mut self.tree;
+        for index in path {
+            node.set_collapsed(false);
+            node = &mut node.children[*index];
+        }
+        self.update_visible();
+        if let Some(selected) = self.visible.iter().position(|visible| visible == path) {
+            self.selected = selected;
+        }
+    }
+
+    ///
+    /// Select the next, or previous, node in pre-order whose label contains the current query.
+    /// If `inclusive` the selected node itself is considered first.
+    ///
+    fn find(&mut self, forward: bool, inclusive: bool) {
+        if self.query.is_empty() {
+            return;
+        }
+        let all = paths(&self.tree, false);
+        let current = all
+            .iter()
+            .position(|path| *path == self.visible[self.selected])
+            .unwrap_or_default();
+        let count = all.len();
+        let start = if inclusive { 0 } else { 1 };
+        let found = (start..count + start)
+            .map(|step| {
+                if forward {
+                    (current + step) % count
+                } else {
+                    (current + count - step % count) % count
+                }
+            })
+            .find(|index| node_at(&self.tree, &all[*index]).data.contains(&self.query));
+        if let Some(index) = found {
+            let path = all[index].clone();
+            self.select_path(&path);
+        }
+    }
+
+    ///
+    /// Update the selectable nodes, those written on a line of their own, and the line of each.
+    /// Hidden nodes, flattened nodes, and all but the first of a run of collapsed repeated
+    /// leaves, are not written on a line of their own and so cannot be selected.
+    ///
+    fn update_visible(&mut self) {
+        let lines = node_lines(&self.tree, &self.format);
+        let (visible, visible_lines) = paths(&self.tree, true)
+            .into_iter()
+            .filter_map(|path| {
+                let node: *const StringTreeNode = node_at(&self.tree, &path);
+                let line = lines.get(&node)?;
+                Some((path, *line))
+            })
+            .unzip();
+        self.visible = visible;
+        self.visible_lines = visible_lines;
+        self.selected = self.selected.min(self.visible.len() - 1);
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Collapse the node, and each of its descendants, that has children and is written; a
+/// flattened node is not collapsed, as its children are written in its place.
+///
+fn collapse_all(node: &mut StringTreeNode) {
+    let mut work = vec![node];
+    while let Some(node) = work.pop() {
+        node.collapsed = node.has_children() && node.visibility == Visibility::Visible;
+        work.extend(node.children.iter_mut());
+    }
+}
+
+///
+/// Return the index of the line written for each node that has a line of its own, by the
+/// address of the node.
+///
+fn node_lines(
+    node: &StringTreeNode,
+    format: &TreeFormatting,
+) -> HashMap<*const StringTreeNode, usize> {
+    let mut line = 0;
+    Rows::new(node, format)
+        .map(|(row, node)| {
+            if row.connector.is_some() {
+                line += 1;
+            }
+            line += 1;
+            let node: *const StringTreeNode = node;
+            (node, line - 1)
+        })
+        .collect()
+}
+
+///
+/// Return the index path of each node, in pre-order, not including the descendants of collapsed
+/// nodes if `visible_only`.
+///
+fn paths(node: &StringTreeNode, visible_only: bool) -> Vec<Vec<usize>> {
+    let mut paths_out = Vec::new();
+    let mut work: Vec<(&StringTreeNode, Vec<usize>)> = vec![(node, Vec::new())];
+    while let Some((node, path)) = work.pop() {
+        if !(visible_only && node.collapsed) {
+            for (index, child) in node.children.iter().enumerate().rev() {
+                let mut child_path = path.clone();
+                child_path.push(index);
+                work.push((child, child_path));
+            }
+        }
+        paths_out.push(path);
+    }
+    paths_out
+}
+
+fn node_at<'a>(node: &'a StringTreeNode, path: &[usize]) -> &'a StringTreeNode {
+    path.iter().fold(node, |node, index| &node.children[*index])
+}
+
+fn node_at_mut<'a>(node: &'a mut StringTreeNode, path: &[usize]) -> &'a mut StringTreeNode {
+    path.iter()
+        .fold(node, |node, index| &mut node.children[*index])
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_viewer() -> Viewer {
+        let tree = TreeNode::with_child_nodes(
+            "root".to_string(),
+            vec![
+                TreeNode::with_children(
+                    "alpha".to_string(),
+                    vec!["one".to_string(), "two".to_string()].into_iter(),
+                ),
+                TreeNode::with_children("beta".to_string(), vec!["three".to_string()].into_iter()),
+            ]
+            .into_iter(),
+        );
+        Viewer::new(&tree, TreeFormatting::dir_tree(Default::default()))
+    }
+
+    fn selected_label(viewer: &Viewer) -> String {
+        viewer.selected_node().data.clone()
+    }
+
+    #[test]
+    fn test_initially_collapsed() {
+        let viewer = make_viewer();
+        assert_eq!(viewer.visible.len(), 3);
+        assert_eq!(
+            viewer.tree.to_string(),
+            "root\n+-- alpha [+2]\n'-- beta [+1]\n".to_string()
+        );
+    }
+
+    #[test]
+    fn test_toggle_and_move() {
+        let mut viewer = make_viewer();
+        viewer.handle_key(KeyCode::Down);
+        viewer.handle_key(KeyCode::Enter);
+        assert_eq!(viewer.visible.len(), 5);
+        viewer.handle_key(KeyCode::End);
+        assert_eq!(selected_label(&viewer), "beta");
+        viewer.handle_key(KeyCode::Up);
+        assert_eq!(selected_label(&viewer), "two");
+        viewer.handle_key(KeyCode::Left);
+        assert_eq!(selected_label(&viewer), "alpha");
+        viewer.handle_key(KeyCode::Left);
+        assert_eq!(viewer.visible.len(), 3);
+        viewer.handle_key(KeyCode::Char('q'));
+        assert!(viewer.quit);
+    }
+
+    #[test]
+    fn test_window_highlights_selected_line() {
+        let mut tree = TreeNode::with_children(
+            "root".to_string(),
+            vec!["retry", "retry", "retry", "hidden", "done"]
+                .into_iter()
+                .map(str::to_string),
+        );
+        tree.children_vec_mut()[3].set_hidden(true);
+        let format = TreeFormatting {
+            root_connector: Some('|'),
+            collapse_repeated_leaves: true,
+            ..TreeFormatting::dir_tree(Default::default())
+        };
+        let mut viewer = Viewer::new(&tree, format);
+        assert_eq!(viewer.visible.len(), 3);
+        assert_eq!(viewer.visible_lines, vec![0, 2, 3]);
+
+        viewer.handle_key(KeyCode::End);
+        assert_eq!(selected_label(&viewer), "done");
+        viewer.page_height = 2;
+        viewer.scroll_to_selected();
+        assert_eq!(viewer.offset, 2);
+        let window = viewer.window();
+        assert_eq!(window.len(), 2);
+        assert_eq!(window[0].to_string(), "+-- retry (×3)");
+        assert_eq!(window[1].to_string(), "'-- done");
+        assert_eq!(window[1].style, Style::new().reversed());
+        assert_eq!(window[0].style, Style::new());
+    }
+
+    #[test]
+    fn test_search() {
+        let mut viewer = make_viewer();
+        viewer.handle_key(KeyCode::Char('/'));
+        viewer.handle_key(KeyCode::Char('e'));
+        viewer.handle_key(KeyCode::Enter);
+        assert_eq!(selected_label(&viewer), "one");
+        viewer.handle_key(KeyCode::Char('n'));
+        assert_eq!(selected_label(&viewer), "beta");
+        viewer.handle_key(KeyCode::Char('n'));
+        assert_eq!(selected_label(&viewer), "three");
+        viewer.handle_key(KeyCode::Char('N'));
+        assert_eq!(selected_label(&viewer), "beta");
+    }
+}
@@ -0,0 +1,203 @@
+/*!
+The common support for rendering structured documents, such as JSON, as trees.
+
+Each document format provides an implementation of the crate-private `TreeValue` trait which
+describes each value as either a scalar, a mapping from keys to values, or a sequence of values.
+The functions here then build the tree, and write it, using the shared
+[`ValueTreeOptions`](struct.ValueTreeOptions.html).
+*/
+
+use crate::{map_tree, TreeFormatting, TreeNode};
+use std::fmt::{Display, Formatter};
+use std::io::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The options that control how a structured document is represented as a tree. These options
+/// are shared by all the document formats supported by this crate.
+///
+/// Mappings, such as JSON objects, become a node with a child for each member labeled with
+/// the member's key; sequences, such as JSON arrays, become a node with a child for each element.
+/// Scalar values are written in the label of their parent key, or index, as `key: value`.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValueTreeOptions {
+    /// The label of the root node.
+    pub root_label: String,
+    /// If `true` sequence elements are labeled with their index, `[0]`, `[1]`, and so on;
+    /// otherwise scalar elements are labeled with only their value and mapping, or sequence,
+    /// elements with `{}` or `[]` respectively.
+    pub index_labels: bool,
+    /// If set, the maximum number of characters of a scalar value to write; longer values are
+    /// truncated and end with `'…'`.
+    pub max_value_width: Option<usize>,
+    /// If `true` the type of each value is written as an aligned badge after its label, see
+    /// [`Badge`](trait.Badge.html).
+    pub type_badges: bool,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Crate Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Describes the shape of a value in a structured document.
+///
+pub(crate) enum ValueShape<'a, V> {
+    /// A value with no children, and its textual representation.
+    Scalar(String),
+    /// A value with keyed children.
+    Mapping(Vec<(String, &'a V)>),
+    /// A value with ordered children.
+    Sequence(Vec<&'a V>),
+}
+
+///
+/// Implemented for the value type of each supported document format.
+///
+pub(crate) trait TreeValue {
+    /// The name of this value's type, used in type badges.
+    fn type_name(&self) -> &'static str;
+
+    /// The shape of this value.
+    fn shape(&self) -> ValueShape<'_, Self>
+    where
+        Self: Sized;
+}
+
+///
+/// The data for each node in a value tree, the label and the value's type name.
+///
+#[derive(Clone, Debug)]
+pub(crate) struct ValueLabel {
+    label: String,
+    type_name: &'static str,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Crate Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return a tree of labels representing the provided value.
+///
+pub(crate) fn string_tree<V>(value: &V, options: &ValueTreeOptions) -> TreeNode<String>
+where
+    V: TreeValue,
+{
+    to_string_tree(value_tree(value, options.root_label.clone(), options))
+}
+
+///
+/// Write the tree representing the provided value, with type badges if requested.
+///
+pub(crate) fn write_value_tree<V>(
+    value: &V,
+    to_writer: &mut impl Write,
+    format: &TreeFormatting,
+    options: &ValueTreeOptions,
+) -> Result<()>
+where
+    V: TreeValue,
+{
+    let tree = value_tree(value, options.root_label.clone(), options);
+    if options.type_badges {
+        tree.write_with_badges(to_writer, format, &|data: &ValueLabel| {
+            Some(format!("({})", data.type_name))
+        })
+    } else {
+        tree.write_with_format(to_writer, format)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Default for ValueTreeOptions {
+    fn default() -> Self {
+        Self {
+            root_label: "$".to_string(),
+            index_labels: true,
+            max_value_width: None,
+            type_badges: false,
+        }
+    }
+}
+
+impl ValueTreeOptions {
+    fn scalar(&self, value: String) -> String {
+        match self.max_value_width {
+            Some(max_width) if value.chars().count() > max_width => format!(
+                "{}…",
+                value
+                    .chars()
+                    .take(max_width.saturating_sub(1))
+                    .collect::<String>()
+            ),
+            _ => value,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for ValueLabel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn value_tree<V>(value: &V, label: String, options: &ValueTreeOptions) -> TreeNode<ValueLabel>
+where
+    V: TreeValue,
+{
+    let type_name = value.type_name();
+    match value.shape() {
+        ValueShape::Scalar(scalar) => TreeNode::new(ValueLabel {
+            label: format!("{}: {}", label, options.scalar(scalar)),
+            type_name,
+        }),
+        ValueShape::Mapping(members) => TreeNode::with_child_nodes(
+            ValueLabel { label, type_name },
+            members
+                .into_iter()
+                .map(|(key, value)| value_tree(value, key, options)),
+        ),
+        ValueShape::Sequence(elements) => TreeNode::with_child_nodes(
+            ValueLabel { label, type_name },
+            elements
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| element_tree(value, index, options)),
+        ),
+    }
+}
+
+fn element_tree<V>(value: &V, index: usize, options: &ValueTreeOptions) -> TreeNode<ValueLabel>
+where
+    V: TreeValue,
+{
+    if options.index_labels {
+        return value_tree(value, format!("[{}]", index), options);
+    }
+    match value.shape() {
+        ValueShape::Scalar(scalar) => TreeNode::new(ValueLabel {
+            label: options.scalar(scalar),
+            type_name: value.type_name(),
+        }),
+        ValueShape::Mapping(_) => value_tree(value, "{}".to_string(), options),
+        ValueShape::Sequence(_) => value_tree(value, "[]".to_string(), options),
+    }
+}
+
+fn to_string_tree(node: TreeNode<ValueLabel>) -> TreeNode<String> {
+    map_tree(node, |data| data.label)
+}
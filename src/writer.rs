@@ -0,0 +1,275 @@
+/*!
+A writer that produces tree output from a stream of events, without a `TreeNode` tree.
+
+The [`TreeWriter`](struct.TreeWriter.html) accepts nodes in pre-order, each
+[`start_node`](struct.TreeWriter.html#method.start_node) followed by the node's children and
+then a matching [`end_node`](struct.TreeWriter.html#method.end_node), and writes each line as
+soon as the tree lines that precede it are known.
+
+# Example
+
+```rust
+use text_trees::{TreeFormatting, TreeWriter};
+
+let mut writer = TreeWriter::new(Vec::new(), &TreeFormatting::default());
+writer.start_node("root").unwrap();
+writer.start_node("one").unwrap();
+writer.end_node().unwrap();
+writer.start_last_node("two").unwrap();
+writer.start_node("three").unwrap();
+let output = writer.finish().unwrap();
+
+assert_eq!(
+    String::from_utf8(output).unwrap(),
+    "root\n+-- one\n'-- two\n    '-- three\n"
+);
+```
+
+A node's line cannot be written until it is known whether the node is its parent's last child,
+which determines the connector before its label, and the lines before each of its descendants.
+Until the next sibling starts, or the parent ends, the node and its descendants are held in
+memory. Where the caller knows a node is its parent's last child the
+[`start_last_node`](struct.TreeWriter.html#method.start_last_node) method allows the node, and
+its descendants, to be written immediately.
+*/
+
+use crate::{FlushCounter, RowPrefixBuilder, TreeFormatting};
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::io::{Error, ErrorKind, Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Writes tree output, to an implementation of `std::io::Write`, from a stream of node events.
+///
+/// _Note_: the `align_depths` formatting option, and the `Classic` and `OrgChart` orientations, all
+/// require the entire tree to be laid out before any output is written; they are ignored by this
+/// writer and the tree is written top-down without alignment.
+///
+/// Unlike the `write_*` methods of [`TreeNode`](struct.TreeNode.html) this writer does not buffer
+/// its output, so that each line is written as soon as it is known. When writing to a `File`, or
+/// similar, callers should wrap it in a `BufWriter`; the writer is flushed according to the
+/// `flush_policy` formatting option, and when finished.
+///
+#[derive(Debug)]
+pub struct TreeWriter<W>
+where
+    W: Write,
+{
+    writer: W,
+    format: TreeFormatting,
+    prefix: RowPrefixBuilder,
+    /// Rows not yet written, in output order.
+    pending: VecDeque<PendingRow>,
+    /// The number of rows written, and so the index of the first pending row.
+    written: usize,
+    /// The currently open nodes, from the root down.
+    open: Vec<OpenNode>,
+    has_root: bool,
+    /// The number of nodes started.
+    started: usize,
+    /// The lines, or bytes, written since the last flush, according to the format's flush
+    /// policy.
+    flush: FlushCounter,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+struct PendingRow {
+    depth: usize,
+    label: String,
+    is_last: Option<bool>,
+    has_children: Option<bool>,
+}
+
+#[derive(Debug)]
+struct OpenNode {
+    /// The index of this node's row.
+    index: usize,
+    /// The index of the row of this node's most recent child, if any.
+    last_child: Option<usize>,
+    /// `true` if the most recent child was started as the last child.
+    last_child_final: bool,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<W> TreeWriter<W>
+where
+    W: Write,
+{
+    /// Construct a new writer that writes to `writer` with the provided format settings.
+    pub fn new(writer: W, format: &TreeFormatting) -> Self {
+        Self {
+            writer,
+            format: format.clone(),
+            prefix: RowPrefixBuilder::new(format),
+            pending: Default::default(),
+            written: 0,
+            open: Default::default(),
+            has_root: false,
+            started: 0,
+            flush: FlushCounter::new(format.flush_policy),
+        }
+    }
+
+    ///
+    /// Start a new node, the root if no node has been started, else a child of the most recently
+    /// started node that has not ended.
+    ///
+    /// This returns an error of kind `InvalidInput` if the root has already ended, if the
+    /// parent's last child has already been started, or if the node would exceed the
+    /// `max_nodes` or `max_depth` limits of the format settings.
+    ///
+    pub fn start_node(&mut self, label: impl Display) -> Result<()> {
+        self.start(label, false)
+    }
+
+    ///
+    /// Start a new node, as [`start_node`](struct.TreeWriter.html#method.start_node) does, which
+    /// the caller knows to be the last child of its parent. This allows the node, and its
+    /// descendants, to be written without waiting for the parent to end.
+    ///
+    pub fn start_last_node(&mut self, label: impl Display) -> Result<()> {
+        self.start(label, true)
+    }
+
+    ///
+    /// End the most recently started node that has not ended.
+    ///
+    /// This returns an error of kind `InvalidInput` if there is no such node.
+    ///
+    pub fn end_node(&mut self) -> Result<()> {
+        let node = self
+            .open
+            .pop()
+            .ok_or_else(|| invalid_input("there is no node to end"))?;
+        if let Some(row) = self.row_mut(node.index) {
+            let _ = row.has_children.get_or_insert(false);
+        }
+        if let Some(last_child) = node.last_child {
+            if let Some(row) = self.row_mut(last_child) {
+                let _ = row.is_last.get_or_insert(true);
+            }
+        }
+        self.write_pending()
+    }
+
+    ///
+    /// End any nodes that have not ended, write any remaining output, and return the underlying
+    /// writer.
+    ///
+    pub fn finish(mut self) -> Result<W> {
+        while !self.open.is_empty() {
+            self.end_node()?;
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn start(&mut self, label: impl Display, is_last: bool) -> Result<()> {
+        let index = self.written + self.pending.len();
+        let depth = self.open.len();
+        if let Some(max_nodes) = self.format.max_nodes.filter(|max| self.started >= *max) {
+            return Err(invalid_input(&format!(
+                "the tree has more than the maximum of {} nodes",
+                max_nodes
+            )));
+        }
+        if let Some(max_depth) = self.format.max_depth.filter(|max| depth > *max) {
+            return Err(invalid_input(&format!(
+                "the tree is deeper than the maximum depth of {}",
+                max_depth
+            )));
+        }
+        match self.open.last_mut() {
+            None if self.has_root => {
+                return Err(invalid_input("the root node has already ended"));
+            }
+            None => self.has_root = true,
+            Some(parent) => {
+                if parent.last_child_final {
+                    return Err(invalid_input("the parent's last child has already started"));
+                }
+                let parent_index = parent.index;
+                let previous = parent.last_child.replace(index);
+                parent.last_child_final = is_last;
+                if let Some(row) = self.row_mut(parent_index) {
+                    row.has_children = Some(true);
+                }
+                if let Some(row) = previous.and_then(|previous| self.row_mut(previous)) {
+                    row.is_last = Some(false);
+                }
+            }
+        }
+
+        self.started += 1;
+        self.pending.push_back(PendingRow {
+            depth,
+            label: self.format.format_label(label.to_string()),
+            is_last: if depth == 0 || is_last {
+                Some(true)
+            } else {
+                None
+            },
+            has_children: None,
+        });
+        self.open.push(OpenNode {
+            index,
+            last_child: None,
+            last_child_final: false,
+        });
+        self.write_pending()
+    }
+
+    fn row_mut(&mut self, index: usize) -> Option<&mut PendingRow> {
+        index
+            .checked_sub(self.written)
+            .and_then(move |index| self.pending.get_mut(index))
+    }
+
+    ///
+    /// Write each pending row, in order, for which it is known whether it is the last child of
+    /// its parent and whether it has children. The ancestors of a row always precede it, and so
+    /// have already been written.
+    ///
+    fn write_pending(&mut self) -> Result<()> {
+        while let Some(PendingRow {
+            is_last: Some(is_last),
+            has_children: Some(has_children),
+            ..
+        }) = self.pending.front()
+        {
+            let (is_last, has_children) = (*is_last, *has_children);
+            let row = self.pending.pop_front().unwrap();
+            let prefix = self.prefix.row(row.depth, is_last, has_children);
+            let label = prefix.repeated_label(row.label);
+            // The prefix may include the root connector line, each line is written separately.
+            for line in format!("{}{}", prefix, label).split('\n') {
+                let line = self.format.output_line(line);
+                writeln!(self.writer, "{}", line)?;
+                if self.flush.wrote(1, line.len() + 1) {
+                    self.writer.flush()?;
+                }
+            }
+            self.written += 1;
+        }
+        Ok(())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn invalid_input(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidInput, message)
+}
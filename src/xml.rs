@@ -0,0 +1,156 @@
+/*!
+Rendering of the element hierarchy of [roxmltree](https://docs.rs/roxmltree) documents as
+trees, available with the `roxmltree` feature.
+
+Each element becomes a node labeled with its name, including any namespace prefix, and the
+element's attributes are carried by the node's [`XmlLabel`](struct.XmlLabel.html) to be written
+as an annotation. Text, comments, and processing instructions are not included.
+
+# Example
+
+```rust
+use text_trees::{write_xml_document, TreeFormatting};
+
+let document = roxmltree::Document::parse(
+    r#"<svg width="10"><g id="a"><rect/><circle r="2"/></g>text</svg>"#,
+)
+.unwrap();
+let mut buffer: Vec<u8> = Vec::new();
+write_xml_document(&document, &mut buffer, &TreeFormatting::default()).unwrap();
+
+assert_eq!(
+    String::from_utf8(buffer).unwrap(),
+    "svg width=\"10\"\n'-- g id=\"a\"\n    +-- rect\n    '-- circle r=\"2\"\n"
+);
+```
+*/
+
+use crate::{TreeFormatting, TreeNode};
+use roxmltree::{Document, ExpandedName, Node};
+use std::fmt::{self, Display, Formatter};
+use std::io::{Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The data of each node of a tree representing an XML document, the element's name and its
+/// attributes, in document order.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct XmlLabel {
+    name: String,
+    attributes: Vec<(String, String)>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return a tree representing the element hierarchy of the provided document, the root of the
+/// tree is the document's root element.
+///
+pub fn xml_tree(document: &Document<'_>) -> TreeNode<XmlLabel> {
+    element_tree(document.root_element())
+}
+
+///
+/// Write the tree representing the element hierarchy of the provided document to the provided
+/// implementation of `std::io::Write` with the provided format settings, where the attributes
+/// of each element are written as an annotation.
+///
+pub fn write_xml_document(
+    document: &Document<'_>,
+    to_writer: &mut impl Write,
+    format: &TreeFormatting,
+) -> Result<()> {
+    xml_tree(document).write_with_annotations(to_writer, format, XmlLabel::annotation)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl From<&Document<'_>> for TreeNode<XmlLabel> {
+    fn from(document: &Document<'_>) -> Self {
+        xml_tree(document)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for XmlLabel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl XmlLabel {
+    /// Return the name of this element, including any namespace prefix.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the names, including any namespace prefix, and values of this element's
+    /// attributes.
+    pub fn attributes(&self) -> &[(String, String)] {
+        &self.attributes
+    }
+
+    ///
+    /// Return the attributes as they are written in XML, such as `id="a" class="b"`, or `None`
+    /// if the element has no attributes; for use with
+    /// [`write_with_annotations`](struct.TreeNode.html#method.write_with_annotations).
+    ///
+    pub fn annotation(&self) -> Option<String> {
+        if self.attributes.is_empty() {
+            None
+        } else {
+            Some(
+                self.attributes
+                    .iter()
+                    .map(|(name, value)| format!("{}=\"{}\"", name, value.replace('"', "&quot;")))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+            )
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn element_tree(element: Node<'_, '_>) -> TreeNode<XmlLabel> {
+    TreeNode::with_child_nodes(
+        XmlLabel {
+            name: qualified_name(element, element.tag_name()),
+            attributes: element
+                .attributes()
+                .map(|attribute| {
+                    let name = match attribute.namespace() {
+                        Some(uri) => ExpandedName::from((uri, attribute.name())),
+                        None => ExpandedName::from(attribute.name()),
+                    };
+                    (qualified_name(element, name), attribute.value().to_string())
+                })
+                .collect(),
+        },
+        element
+            .children()
+            .filter(Node::is_element)
+            .map(element_tree),
+    )
+}
+
+///
+/// Return the name with the prefix bound to its namespace, if any, in scope at `element`.
+///
+fn qualified_name(element: Node<'_, '_>, name: ExpandedName<'_, '_>) -> String {
+    match name.namespace().and_then(|uri| element.lookup_prefix(uri)) {
+        Some(prefix) => format!("{}:{}", prefix, name.name()),
+        None => name.name().to_string(),
+    }
+}
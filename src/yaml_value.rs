@@ -0,0 +1,139 @@
+/*!
+Rendering of [serde_yaml](https://docs.rs/serde_yaml) values as trees, available with the
+`serde_yaml` feature.
+
+YAML documents are represented in the same way as JSON, and TOML, documents, using the shared
+[`ValueTreeOptions`](struct.ValueTreeOptions.html), so that a tool inspecting configuration in
+any of these formats has a single code path. Mapping keys that are not strings are written as
+their scalar value, and tagged values are written with their tag before the value.
+
+# Example
+
+```rust
+use text_trees::{from_yaml_str, ValueTreeOptions};
+
+let tree = from_yaml_str(
+    "name: text_trees\nkeywords:\n  - tree\n  - text\n",
+    &ValueTreeOptions::default(),
+)
+.unwrap();
+
+assert_eq!(
+    tree.to_string(),
+    r#"$
++-- name: "text_trees"
+'-- keywords
+    +-- [0]: "tree"
+    '-- [1]: "text"
+"#
+);
+```
+*/
+
+use crate::value_tree::{string_tree, write_value_tree, TreeValue, ValueShape};
+use crate::{StringTreeNode, TreeFormatting, ValueTreeOptions};
+use serde_yaml::Value;
+use std::io::{Error, ErrorKind, Result, Write};
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Return a tree representing the provided YAML value, according to the provided options.
+///
+pub fn yaml_tree(value: &Value, options: &ValueTreeOptions) -> StringTreeNode {
+    string_tree(value, options)
+}
+
+///
+/// Parse the provided YAML document and return a tree representing it, according to the
+/// provided options. An error of kind `InvalidData` is returned if the document cannot be
+/// parsed.
+///
+pub fn from_yaml_str(text: &str, options: &ValueTreeOptions) -> Result<StringTreeNode> {
+    let value: Value =
+        serde_yaml::from_str(text).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    Ok(yaml_tree(&value, options))
+}
+
+///
+/// Write the tree representing the provided YAML value to the provided implementation of
+/// `std::io::Write` with the provided format settings and options.
+///
+pub fn write_yaml_value(
+    value: &Value,
+    to_writer: &mut impl Write,
+    format: &TreeFormatting,
+    options: &ValueTreeOptions,
+) -> Result<()> {
+    write_value_tree(value, to_writer, format, options)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl From<&Value> for StringTreeNode {
+    fn from(v: &Value) -> Self {
+        yaml_tree(v, &Default::default())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl TreeValue for Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Sequence(_) => "sequence",
+            Value::Mapping(_) => "mapping",
+            Value::Tagged(tagged) => tagged.value.type_name(),
+        }
+    }
+
+    fn shape(&self) -> ValueShape<'_, Self> {
+        match self {
+            Value::Sequence(elements) => ValueShape::Sequence(elements.iter().collect()),
+            Value::Mapping(members) => ValueShape::Mapping(
+                members
+                    .iter()
+                    .map(|(key, value)| (key_label(key), value))
+                    .collect(),
+            ),
+            Value::Tagged(tagged) => match tagged.value.shape() {
+                ValueShape::Scalar(scalar) => {
+                    ValueShape::Scalar(format!("{} {}", tagged.tag, scalar))
+                }
+                shape => shape,
+            },
+            scalar => ValueShape::Scalar(scalar_text(scalar)),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(v) => v.to_string(),
+        Value::Number(v) => v.to_string(),
+        Value::String(v) => format!("{:?}", v),
+        Value::Sequence(_) => "[]".to_string(),
+        Value::Mapping(_) => "{}".to_string(),
+        Value::Tagged(tagged) => format!("{} {}", tagged.tag, scalar_text(&tagged.value)),
+    }
+}
+
+fn key_label(key: &Value) -> String {
+    match key {
+        Value::String(key) => key.clone(),
+        key => scalar_text(key),
+    }
+}
@@ -0,0 +1,97 @@
+use pretty_assertions::assert_eq;
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    let mut tree = StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            StringTreeNode::with_children(
+                "Parent".to_string(),
+                vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+            ),
+            StringTreeNode::with_children(
+                "Aunt".to_string(),
+                vec!["Child 3".to_string()].into_iter(),
+            ),
+        ]
+        .into_iter(),
+    );
+    tree.children_vec_mut()[1].set_collapsed(true);
+    tree
+}
+
+#[test]
+fn test_accessible() {
+    let tree = make_tree();
+    let result = tree
+        .to_string_accessible(&TreeFormatting::default(), &AccessibleOptions::default())
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"level 1: root (2 children)
+level 2: Parent (2 children)
+level 3: Child 1
+level 3: Child 2 (last)
+level 2: Aunt [+1] (last, collapsed)
+"#
+    );
+}
+
+#[test]
+fn test_accessible_fields_per_line() {
+    let tree = make_tree();
+    let options = AccessibleOptions {
+        fields_per_line: true,
+    };
+    let result = tree
+        .to_string_accessible(&TreeFormatting::default(), &options)
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"level: 1
+label: root
+children: 2
+
+level: 2
+label: Parent
+children: 2
+
+level: 3
+label: Child 1
+
+level: 3
+label: Child 2
+last: yes
+
+level: 2
+label: Aunt [+1]
+last: yes
+collapsed: yes
+"#
+    );
+}
+
+#[test]
+fn test_accessible_repeated_leaves() {
+    let tree = StringTreeNode::with_children(
+        "root".to_string(),
+        vec!["leaf".to_string(), "leaf".to_string(), "other".to_string()].into_iter(),
+    );
+    let format = TreeFormatting {
+        collapse_repeated_leaves: true,
+        ..Default::default()
+    };
+    let result = tree
+        .to_string_accessible(&format, &AccessibleOptions::default())
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"level 1: root (3 children)
+level 2: leaf (×2)
+level 2: other (last)
+"#
+    );
+}
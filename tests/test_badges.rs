@@ -0,0 +1,182 @@
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "tests".to_string(),
+        vec![
+            StringTreeNode::with_children(
+                "parser".to_string(),
+                vec!["empty input".to_string(), "nested".to_string()].into_iter(),
+            ),
+            "renderer".into(),
+        ]
+        .into_iter(),
+    )
+}
+
+fn status(label: &str) -> Option<String> {
+    match label {
+        "tests" => None,
+        "nested" => Some("[!]".to_string()),
+        _ => Some("[ok]".to_string()),
+    }
+}
+
+#[test]
+fn test_badges_after_label() {
+    let tree = make_tree();
+
+    let result =
+        tree.to_string_with_badges(&TreeFormatting::default(), &|label: &String| status(label));
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"tests
++-- parser          [ok]
+|   +-- empty input [ok]
+|   '-- nested      [!]
+'-- renderer        [ok]
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_badges_after_label_with_column_format() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        column_formats: vec![ColumnFormat {
+            alignment: ColumnAlignment::Right,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let result = tree
+        .to_string_with_badges(&format, &|label: &String| status(label))
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"tests
++-- parser          [ok]
+|   +-- empty input [ok]
+|   '-- nested       [!]
+'-- renderer        [ok]
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_badges_before_label() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        badge_position: BadgePosition::BeforeLabel,
+        ..TreeFormatting::dir_tree(FormatCharacters::box_chars())
+    };
+
+    let result = tree.to_string_with_badges(&format, &|label: &String| status(label));
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"     tests
+├── [ok] parser
+│   ├── [ok] empty input
+│   └── [!]  nested
+└── [ok] renderer
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_no_badges() {
+    let tree = make_tree();
+
+    let result = tree.to_string_with_badges(&TreeFormatting::default(), &|_: &String| None);
+    assert!(result.is_ok());
+    assert_eq!(result.unwrap(), tree.to_string());
+}
+
+#[test]
+fn test_badges_with_root_connector() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        badge_position: BadgePosition::BeforeLabel,
+        root_connector: Some('│'),
+        ..TreeFormatting::dir_tree(FormatCharacters::box_chars())
+    };
+
+    let result = tree.to_string_with_badges(&format, &|label: &String| status(label));
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"     tests
+│
+├── [ok] parser
+│   ├── [ok] empty input
+│   └── [!]  nested
+└── [ok] renderer
+"#
+        .to_string()
+    );
+}
+
+struct Status;
+
+impl Badge<String> for Status {
+    fn badge(&self, data: &String) -> Option<String> {
+        status(data)
+    }
+
+    fn description(&self, badge: &str) -> Option<String> {
+        match badge {
+            "[ok]" => Some("passed".to_string()),
+            "[!]" => Some("failed".to_string()),
+            "[-]" => Some("skipped".to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn test_badges_with_legend() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        badge_legend: true,
+        ..Default::default()
+    };
+
+    let result = tree.to_string_with_badges(&format, &Status);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"tests
++-- parser          [ok]
+|   +-- empty input [ok]
+|   '-- nested      [!]
+'-- renderer        [ok]
+
+[ok] passed
+[!]  failed
+"#
+        .to_string()
+    );
+
+    let result = tree.to_string_with_badges(&format, &|label: &String| status(label));
+    assert!(result.is_ok());
+    assert_eq!(
+        result.unwrap(),
+        tree.to_string_with_badges(&TreeFormatting::default(), &Status)
+            .unwrap()
+    );
+}
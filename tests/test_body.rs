@@ -0,0 +1,137 @@
+use pretty_assertions::assert_eq;
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            StringTreeNode::with_children(
+                "Parent".to_string(),
+                vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+            ),
+            StringTreeNode::with_children(
+                "Aunt".to_string(),
+                vec!["Child 3".to_string()].into_iter(),
+            ),
+        ]
+        .into_iter(),
+    )
+}
+
+fn bodies(label: &String) -> Option<String> {
+    if label == "Child 3" {
+        None
+    } else {
+        Some(format!("about {}\n\nend", label))
+    }
+}
+
+#[test]
+fn test_bodies() {
+    let tree = make_tree();
+    let result = tree
+        .to_string_with_bodies(
+            &TreeFormatting::dir_tree(FormatCharacters::box_chars()),
+            bodies,
+        )
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
+│   about root
+│
+│   end
+├── Parent
+│   │   about Parent
+│   │
+│   │   end
+│   ├── Child 1
+│   │   about Child 1
+│   │
+│   │   end
+│   └── Child 2
+│       about Child 2
+│
+│       end
+└── Aunt
+    │   about Aunt
+    │
+    │   end
+    └── Child 3
+"#
+    );
+}
+
+#[test]
+fn test_bodies_left_anchor_and_prefix() {
+    let tree = make_tree();
+    let result = tree
+        .to_string_with_bodies(
+            &TreeFormatting::dir_tree_left_with_prefix(FormatCharacters::ascii(), "> ".to_string()),
+            bodies,
+        )
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"> + root
+> |  about root
+> |
+> |  end
+> +--, Parent
+> |  |  about Parent
+> |  |
+> |  |  end
+> |  +--- Child 1
+> |  |    about Child 1
+> |  |
+> |  |    end
+> |  '--- Child 2
+> |       about Child 2
+> |
+> |       end
+> '--, Aunt
+>    |  about Aunt
+>    |
+>    |  end
+>    '--- Child 3
+"#
+    );
+}
+
+#[test]
+fn test_bodies_narrow_continuation_indent() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        continuation_indent: Some(2),
+        ..Default::default()
+    };
+    let result = tree.to_string_with_bodies(&format, bodies).unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
+| about root
+|
+| end
++-- Parent
+| | about Parent
+| |
+| | end
+| +-- Child 1
+| |   about Child 1
+| |
+| |   end
+| '-- Child 2
+|     about Child 2
+|
+|     end
+'-- Aunt
+  | about Aunt
+  |
+  | end
+  '-- Child 3
+"#
+    );
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "cargo-metadata")]
+
+use cargo_metadata::MetadataCommand;
+use pretty_assertions::assert_eq;
+use text_trees::{cargo_tree, CargoTreeOptions};
+
+const NORMAL: &str = r#"{"kind": null}"#;
+
+fn package(name: &str) -> String {
+    format!(
+        r#"{{"name": "{0}", "version": "0.1.0", "id": "path+file:///ws/{0}#0.1.0",
+            "source": null, "dependencies": [], "targets": [], "features": {{}},
+            "manifest_path": "/ws/{0}/Cargo.toml", "edition": "2021"}}"#,
+        name
+    )
+}
+
+fn node(name: &str, deps: &[(&str, &str)]) -> String {
+    let deps: Vec<String> = deps
+        .iter()
+        .map(|(dep, kind)| {
+            format!(
+                r#"{{"name": "{0}", "pkg": "path+file:///ws/{0}#0.1.0",
+                    "dep_kinds": [{1}]}}"#,
+                dep, kind
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"id": "path+file:///ws/{}#0.1.0", "dependencies": [], "deps": [{}], "features": []}}"#,
+        name,
+        deps.join(",")
+    )
+}
+
+fn make_metadata(root: Option<&str>) -> cargo_metadata::Metadata {
+    let names = ["app", "lib_a", "lib_b", "lib_c"];
+    let packages: Vec<String> = names.iter().map(|name| package(name)).collect();
+    let nodes = [
+        node(
+            "app",
+            &[
+                ("lib_a", NORMAL),
+                ("lib_b", r#"{"kind": null}, {"kind": "dev"}"#),
+                ("lib_c", r#"{"kind": "build"}"#),
+            ],
+        ),
+        node("lib_a", &[]),
+        node("lib_b", &[("lib_a", NORMAL)]),
+        node("lib_c", &[("lib_b", NORMAL)]),
+    ];
+    let root = root
+        .map(|root| format!(r#""path+file:///ws/{}#0.1.0""#, root))
+        .unwrap_or_else(|| "null".to_string());
+    MetadataCommand::parse(format!(
+        r#"{{"packages": [{}], "workspace_members": ["path+file:///ws/app#0.1.0"],
+            "resolve": {{"nodes": [{}], "root": {}}},
+            "target_directory": "/ws/target", "version": 1, "workspace_root": "/ws"}}"#,
+        packages.join(","),
+        nodes.join(","),
+        root
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_cargo_tree_dedup() {
+    let metadata = make_metadata(Some("app"));
+    let result = cargo_tree(&metadata, &CargoTreeOptions::default())
+        .unwrap()
+        .to_string();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"app v0.1.0
++-- lib_a v0.1.0
++-- lib_b v0.1.0
+|   '-- lib_a v0.1.0
+'-- lib_c v0.1.0 (build)
+    '-- lib_b v0.1.0 (*)
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_cargo_tree_options() {
+    let metadata = make_metadata(Some("app"));
+    let options = CargoTreeOptions {
+        dedup: false,
+        max_depth: Some(2),
+        dev_dependencies: false,
+        ..Default::default()
+    };
+    let result = cargo_tree(&metadata, &options).unwrap().to_string();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"app v0.1.0
++-- lib_a v0.1.0
++-- lib_b v0.1.0
+|   '-- lib_a v0.1.0
+'-- lib_c v0.1.0 (build)
+    '-- lib_b v0.1.0
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_cargo_tree_workspace() {
+    let metadata = make_metadata(None);
+    let options = CargoTreeOptions {
+        max_depth: Some(1),
+        ..Default::default()
+    };
+    let result = cargo_tree(&metadata, &options).unwrap().to_string();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"/ws
+'-- app v0.1.0
+    +-- lib_a v0.1.0
+    +-- lib_b v0.1.0
+    '-- lib_c v0.1.0 (build)
+"#
+        .to_string()
+    );
+}
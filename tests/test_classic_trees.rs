@@ -0,0 +1,176 @@
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            "Uncle".into(),
+            StringTreeNode::with_children(
+                "Parent".to_string(),
+                vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+            ),
+        ]
+        .into_iter(),
+    )
+}
+
+fn classic_format(chars: FormatCharacters) -> TreeFormatting {
+    TreeFormatting {
+        orientation: TreeOrientation::Classic,
+        ..TreeFormatting::dir_tree(chars)
+    }
+}
+
+#[test]
+fn test_ascii_classic_tree() {
+    let tree = make_tree();
+
+    let result = tree.to_string_with_format(&classic_format(FormatCharacters::ascii()));
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"      root
+     /     \
+Uncle      Parent
+            /    \
+       Child 1  Child 2
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_box_char_classic_tree_with_prefix() {
+    let tree = make_tree();
+    let mut format = classic_format(FormatCharacters::box_chars());
+    format.prefix_str = Some("> ".to_string());
+
+    let result = tree.to_string_with_format(&format);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#">       root
+>      ╱     ╲
+> Uncle      Parent
+>             ╱    ╲
+>        Child 1  Child 2
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_classic_single_child_and_wide_label() {
+    let tree = StringTreeNode::with_child_nodes(
+        "a very wide root".to_string(),
+        vec![StringTreeNode::with_children(
+            "b".to_string(),
+            vec!["c".to_string()].into_iter(),
+        )]
+        .into_iter(),
+    );
+
+    let result = tree.to_string_with_format(&classic_format(FormatCharacters::ascii()));
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"a very wide root
+        |
+        b
+        |
+        c
+"#
+        .to_string()
+    );
+}
+
+fn org_chart_format(chars: FormatCharacters) -> TreeFormatting {
+    TreeFormatting {
+        orientation: TreeOrientation::OrgChart,
+        ..TreeFormatting::dir_tree(chars)
+    }
+}
+
+#[test]
+fn test_box_char_org_chart() {
+    let mut tree = make_tree();
+    tree.push_node(StringTreeNode::with_children(
+        "Aunt".to_string(),
+        vec!["Child 3".to_string()].into_iter(),
+    ));
+
+    let result = tree.to_string_with_format(&org_chart_format(FormatCharacters::box_chars()));
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    assert_eq!(
+        result,
+        r#"             root
+  ┌───────────┬┴────────────┐
+Uncle      Parent         Aunt
+          ┌───┴────┐        │
+       Child 1  Child 2  Child 3
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_ascii_org_chart() {
+    let tree = make_tree();
+
+    let result = tree.to_string_with_format(&org_chart_format(FormatCharacters::ascii()));
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    assert_eq!(
+        result,
+        r#"      root
+  +-----+-----+
+Uncle      Parent
+          +---+----+
+       Child 1  Child 2
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_very_deep_tree() {
+    const DEPTH: usize = 5_000;
+    const STACK: usize = 256 * 1024;
+
+    // A small stack ensures that neither layout pass recurses per level.
+    let handle = std::thread::Builder::new()
+        .stack_size(STACK)
+        .spawn(|| {
+            let mut tree = StringTreeNode::new("x".to_string());
+            for _ in 0..DEPTH {
+                tree = StringTreeNode::with_child_nodes("x".to_string(), vec![tree].into_iter());
+            }
+            [TreeOrientation::Classic, TreeOrientation::OrgChart]
+                .iter()
+                .map(|orientation| {
+                    let format = TreeFormatting {
+                        orientation: orientation.clone(),
+                        ..TreeFormatting::dir_tree(FormatCharacters::ascii())
+                    };
+                    tree.to_string_with_format(&format).unwrap()
+                })
+                .collect::<Vec<String>>()
+        })
+        .unwrap();
+    let results = handle.join().unwrap();
+
+    for result in results {
+        let lines: Vec<&str> = result.lines().collect();
+        assert_eq!(lines.len(), DEPTH * 2 + 1);
+        assert_eq!(lines[0], "x");
+        assert_eq!(lines[1], "|");
+        assert_eq!(lines[DEPTH * 2], "x");
+    }
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "icu")]
+
+use pretty_assertions::assert_eq;
+use std::io::ErrorKind;
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_children(
+        "words".to_string(),
+        vec![
+            "zebra".to_string(),
+            "Zebra".to_string(),
+            "Öl".to_string(),
+            "apple".to_string(),
+            "Äpfel".to_string(),
+            "éclair".to_string(),
+            "Eclair".to_string(),
+        ]
+        .into_iter(),
+    )
+}
+
+fn labels(tree: &StringTreeNode) -> Vec<&str> {
+    tree.children().map(|child| child.data().as_str()).collect()
+}
+
+#[test]
+fn test_locale_order() {
+    let mut tree = make_tree();
+    tree.sort_tree_by(locale_order("en").unwrap());
+    assert_eq!(
+        labels(&tree),
+        vec!["Äpfel", "apple", "Eclair", "éclair", "Öl", "zebra", "Zebra"]
+    );
+
+    tree.sort_tree_by(locale_order("sv").unwrap());
+    assert_eq!(
+        labels(&tree),
+        vec!["apple", "Eclair", "éclair", "zebra", "Zebra", "Äpfel", "Öl"]
+    );
+}
+
+#[test]
+fn test_locale_case_insensitive_order() {
+    let mut tree = make_tree();
+    tree.sort_tree_by(locale_case_insensitive_order("en").unwrap());
+    assert_eq!(
+        labels(&tree),
+        vec!["Äpfel", "apple", "Eclair", "éclair", "Öl", "Zebra", "zebra"]
+    );
+}
+
+#[test]
+fn test_invalid_locale() {
+    assert_eq!(
+        locale_order::<String>("not a locale").err().unwrap().kind(),
+        ErrorKind::InvalidInput
+    );
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "derive")]
+
+use std::collections::BTreeMap;
+use text_trees::*;
+
+#[derive(DisplayTree)]
+enum Expr {
+    Number(i64),
+    Add(Box<Expr>, Box<Expr>),
+    Negate { operand: Box<Expr> },
+    Zero,
+}
+
+#[derive(DisplayTree)]
+struct Config<T> {
+    name: String,
+    server: Server,
+    plugins: Vec<T>,
+    limits: BTreeMap<String, u32>,
+    timeout: Option<u32>,
+    #[tree(skip)]
+    #[allow(dead_code)]
+    secret: String,
+    #[tree(display)]
+    mode: Mode,
+}
+
+#[derive(DisplayTree)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(DisplayTree)]
+struct Plugin(String);
+
+struct Mode;
+
+impl std::fmt::Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "release")
+    }
+}
+
+#[test]
+fn test_derive_enum() {
+    let expr = Expr::Add(
+        Box::new(Expr::Number(1)),
+        Box::new(Expr::Negate {
+            operand: Box::new(Expr::Zero),
+        }),
+    );
+
+    let result = expr.to_tree_node().to_string();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"Add
++-- Number
+|   '-- 1
+'-- Negate
+    '-- operand: Zero
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_derive_generic_struct() {
+    let config = Config {
+        name: "example".to_string(),
+        server: Server {
+            host: "localhost".to_string(),
+            port: 8080,
+        },
+        plugins: vec![Plugin("auth".to_string()), Plugin("cache".to_string())],
+        limits: vec![("connections".to_string(), 10)].into_iter().collect(),
+        timeout: None,
+        secret: "hunter2".to_string(),
+        mode: Mode,
+    };
+
+    let result = config.to_tree_node().to_string();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"Config
++-- name: example
++-- server: Server
+|   +-- host: localhost
+|   '-- port: 8080
++-- plugins
+|   +-- Plugin
+|   |   '-- auth
+|   '-- Plugin
+|       '-- cache
++-- limits
+|   '-- connections: 10
++-- timeout: None
+'-- mode: release
+"#
+        .to_string()
+    );
+}
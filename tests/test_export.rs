@@ -0,0 +1,144 @@
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            "Uncle".into(),
+            StringTreeNode::with_children(
+                "Parent".to_string(),
+                vec!["Child, 1".to_string(), "Child \"2\"".to_string()].into_iter(),
+            ),
+        ]
+        .into_iter(),
+    )
+}
+
+#[test]
+fn test_write_csv() {
+    let tree = make_tree();
+    let mut buffer: Vec<u8> = Vec::new();
+
+    let result = tree.write_csv(&mut buffer);
+    assert!(result.is_ok());
+    let result = String::from_utf8(buffer).unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"depth,index_path,label
+0,,root
+1,0,Uncle
+1,1,Parent
+2,1.0,"Child, 1"
+2,1.1,"Child ""2"""
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_write_tsv_without_header() {
+    let tree = make_tree();
+    let mut buffer: Vec<u8> = Vec::new();
+    let options = CsvOptions {
+        header: false,
+        ..CsvOptions::tsv()
+    };
+
+    let result = tree.write_csv_with_options(&mut buffer, &options);
+    assert!(result.is_ok());
+    let result = String::from_utf8(buffer).unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        "0\t\troot\n1\t0\tUncle\n1\t1\tParent\n2\t1.0\tChild, 1\n2\t1.1\t\"Child \"\"2\"\"\"\n"
+            .to_string()
+    );
+}
+
+#[test]
+fn test_write_json_lines() {
+    let tree = make_tree();
+    let mut buffer: Vec<u8> = Vec::new();
+
+    let result = tree.write_json_lines(&mut buffer);
+    assert!(result.is_ok());
+    let result = String::from_utf8(buffer).unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"{"path":[],"depth":0,"label":"root"}
+{"path":[0],"depth":1,"label":"Uncle"}
+{"path":[1],"depth":1,"label":"Parent"}
+{"path":[1,0],"depth":2,"label":"Child, 1"}
+{"path":[1,1],"depth":2,"label":"Child \"2\""}
+"#
+        .to_string()
+    );
+}
+
+fn make_deep_tree(depth: usize) -> StringTreeNode {
+    let mut tree = StringTreeNode::new(depth.to_string());
+    for depth in (0..depth).rev() {
+        tree = StringTreeNode::with_child_nodes(depth.to_string(), vec![tree].into_iter());
+    }
+    tree
+}
+
+#[test]
+fn test_write_csv_very_deep_tree() {
+    const DEPTH: usize = 2_000;
+    const STACK: usize = 128 * 1024;
+
+    // A small stack ensures that exporting the tree does not recurse per level.
+    let handle = std::thread::Builder::new()
+        .stack_size(STACK)
+        .spawn(|| {
+            let tree = make_deep_tree(DEPTH);
+            let mut buffer: Vec<u8> = Vec::new();
+            tree.write_csv(&mut buffer).unwrap();
+            String::from_utf8(buffer).unwrap()
+        })
+        .unwrap();
+    let result = handle.join().unwrap();
+
+    let lines: Vec<&str> = result.lines().collect();
+    assert_eq!(lines.len(), DEPTH + 2);
+    assert_eq!(lines[1], "0,,0");
+    assert_eq!(lines[3], "2,0.0,2");
+    assert_eq!(
+        lines[DEPTH + 1],
+        format!("{},{},{}", DEPTH, vec!["0"; DEPTH].join("."), DEPTH)
+    );
+}
+
+#[test]
+fn test_write_json_lines_very_deep_tree() {
+    const DEPTH: usize = 2_000;
+    const STACK: usize = 128 * 1024;
+
+    // A small stack ensures that streaming the tree does not recurse per level.
+    let handle = std::thread::Builder::new()
+        .stack_size(STACK)
+        .spawn(|| {
+            let tree = make_deep_tree(DEPTH);
+            let mut buffer: Vec<u8> = Vec::new();
+            tree.write_json_lines(&mut buffer).unwrap();
+            String::from_utf8(buffer).unwrap()
+        })
+        .unwrap();
+    let result = handle.join().unwrap();
+
+    let lines: Vec<&str> = result.lines().collect();
+    assert_eq!(lines.len(), DEPTH + 1);
+    assert_eq!(lines[0], r#"{"path":[],"depth":0,"label":"0"}"#);
+    assert_eq!(
+        lines[DEPTH],
+        format!(
+            r#"{{"path":[{}],"depth":{},"label":"{}"}}"#,
+            vec!["0"; DEPTH].join(","),
+            DEPTH,
+            DEPTH
+        )
+    );
+}
@@ -0,0 +1,213 @@
+#![cfg(feature = "fs")]
+
+use pretty_assertions::assert_eq;
+use std::fs;
+use std::path::PathBuf;
+use text_trees::fs::{fs_tree, write_gnu_tree, FsEntryKind, FsIcons, FsOptions, FsSort, FsSummary};
+use text_trees::{BadgePosition, TreeFormatting};
+
+fn make_test_dir(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("text_trees_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src/nested")).unwrap();
+    fs::create_dir_all(root.join(".git")).unwrap();
+    fs::write(root.join("Cargo.toml"), "[package]\n").unwrap();
+    fs::write(root.join("README.md"), "# Readme\n").unwrap();
+    fs::write(root.join("src/lib.rs"), "").unwrap();
+    fs::write(root.join("src/lib.rs.bak"), "").unwrap();
+    fs::write(root.join("src/nested/mod.rs"), "").unwrap();
+    root
+}
+
+fn relabel_root(result: String) -> String {
+    let mut lines = result.lines();
+    let _ = lines.next();
+    let mut result = String::from("ROOT\n");
+    for line in lines {
+        result.push_str(line);
+        result.push('\n');
+    }
+    result
+}
+
+#[test]
+fn test_fs_tree_defaults() {
+    let root = make_test_dir("defaults");
+    let options = FsOptions {
+        icons: false,
+        ..Default::default()
+    };
+    let tree = fs_tree(&root, &options).unwrap();
+    assert_eq!(tree.data().kind(), FsEntryKind::Directory);
+
+    let result = relabel_root(tree.to_string());
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"ROOT
++-- Cargo.toml
++-- README.md
+'-- src
+    +-- lib.rs
+    +-- lib.rs.bak
+    '-- nested
+        '-- mod.rs
+"#
+        .to_string()
+    );
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_fs_tree_options() {
+    let root = make_test_dir("options");
+    let options = FsOptions {
+        show_hidden: true,
+        max_depth: Some(1),
+        exclude: vec!["*.bak".to_string(), "README*".to_string()],
+        sort: FsSort::DirectoriesFirst,
+        sizes: true,
+        ..Default::default()
+    };
+    let result = relabel_root(fs_tree(&root, &options).unwrap().to_string());
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"ROOT
++-- 📁 .git
++-- 📁 src
+'-- 📄 Cargo.toml (10 B)
+"#
+        .to_string()
+    );
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_fs_tree_include() {
+    let root = make_test_dir("include");
+    let options = FsOptions {
+        include: vec!["*.rs".to_string()],
+        icons: false,
+        ..Default::default()
+    };
+    let result = relabel_root(fs_tree(&root, &options).unwrap().to_string());
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"ROOT
+'-- src
+    +-- lib.rs
+    '-- nested
+        '-- mod.rs
+"#
+        .to_string()
+    );
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_fs_tree_missing_root() {
+    let options = FsOptions::default();
+    assert!(fs_tree("/this/path/does/not/exist", &options).is_err());
+}
+
+#[test]
+fn test_fs_tree_gnu() {
+    let root = make_test_dir("gnu");
+    let options = FsOptions {
+        show_hidden: true,
+        ..FsOptions::gnu_tree()
+    };
+    let tree = fs_tree(&root, &options).unwrap();
+    assert_eq!(
+        FsSummary::of(&tree),
+        FsSummary {
+            directories: 3,
+            files: 5
+        }
+    );
+
+    let mut buffer: Vec<u8> = Vec::new();
+    write_gnu_tree(&tree, &options, &mut buffer).unwrap();
+    let result = relabel_root(String::from_utf8(buffer).unwrap());
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"ROOT
+├── .git
+├── Cargo.toml
+├── README.md
+└── src
+    ├── lib.rs
+    ├── lib.rs.bak
+    └── nested
+        └── mod.rs
+
+3 directories, 5 files
+"#
+        .to_string()
+    );
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_fs_tree_gnu_directories_only() {
+    let root = make_test_dir("gnu_dirs");
+    let options = FsOptions {
+        directories_only: true,
+        ..FsOptions::gnu_tree()
+    };
+    let tree = fs_tree(&root, &options).unwrap();
+
+    let mut buffer: Vec<u8> = Vec::new();
+    write_gnu_tree(&tree, &options, &mut buffer).unwrap();
+    let result = relabel_root(String::from_utf8(buffer).unwrap());
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"ROOT
+└── src
+    └── nested
+
+2 directories
+"#
+        .to_string()
+    );
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_fs_tree_icon_legend() {
+    let root = make_test_dir("icon_legend");
+    let options = FsOptions {
+        icons: false,
+        ..Default::default()
+    };
+    let tree = fs_tree(&root, &options).unwrap();
+    let format = TreeFormatting {
+        badge_position: BadgePosition::BeforeLabel,
+        badge_legend: true,
+        ..Default::default()
+    };
+
+    let result = relabel_root(tree.to_string_with_badges(&format, &FsIcons).unwrap());
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"ROOT
++-- 📄 Cargo.toml
++-- 📄 README.md
+'-- 📁 src
+    +-- 📄 lib.rs
+    +-- 📄 lib.rs.bak
+    '-- 📁 nested
+        '-- 📄 mod.rs
+
+📁 directory
+📄 file
+"#
+        .to_string()
+    );
+    fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "globset")]
+
+use pretty_assertions::assert_eq;
+use std::io::ErrorKind;
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "crate".to_string(),
+        vec![
+            StringTreeNode::with_children(
+                "src".to_string(),
+                vec!["lib.rs".to_string(), "main.rs".to_string()].into_iter(),
+            ),
+            StringTreeNode::with_children(
+                "tests".to_string(),
+                vec!["test_lib.rs".to_string(), "data.json".to_string()].into_iter(),
+            ),
+            "Cargo.toml".into(),
+        ]
+        .into_iter(),
+    )
+}
+
+#[test]
+fn test_find_all_matching() {
+    let tree = make_tree();
+    let filter = GlobFilter::new(vec!["test_*", "*.toml"]).unwrap();
+    let labels: Vec<&str> = tree
+        .find_all(filter.predicate())
+        .into_iter()
+        .map(|(_, node)| node.data().as_str())
+        .collect();
+    assert_eq!(labels, vec!["test_lib.rs", "Cargo.toml"]);
+}
+
+#[test]
+fn test_write_with_deleted_matching() {
+    let tree = make_tree();
+    let filter = GlobFilter::new(vec!["*.{rs,json}"]).unwrap();
+    let format = TreeFormatting {
+        deleted_style: DeletedStyle::Markers("~".to_string(), "~".to_string()),
+        ..Default::default()
+    };
+    let result = tree
+        .to_string_with_deleted(&format, filter.predicate())
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"crate
++-- src
+|   +-- ~lib.rs~
+|   '-- ~main.rs~
++-- tests
+|   +-- ~test_lib.rs~
+|   '-- ~data.json~
+'-- Cargo.toml
+"#
+    );
+}
+
+#[test]
+fn test_invalid_pattern() {
+    assert_eq!(
+        GlobFilter::new(vec!["[a-"]).err().unwrap().kind(),
+        ErrorKind::InvalidInput
+    );
+    assert!(!GlobFilter::new(Vec::<String>::new())
+        .unwrap()
+        .is_match("anything"));
+}
+
+#[test]
+fn test_hide_unmatched() {
+    let mut tree = make_tree();
+    let filter = GlobFilter::new(vec!["test_*", "*.toml"]).unwrap();
+    tree.hide_unmatched(filter.predicate());
+    let result = tree.to_string();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"crate
++-- tests
+|   '-- test_lib.rs
+'-- Cargo.toml
+"#
+    );
+}
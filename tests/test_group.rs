@@ -0,0 +1,108 @@
+use pretty_assertions::assert_eq;
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    let mut src = StringTreeNode::new("src/".to_string());
+    src.push("lib.rs".to_string());
+    src.push("bin/".to_string());
+    src.push("main.rs".to_string());
+    let mut root = StringTreeNode::new("crate/".to_string());
+    root.push("Cargo.toml".to_string());
+    root.push_node(src);
+    root.push("README.md".to_string());
+    root
+}
+
+fn classify(name: &str) -> String {
+    if name.ends_with('/') {
+        "directories".to_string()
+    } else {
+        "files".to_string()
+    }
+}
+
+#[test]
+fn test_grouped() {
+    let tree = make_tree();
+    let result = tree
+        .to_string_grouped(
+            &TreeFormatting::dir_tree(FormatCharacters::box_chars()),
+            &GroupOptions::default(),
+            |name| classify(name),
+        )
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"crate/
+├── [files]
+│   ├── Cargo.toml
+│   └── README.md
+└── [directories]
+    └── src/
+        ├── [files]
+        │   ├── lib.rs
+        │   └── main.rs
+        └── [directories]
+            └── bin/
+"#
+    );
+}
+
+#[test]
+fn test_grouped_with_connectors() {
+    let tree = make_tree();
+    let options = GroupOptions {
+        header_prefix: String::new(),
+        header_suffix: ":".to_string(),
+        connectors: Some(BranchConnectors::box_chars()),
+    };
+    let result = tree
+        .to_string_grouped(
+            &TreeFormatting::dir_tree(FormatCharacters::box_chars()),
+            &options,
+            |name| classify(name),
+        )
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"crate/
+┝── files:
+│   ├── Cargo.toml
+│   └── README.md
+┕── directories:
+    └── src/
+        ┝── files:
+        │   ├── lib.rs
+        │   └── main.rs
+        ┕── directories:
+            └── bin/
+"#
+    );
+
+    let mut buffer: Vec<u8> = Vec::new();
+    tree.write_grouped(
+        &mut buffer,
+        &TreeFormatting::dir_tree(FormatCharacters::box_chars()),
+        &options,
+        |name| classify(name),
+    )
+    .unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), result);
+}
+
+#[test]
+fn test_grouped_does_not_change_tree() {
+    let tree = make_tree();
+    let copy = tree.clone();
+    let _ = tree
+        .to_string_grouped(
+            &TreeFormatting::default(),
+            &GroupOptions::default(),
+            |name| classify(name),
+        )
+        .unwrap();
+    assert_eq!(tree, copy);
+    assert_eq!(tree.descendant_count(), 6);
+}
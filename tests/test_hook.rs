@@ -0,0 +1,129 @@
+use text_trees::{RenderHook, StringTreeNode, TreeFormatting, TreeNode, TreeOrientation};
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            StringTreeNode::with_children(
+                "Parent".to_string(),
+                vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+            ),
+            "Uncle".into(),
+        ]
+        .into_iter(),
+    )
+}
+
+#[derive(Debug, Default)]
+struct Recorder(Vec<String>);
+
+impl RenderHook<String> for Recorder {
+    fn on_node_start(&mut self, path: &[usize], depth: usize, node: &TreeNode<String>) {
+        self.0
+            .push(format!("start {:?} {} {}", path, depth, node.data()));
+    }
+
+    fn on_line(&mut self, path: &[usize], depth: usize, line: &mut String) {
+        self.0.push(format!("line {:?} {} {}", path, depth, line));
+    }
+
+    fn on_node_end(&mut self, path: &[usize], depth: usize, node: &TreeNode<String>) {
+        self.0
+            .push(format!("end {:?} {} {}", path, depth, node.data()));
+    }
+}
+
+#[derive(Debug)]
+struct FoldMarkers;
+
+impl RenderHook<String> for FoldMarkers {
+    fn on_line(&mut self, path: &[usize], _depth: usize, line: &mut String) {
+        if path.is_empty() {
+            line.push_str(" {{{");
+        }
+    }
+}
+
+#[test]
+fn test_hook_callbacks() {
+    let tree = make_tree();
+    let mut recorder = Recorder::default();
+
+    let result = tree.to_string_with_hook(&TreeFormatting::default(), &mut recorder);
+    assert!(result.is_ok());
+    assert_eq!(result.unwrap(), tree.to_string());
+    assert_eq!(
+        recorder.0,
+        vec![
+            "start [] 0 root",
+            "line [] 0 root",
+            "start [0] 1 Parent",
+            "line [0] 1 +-- Parent",
+            "start [0, 0] 2 Child 1",
+            "line [0, 0] 2 |   +-- Child 1",
+            "end [0, 0] 2 Child 1",
+            "start [0, 1] 2 Child 2",
+            "line [0, 1] 2 |   '-- Child 2",
+            "end [0, 1] 2 Child 2",
+            "end [0] 1 Parent",
+            "start [1] 1 Uncle",
+            "line [1] 1 '-- Uncle",
+            "end [1] 1 Uncle",
+            "end [] 0 root",
+        ]
+    );
+}
+
+#[test]
+fn test_hook_changes_lines() {
+    let tree = make_tree();
+
+    let result = tree.to_string_with_hook(&TreeFormatting::default(), &mut FoldMarkers);
+    assert!(result.is_ok());
+    assert_eq!(
+        result.unwrap(),
+        "root {{{\n+-- Parent\n|   +-- Child 1\n|   '-- Child 2\n'-- Uncle\n"
+    );
+}
+
+#[test]
+fn test_hook_classic() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        orientation: TreeOrientation::Classic,
+        ..Default::default()
+    };
+    let mut recorder = Recorder::default();
+
+    let result = tree.to_string_with_hook(&format, &mut recorder);
+    assert!(result.is_ok());
+    assert_eq!(
+        result.unwrap(),
+        tree.to_string_with_format(&format).unwrap()
+    );
+    let nodes: Vec<&String> = recorder
+        .0
+        .iter()
+        .filter(|event| !event.starts_with("line"))
+        .collect();
+    assert_eq!(
+        nodes,
+        vec![
+            "start [] 0 root",
+            "start [0] 1 Parent",
+            "start [0, 0] 2 Child 1",
+            "end [0, 0] 2 Child 1",
+            "start [0, 1] 2 Child 2",
+            "end [0, 1] 2 Child 2",
+            "end [0] 1 Parent",
+            "start [1] 1 Uncle",
+            "end [1] 1 Uncle",
+            "end [] 0 root",
+        ]
+    );
+    assert!(recorder
+        .0
+        .iter()
+        .filter(|event| event.starts_with("line"))
+        .all(|event| event.starts_with("line [] 0 ")));
+}
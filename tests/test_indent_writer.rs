@@ -0,0 +1,86 @@
+use pretty_assertions::assert_eq;
+use std::io::Write;
+use text_trees::*;
+
+fn write_with_bodies(format: &TreeFormatting) -> String {
+    // root
+    // +-- Parent      position [false], has children
+    // |   +-- Child 1 position [false, false]
+    // |   '-- Child 2 position [false, true]
+    // '-- Uncle       position [true]
+    let mut lines = StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            StringTreeNode::with_children(
+                "Parent".to_string(),
+                vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+            ),
+            "Uncle".into(),
+        ]
+        .into_iter(),
+    )
+    .lines_with_format(format)
+    .collect::<Vec<String>>()
+    .into_iter();
+    let positions: [(&[bool], bool); 5] = [
+        (&[], true),
+        (&[false], true),
+        (&[false, false], false),
+        (&[false, true], false),
+        (&[true], false),
+    ];
+
+    let mut output: Vec<u8> = Vec::new();
+    for (position, has_children) in positions.iter() {
+        writeln!(output, "{}", lines.next().unwrap()).unwrap();
+        let mut body = IndentWriter::new(&mut output, format, position, *has_children);
+        write!(body, "body of {:?}\n\nend", position).unwrap();
+        writeln!(body).unwrap();
+    }
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn test_indent_below_nodes() {
+    let result = write_with_bodies(&TreeFormatting::dir_tree(FormatCharacters::box_chars()));
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
+│   body of []
+│
+│   end
+├── Parent
+│   │   body of [false]
+│   │
+│   │   end
+│   ├── Child 1
+│   │   body of [false, false]
+│   │
+│   │   end
+│   └── Child 2
+│       body of [false, true]
+│
+│       end
+└── Uncle
+    body of [true]
+
+    end
+"#
+    );
+}
+
+#[test]
+fn test_indent_with_prefix() {
+    let format = TreeFormatting::dir_tree_with_prefix(FormatCharacters::ascii(), "> ".to_string());
+    let mut output: Vec<u8> = Vec::new();
+    let mut body = IndentWriter::new(&mut output, &format, &[true, false], true);
+    assert_eq!(body.indent(), ">     |   |   ");
+    write!(body, "one").unwrap();
+    write!(body, " two\nthree\n").unwrap();
+    let output = body.into_inner();
+    assert_eq!(
+        String::from_utf8(output.clone()).unwrap(),
+        ">     |   |   one two\n>     |   |   three\n"
+    );
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "serde_json")]
+
+use serde_json::{json, Value};
+use text_trees::*;
+
+fn make_value() -> Value {
+    json!({
+        "name": "text_trees",
+        "description": "Simple textual output for tree-like structures.",
+        "authors": ["Simon Johnston"],
+        "metadata": { "stars": 12, "published": true, "fork": null }
+    })
+}
+
+fn write_value(value: &Value, format: &TreeFormatting, options: &ValueTreeOptions) -> String {
+    let mut buffer: Vec<u8> = Vec::new();
+    let result = write_json_value(value, &mut buffer, format, options);
+    assert!(result.is_ok());
+    let result = String::from_utf8(buffer).unwrap();
+    println!("{}", result);
+    result
+}
+
+#[test]
+fn test_json_value_with_type_badges() {
+    let options = ValueTreeOptions {
+        root_label: "package".to_string(),
+        max_value_width: Some(12),
+        type_badges: true,
+        ..Default::default()
+    };
+
+    let result = write_value(
+        &make_value(),
+        &TreeFormatting::dir_tree(FormatCharacters::box_chars()),
+        &options,
+    );
+    assert_eq!(
+        result,
+        r#"package                       (object)
+├── authors                   (array)
+│   └── [0]: "Simon John…     (string)
+├── description: "Simple tex… (string)
+├── metadata                  (object)
+│   ├── fork: null            (null)
+│   ├── published: true       (boolean)
+│   └── stars: 12             (number)
+└── name: "text_trees"        (string)
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_json_value_without_index_labels() {
+    let value = json!([1, "two", [3], { "four": 4 }]);
+    let options = ValueTreeOptions {
+        index_labels: false,
+        ..Default::default()
+    };
+
+    let result = write_value(&value, &TreeFormatting::default(), &options);
+    assert_eq!(
+        result,
+        r#"$
++-- 1
++-- "two"
++-- []
+|   '-- 3
+'-- {}
+    '-- four: 4
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_json_scalar_root() {
+    let tree: StringTreeNode = (&json!(42)).into();
+    assert_eq!(tree.to_string(), "$: 42\n");
+}
@@ -0,0 +1,71 @@
+use pretty_assertions::assert_eq;
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            StringTreeNode::with_children(
+                "Parent".to_string(),
+                vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+            ),
+            "Uncle".into(),
+        ]
+        .into_iter(),
+    )
+}
+
+#[test]
+fn test_first_render_changes_every_line() {
+    let tree = make_tree();
+    let mut lines = Vec::new();
+
+    let diff = tree.diff_render(&mut lines, &Default::default());
+    assert_eq!(diff.line_count, 5);
+    assert_eq!(diff.previous_line_count, 0);
+    assert_eq!(
+        diff.changed
+            .iter()
+            .map(|(index, _)| *index)
+            .collect::<Vec<usize>>(),
+        vec![0, 1, 2, 3, 4]
+    );
+    assert_eq!(lines.join("\n") + "\n", tree.to_string());
+
+    let diff = tree.diff_render(&mut lines, &Default::default());
+    assert!(diff.is_empty());
+}
+
+#[test]
+fn test_changed_and_removed_lines() {
+    let mut tree = make_tree();
+    let mut lines = Vec::new();
+    let _ = tree.diff_render(&mut lines, &Default::default());
+    let previous = lines.clone();
+
+    let _ = tree.children_vec_mut()[0].children_vec_mut().pop();
+    let diff = tree.diff_render(&mut lines, &Default::default());
+    assert_eq!(
+        diff.changed,
+        vec![
+            (2, "|   '-- Child 1".to_string()),
+            (3, "'-- Uncle".to_string())
+        ]
+    );
+    assert_eq!(diff.removed(), 4..5);
+    assert!(!diff.is_empty());
+
+    let mut applied = previous;
+    diff.apply(&mut applied);
+    assert_eq!(applied, lines);
+}
+
+#[test]
+fn test_between_longer_output() {
+    let previous = vec!["a".to_string()];
+    let current = vec!["a".to_string(), "b".to_string()];
+
+    let diff = LineDiff::between(&previous, &current);
+    assert_eq!(diff.changed, vec![(1, "b".to_string())]);
+    assert!(diff.removed().is_empty());
+}
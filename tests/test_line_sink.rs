@@ -0,0 +1,123 @@
+use pretty_assertions::assert_eq;
+use std::io::{self, ErrorKind};
+use std::sync::mpsc::{channel, sync_channel};
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            StringTreeNode::with_children(
+                "Parent".to_string(),
+                vec!["Child".to_string()].into_iter(),
+            ),
+            "Uncle".into(),
+        ]
+        .into_iter(),
+    )
+}
+
+struct Limited(usize);
+
+impl LineSink for Limited {
+    fn line(&mut self, _: &str) -> io::Result<()> {
+        if self.0 == 0 {
+            return Err(io::Error::new(ErrorKind::WriteZero, "full"));
+        }
+        self.0 -= 1;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct FlushRecorder {
+    data: Vec<u8>,
+    flushed_at: Vec<usize>,
+}
+
+impl io::Write for FlushRecorder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushed_at.push(self.data.len());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_lines_to_vec() {
+    let tree = make_tree();
+    let format = TreeFormatting::dir_tree(FormatCharacters::box_chars());
+
+    let mut lines: Vec<String> = Vec::new();
+    tree.write_lines_to(&mut lines, &format).unwrap();
+    assert_eq!(
+        lines,
+        vec!["root", "├── Parent", "│   └── Child", "└── Uncle"]
+    );
+    assert_eq!(
+        lines,
+        tree.lines_with_format(&format).collect::<Vec<String>>()
+    );
+}
+
+#[test]
+fn test_lines_to_io_write() {
+    let tree = make_tree();
+
+    let mut bytes: Vec<u8> = Vec::new();
+    tree.write_lines_to(&mut IoWrite(&mut bytes), &Default::default())
+        .unwrap();
+    assert_eq!(String::from_utf8(bytes).unwrap(), tree.to_string());
+}
+
+#[test]
+fn test_lines_to_io_write_flush_policy() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        flush_policy: FlushPolicy::EveryLines(2),
+        ..Default::default()
+    };
+
+    let mut writer = FlushRecorder::default();
+    tree.write_lines_to(&mut IoWrite(&mut writer), &format)
+        .unwrap();
+    let text = String::from_utf8(writer.data.clone()).unwrap();
+    let line_ends: Vec<usize> = text
+        .match_indices('\n')
+        .map(|(index, _)| index + 1)
+        .collect();
+    assert_eq!(
+        writer.flushed_at,
+        vec![line_ends[1], line_ends[3], text.len()]
+    );
+}
+
+#[test]
+fn test_lines_to_channels() {
+    let tree = make_tree();
+
+    let (mut sender, receiver) = sync_channel(8);
+    tree.write_lines_to(&mut sender, &Default::default())
+        .unwrap();
+    drop(sender);
+    assert_eq!(receiver.iter().count(), 4);
+
+    let (mut sender, receiver) = channel::<String>();
+    drop(receiver);
+    let result = tree.write_lines_to(&mut sender, &Default::default());
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn test_sink_error_stops_rendering() {
+    let tree = make_tree();
+
+    let result = tree.write_lines_to(&mut Limited(2), &Default::default());
+    let error = result.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::WriteZero);
+    assert_eq!(error.to_string(), "full");
+}
@@ -0,0 +1,94 @@
+use pretty_assertions::assert_eq;
+use text_trees::*;
+
+#[test]
+fn test_from_newick() {
+    let tree = from_newick(" ( (A:0.1, B : 2e-1)E:0.5 [internal], 'C''s node':3 )F ;\n").unwrap();
+    assert_eq!(
+        tree.to_string_with_annotations(&TreeFormatting::default(), NewickLabel::annotation)
+            .unwrap(),
+        r#"F
++-- E :0.5
+|   +-- A :0.1
+|   '-- B :0.2
+'-- C's node :3
+"#
+    );
+    assert_eq!(tree.data().length(), None);
+    assert_eq!(tree.children().nth(1).unwrap().data().name(), "C's node");
+}
+
+#[test]
+fn test_from_newick_unnamed_nodes() {
+    let tree = from_newick("(,(Homo_sapiens,),);").unwrap();
+    assert_eq!(tree.to_newick(), "(,('Homo sapiens',),);");
+    assert_eq!(tree.descendant_count(), 5);
+    assert_eq!(
+        from_newick("A;").unwrap(),
+        TreeNode::new(NewickLabel::new("A"))
+    );
+}
+
+#[test]
+fn test_from_newick_errors() {
+    for (text, message) in &[
+        ("(A,B)", "invalid Newick at offset 5: missing ';'"),
+        (
+            "(A,B;",
+            "invalid Newick at offset 4: unexpected character ';'",
+        ),
+        (
+            "(A,(B);",
+            "invalid Newick at offset 6: unexpected character ';'",
+        ),
+        ("(A,(B)", "invalid Newick at offset 6: unclosed '('"),
+        (
+            "A,B;",
+            "invalid Newick at offset 1: unexpected character ','",
+        ),
+        ("A:x;", "invalid Newick at offset 2: invalid branch length"),
+        ("'A;", "invalid Newick at offset 0: unclosed quoted name"),
+        ("A[;", "invalid Newick at offset 1: unclosed comment"),
+        (
+            "A; B;",
+            "invalid Newick at offset 3: unexpected text after ';'",
+        ),
+    ] {
+        let error = from_newick(text).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(&error.to_string(), message);
+    }
+}
+
+#[test]
+fn test_to_newick() {
+    let tree = StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            StringTreeNode::with_children(
+                "a (1)".to_string(),
+                vec!["it's".to_string(), "b_c".to_string()].into_iter(),
+            ),
+            "d".into(),
+        ]
+        .into_iter(),
+    );
+    assert_eq!(tree.to_newick(), "(('it''s','b_c')'a (1)',d)root;");
+    assert_eq!(
+        tree.to_newick_with_lengths(|label| Some(label.len() as f64 / 2.0)),
+        "(('it''s':2,'b_c':1.5)'a (1)':2.5,d:0.5)root:2;"
+    );
+}
+
+#[test]
+fn test_newick_round_trip() {
+    let text = "((raccoon:19.2,bear:6.8):0.8,((sea_lion:12.0,seal:12.0):7.5,'monkey':100.9):3.9,\
+                weasel:18.9);";
+    let tree = from_newick(text).unwrap();
+    let written = tree.to_newick_with_lengths(NewickLabel::length);
+    assert_eq!(
+        written,
+        "((raccoon:19.2,bear:6.8):0.8,(('sea lion':12,seal:12):7.5,monkey:100.9):3.9,weasel:18.9);"
+    );
+    assert_eq!(from_newick(&written).unwrap(), tree);
+}
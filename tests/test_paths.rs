@@ -0,0 +1,68 @@
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use text_trees::*;
+
+#[test]
+fn test_from_path() {
+    let tree = StringTreeNode::from(Path::new("/usr/./lib/libc.so"));
+    assert_eq!(
+        tree.to_string(),
+        "/\n'-- usr\n    '-- lib\n        '-- libc.so\n"
+    );
+
+    let tree = StringTreeNode::from(Path::new("."));
+    assert_eq!(tree.label(), "");
+    assert!(!tree.has_children());
+}
+
+#[test]
+fn test_insert_path() {
+    let mut tree = StringTreeNode::new(".".to_string());
+    for path in &["src/lib.rs", "src/bin/ttree.rs", "README.md", "src/lib.rs"] {
+        let path = Path::new(path);
+        let leaf = tree.insert_path(
+            path.parent().unwrap(),
+            path.file_name().unwrap().to_string_lossy().into_owned(),
+        );
+        assert!(!leaf.has_children());
+    }
+    tree.insert_path(Path::new("src/bin"), "tls.rs".to_string())
+        .push("(example)".to_string());
+
+    assert_eq!(
+        tree.to_string(),
+        r#".
++-- src
+|   +-- lib.rs
+|   '-- bin
+|       +-- ttree.rs
+|       '-- tls.rs
+|           '-- (example)
+'-- README.md
+"#
+    );
+}
+
+#[test]
+fn test_insert_path_with_separator() {
+    let mut tree = StringTreeNode::new("root".to_string());
+    tree.insert_path_with_separator("a/b/c", "/");
+    tree.insert_path_with_separator("/a//b/d/", "/");
+    tree.insert_path_with_separator("e", "/");
+    assert_eq!(tree.insert_path_with_separator("", "/").label(), "root");
+    tree.insert_path_with_separator("a.x", ".")
+        .push("y".to_string());
+
+    assert_eq!(
+        tree.to_string(),
+        r#"root
++-- a
+|   +-- b
+|   |   +-- c
+|   |   '-- d
+|   '-- x
+|       '-- y
+'-- e
+"#
+    );
+}
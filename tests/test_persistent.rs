@@ -0,0 +1,105 @@
+use pretty_assertions::assert_eq;
+use text_trees::*;
+
+fn make_tree() -> PersistentTree<String> {
+    PersistentTree::with_child_nodes(
+        "root".to_string(),
+        vec![
+            PersistentTree::with_children(
+                "Parent".to_string(),
+                vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+            ),
+            PersistentTree::new("Uncle".to_string()),
+        ]
+        .into_iter(),
+    )
+}
+
+#[test]
+fn test_changes_share_unchanged_subtrees() {
+    let v1 = make_tree();
+    let v2 = v1.push(&[0, 1], "Grandchild".to_string()).unwrap();
+    let v3 = v2.replace(&[1], "Aunt".to_string()).unwrap();
+    let v4 = v3.remove(&[0, 0]).unwrap();
+
+    assert!(!v2.ptr_eq(&v1));
+    assert!(v2.children()[1].ptr_eq(&v1.children()[1]));
+    assert!(v2
+        .subtree_at(&[0, 0])
+        .unwrap()
+        .ptr_eq(v1.subtree_at(&[0, 0]).unwrap()));
+    assert!(v3.children()[0].ptr_eq(&v2.children()[0]));
+    assert!(v4.children()[1].ptr_eq(&v3.children()[1]));
+
+    assert_eq!(v1, make_tree());
+    let result = v4.to_string();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
++-- Parent
+|   '-- Child 2
+|       '-- Grandchild
+'-- Aunt
+"#
+    );
+}
+
+#[test]
+fn test_invalid_paths() {
+    let tree = make_tree();
+    assert!(tree.push(&[2], "x".to_string()).is_none());
+    assert!(tree.replace(&[0, 2], "x".to_string()).is_none());
+    assert!(tree.remove(&[0, 2]).is_none());
+    assert!(tree.remove(&[]).is_none());
+}
+
+#[test]
+fn test_written_with_format() {
+    let tree = make_tree();
+    let format = TreeFormatting::dir_tree(FormatCharacters::box_chars());
+    assert_eq!(
+        tree.as_tree_node().to_string_with_format(&format).unwrap(),
+        StringTreeNode::with_child_nodes(
+            "root".to_string(),
+            vec![
+                StringTreeNode::with_children(
+                    "Parent".to_string(),
+                    vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+                ),
+                "Uncle".into(),
+            ]
+            .into_iter(),
+        )
+        .to_string_with_format(&format)
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_very_deep_path() {
+    const DEPTH: usize = 5_000;
+    const STACK: usize = 256 * 1024;
+
+    // A small stack ensures that neither changing, nor dropping, the tree recurses per level.
+    let handle = std::thread::Builder::new()
+        .stack_size(STACK)
+        .spawn(|| {
+            let mut tree = PersistentTree::new(DEPTH);
+            for depth in (0..DEPTH).rev() {
+                tree = PersistentTree::with_child_nodes(depth, vec![tree].into_iter());
+            }
+            let path = vec![0; DEPTH];
+            let replaced = tree.replace(&path, 0).unwrap();
+            let pushed = replaced.push(&path, 1).unwrap();
+            let removed = pushed.remove(&path).unwrap();
+            (
+                *tree.subtree_at(&path).unwrap().data(),
+                *replaced.subtree_at(&path).unwrap().data(),
+                pushed.subtree_at(&path).unwrap().children().len(),
+                removed.subtree_at(&path[1..]).unwrap().children().len(),
+            )
+        })
+        .unwrap();
+    assert_eq!(handle.join().unwrap(), (DEPTH, 0, 1, 0));
+}
@@ -0,0 +1,66 @@
+use text_trees::progress::{ProgressNode, ProgressRenderer, ProgressStatus, ProgressTree};
+use text_trees::TreeFormatting;
+
+fn make_tree() -> ProgressTree {
+    ProgressTree::with_children(
+        ProgressNode::new("build"),
+        vec![ProgressNode::new("compile"), ProgressNode::new("test")].into_iter(),
+    )
+}
+
+#[test]
+fn test_set_status() {
+    let mut tree = make_tree();
+    assert!(!tree.is_finished());
+    assert!(tree.set_status(&[], ProgressStatus::Ok));
+    assert!(tree.set_status(&[0], ProgressStatus::Ok));
+    assert!(tree.set_status(&[1], ProgressStatus::Failed));
+    assert!(!tree.set_status(&[2], ProgressStatus::Ok));
+    assert!(tree.is_finished());
+    assert_eq!(
+        tree.children().nth(1).unwrap().data().status(),
+        ProgressStatus::Failed
+    );
+}
+
+#[test]
+fn test_draw_rewrites_changed_lines() {
+    let mut tree = make_tree();
+    let mut renderer = ProgressRenderer::new(Vec::new(), &TreeFormatting::default());
+
+    renderer.draw(&tree).unwrap();
+    let _ = tree.set_status(&[1], ProgressStatus::Running);
+    renderer.draw(&tree).unwrap();
+    let _ = tree.set_status(&[1], ProgressStatus::Ok);
+    tree.push_node(ProgressNode::with_status("package", ProgressStatus::Running).into());
+    renderer.draw(&tree).unwrap();
+
+    let output = String::from_utf8(renderer.into_inner()).unwrap();
+    let expected = [
+        "\x1b[2Kbuild       ·\n",
+        "\x1b[2K+-- compile ·\n",
+        "\x1b[2K'-- test    ·\n",
+        "\x1b[3F",
+        "\x1b[1E",
+        "\x1b[1E",
+        "\x1b[2K'-- test    ▶\n",
+        "\x1b[3F",
+        "\x1b[1E",
+        "\x1b[1E",
+        "\x1b[2K+-- test    ✔\n",
+        "\x1b[2K'-- package ▶\n",
+    ]
+    .concat();
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_draw_clears_removed_lines() {
+    let tree = make_tree();
+    let mut renderer = ProgressRenderer::new(Vec::new(), &TreeFormatting::default());
+    renderer.draw(&tree).unwrap();
+    renderer.draw(&ProgressTree::new("build".into())).unwrap();
+
+    let output = String::from_utf8(renderer.into_inner()).unwrap();
+    assert!(output.ends_with("\x1b[3F\x1b[2Kbuild ·\n\x1b[J"));
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "proptest")]
+
+use proptest::prelude::*;
+use text_trees::*;
+
+fn options() -> TreeStrategyOptions {
+    TreeStrategyOptions {
+        max_depth: 3,
+        max_children: 4,
+        desired_size: 32,
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_generated_trees_are_within_bounds(tree in string_tree_strategy(&options())) {
+        let stats = tree.stats();
+        prop_assert!(stats.height <= 3);
+        prop_assert!(stats.max_children <= 4);
+    }
+
+    #[test]
+    fn test_writes_a_line_per_node(tree in string_tree_strategy(&options())) {
+        for format in [
+            TreeFormatting::dir_tree(FormatCharacters::ascii()),
+            TreeFormatting::dir_tree_left(FormatCharacters::box_chars()),
+        ] {
+            let result = tree.to_string_with_format(&format).unwrap();
+            prop_assert_eq!(result.lines().count(), 1 + tree.descendant_count());
+        }
+    }
+
+    #[test]
+    fn test_writing_never_fails(tree in tree_strategy(any::<u8>(), &options())) {
+        let format = TreeFormatting {
+            orientation: TreeOrientation::Classic,
+            ..Default::default()
+        };
+        prop_assert!(tree.to_string_with_format(&format).is_ok());
+        prop_assert!(tree.to_string_with_format(&TreeFormatting::default()).is_ok());
+    }
+
+    #[test]
+    fn test_roundtrip(tree in tree_strategy(".*", &options())) {
+        prop_assert_eq!(roundtrip::parse(&roundtrip::to_string(&tree)).unwrap(), tree);
+    }
+}
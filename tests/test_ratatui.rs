@@ -0,0 +1,131 @@
+#![cfg(feature = "ratatui")]
+
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_children(
+        "root".to_string(),
+        vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+    )
+}
+
+#[test]
+fn test_to_text_unstyled() {
+    let tree = make_tree();
+
+    let text = tree
+        .to_text(&TreeFormatting::dir_tree(FormatCharacters::box_chars()))
+        .unwrap();
+    let lines: Vec<String> = text.lines.iter().map(ToString::to_string).collect();
+    assert_eq!(lines, vec!["root", "├── Child 1", "└── Child 2"]);
+}
+
+#[test]
+fn test_to_text_with_styles() {
+    let tree = make_tree();
+    let styles = TextStyles {
+        prefix: Style::default().fg(Color::DarkGray),
+        lines: Style::default().fg(Color::Blue),
+        label: Style::default().fg(Color::White),
+        ..Default::default()
+    };
+    let format = TreeFormatting::dir_tree_with_prefix(FormatCharacters::ascii(), "> ".to_string());
+
+    let text = tree.to_text_with_styles(&format, &styles).unwrap();
+    assert_eq!(text.lines.len(), 3);
+    assert_eq!(
+        text.lines[1],
+        Line::from(vec![
+            Span::styled("> ", styles.prefix),
+            Span::styled("+-- ", styles.lines),
+            Span::styled("Child 1", styles.label),
+        ])
+    );
+}
+
+#[test]
+fn test_to_text_with_alternate_rows() {
+    let tree = StringTreeNode::with_children(
+        "root".to_string(),
+        vec![
+            "Child 1".to_string(),
+            "Child 2".to_string(),
+            "Child 3".to_string(),
+        ]
+        .into_iter(),
+    );
+    let styles = TextStyles {
+        alternate_rows: Style::default().bg(Color::DarkGray),
+        ..Default::default()
+    };
+
+    let text = tree
+        .to_text_with_styles(&TreeFormatting::default(), &styles)
+        .unwrap();
+    let shaded: Vec<bool> = text
+        .lines
+        .iter()
+        .map(|line| line.style == styles.alternate_rows)
+        .collect();
+    assert_eq!(shaded, vec![false, true, false, true]);
+
+    let format = TreeFormatting {
+        root_connector: Some('|'),
+        ..TreeFormatting::default()
+    };
+    let text = tree.to_text_with_styles(&format, &styles).unwrap();
+    assert_eq!(text.lines[1].to_string(), "|");
+    assert_eq!(text.lines[1].style, styles.alternate_rows);
+    assert_eq!(text.lines[2].style, Style::default());
+}
+
+#[test]
+fn test_to_text_clipped_and_transformed() {
+    let tree = make_tree();
+    let styles = TextStyles {
+        lines: Style::default().fg(Color::Blue),
+        label: Style::default().fg(Color::White),
+        ..Default::default()
+    };
+    let format = TreeFormatting {
+        max_line_width: Some(8),
+        line_transforms: vec![LineTransform::new(|line| line.replace("+--", "+=="))],
+        ..TreeFormatting::default()
+    };
+
+    let text = tree.to_text_with_styles(&format, &styles).unwrap();
+    let lines: Vec<String> = text.lines.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        lines,
+        tree.lines_with_format(&format).collect::<Vec<String>>()
+    );
+    assert_eq!(
+        text.lines[2],
+        Line::from(vec![
+            Span::styled("'-- ", styles.lines),
+            Span::styled("Chi…", styles.label),
+        ])
+    );
+    assert_eq!(
+        text.lines[1],
+        Line::from(vec![Span::styled("+== Chi…", styles.lines)])
+    );
+}
+
+#[test]
+fn test_to_text_exceeds_limits() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        max_nodes: Some(2),
+        ..TreeFormatting::default()
+    };
+    assert!(tree.to_text(&format).is_err());
+
+    let format = TreeFormatting {
+        orientation: TreeOrientation::Classic,
+        ..format
+    };
+    assert!(tree.to_text(&format).is_err());
+}
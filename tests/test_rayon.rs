@@ -0,0 +1,148 @@
+#![cfg(feature = "rayon")]
+
+use pretty_assertions::assert_eq;
+use std::io;
+use text_trees::*;
+
+fn make_tree(breadth: usize, depth: usize) -> StringTreeNode {
+    if depth == 0 {
+        StringTreeNode::new(format!("leaf {}", breadth))
+    } else {
+        StringTreeNode::with_child_nodes(
+            format!("node {}/{}", depth, breadth),
+            (0..breadth).map(|index| make_tree(breadth - (index % 2), depth - 1)),
+        )
+    }
+}
+
+#[test]
+fn test_parallel_matches_sequential() {
+    let tree = make_tree(4, 4);
+    for format in [
+        TreeFormatting::dir_tree(FormatCharacters::ascii()),
+        TreeFormatting::dir_tree_with_prefix(FormatCharacters::box_chars(), "> ".to_string()),
+        TreeFormatting::dir_tree_left(FormatCharacters::box_chars()),
+        TreeFormatting {
+            align_depths: true,
+            ..Default::default()
+        },
+        TreeFormatting {
+            orientation: TreeOrientation::Classic,
+            ..Default::default()
+        },
+        TreeFormatting {
+            collapse_repeated_leaves: true,
+            ..Default::default()
+        },
+    ] {
+        let expected = tree.to_string_with_format(&format).unwrap();
+        assert_eq!(
+            tree.to_string_with_format_parallel(&format).unwrap(),
+            expected
+        );
+
+        let mut buffer: Vec<u8> = Vec::new();
+        tree.write_with_format_parallel(&mut buffer, &format)
+            .unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_parallel_single_node() {
+    let tree = StringTreeNode::new("root".to_string());
+    assert_eq!(
+        tree.to_string_with_format_parallel(&Default::default())
+            .unwrap(),
+        "root\n".to_string()
+    );
+}
+
+#[test]
+fn test_parallel_exceeds_limits() {
+    let tree = StringTreeNode::with_children(
+        "root".to_string(),
+        vec!["a".to_string(), "b".to_string()].into_iter(),
+    );
+    let format = TreeFormatting {
+        max_nodes: Some(2),
+        ..Default::default()
+    };
+    assert!(tree.to_string_with_format_parallel(&format).is_err());
+    assert!(tree
+        .write_with_format_parallel(&mut Vec::new(), &format)
+        .is_err());
+}
+
+fn expand(seed: (usize, usize)) -> (String, Vec<(usize, usize)>) {
+    let (breadth, depth) = seed;
+    if depth == 0 {
+        (format!("leaf {}", breadth), Vec::new())
+    } else {
+        (
+            format!("node {}/{}", depth, breadth),
+            (0..breadth)
+                .map(|index| (breadth - (index % 2), depth - 1))
+                .collect(),
+        )
+    }
+}
+
+#[test]
+fn test_build_parallel_matches_unfold() {
+    let expected = make_tree(4, 4);
+    assert_eq!(StringTreeNode::unfold((4, 4), expand), expected);
+    assert_eq!(StringTreeNode::build_parallel((4, 4), expand), expected);
+}
+
+#[test]
+fn test_parallel_max_label_widths() {
+    let tree = StringTreeNode::with_child_nodes(
+        "a very long root label".to_string(),
+        vec![make_tree(3, 2), make_tree(2, 1)].into_iter(),
+    );
+    let format = TreeFormatting {
+        max_label_widths: vec![6],
+        ..Default::default()
+    };
+
+    let expected = tree.to_string_with_format(&format).unwrap();
+    assert!(expected.starts_with("a ver…\n"));
+    assert_eq!(
+        tree.to_string_with_format_parallel(&format).unwrap(),
+        expected
+    );
+}
+
+#[derive(Debug, Default)]
+struct FlushRecorder {
+    data: Vec<u8>,
+    flushed_at: Vec<usize>,
+}
+
+impl io::Write for FlushRecorder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushed_at.push(self.data.len());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_parallel_write_is_flushed() {
+    let tree = make_tree(3, 3);
+    let format = TreeFormatting::default();
+
+    let mut writer = FlushRecorder::default();
+    tree.write_with_format_parallel(&mut writer, &format)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(writer.data.clone()).unwrap(),
+        tree.to_string_with_format(&format).unwrap()
+    );
+    assert_eq!(writer.flushed_at, vec![writer.data.len()]);
+}
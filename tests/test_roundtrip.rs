@@ -0,0 +1,110 @@
+use pretty_assertions::assert_eq;
+use std::io::ErrorKind;
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            "+-- looks like a connector".into(),
+            StringTreeNode::with_child_nodes(
+                "|   looks like continuation".to_string(),
+                vec![
+                    StringTreeNode::with_children(
+                        "back\\slash".to_string(),
+                        vec!["".to_string(), "  spaced  ".to_string()].into_iter(),
+                    ),
+                    "multi\nline\r\n\ttabbed".into(),
+                ]
+                .into_iter(),
+            ),
+            "Aunt".into(),
+        ]
+        .into_iter(),
+    )
+}
+
+#[test]
+fn test_to_string() {
+    let result = roundtrip::to_string(&make_tree());
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
++-- +-- looks like a connector
++-- |   looks like continuation
+|   +-- back\\slash
+|   |   +-- 
+|   |   '--   spaced  
+|   '-- multi\nline\r\n\ttabbed
+'-- Aunt
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_roundtrip() {
+    let tree = make_tree();
+    assert_eq!(
+        roundtrip::parse(&roundtrip::to_string(&tree)).unwrap(),
+        tree
+    );
+
+    let mut buffer: Vec<u8> = Vec::new();
+    roundtrip::write(&tree, &mut buffer).unwrap();
+    let parsed = roundtrip::parse(&String::from_utf8(buffer).unwrap()).unwrap();
+    assert_eq!(parsed, tree);
+
+    let leaf = StringTreeNode::new(String::new());
+    assert_eq!(
+        roundtrip::parse(&roundtrip::to_string(&leaf)).unwrap(),
+        leaf
+    );
+}
+
+#[test]
+fn test_roundtrip_display_labels() {
+    let tree = TreeNode::with_children(1, vec![2, 3].into_iter());
+    let parsed = roundtrip::parse(&roundtrip::to_string(&tree)).unwrap();
+    assert_eq!(parsed.to_string(), tree.to_string());
+}
+
+#[test]
+fn test_roundtrip_hidden_and_flattened() {
+    let mut tree = make_tree();
+    tree.children_vec_mut()[0].set_hidden(true);
+    tree.children_vec_mut()[1].set_visibility(Visibility::Flattened);
+
+    let parsed = roundtrip::parse(&roundtrip::to_string(&tree)).unwrap();
+    let labels: Vec<String> = parsed.children().map(|child| child.label()).collect();
+    assert_eq!(
+        labels,
+        vec![
+            "back\\slash".to_string(),
+            "multi\nline\r\n\ttabbed".to_string(),
+            "Aunt".to_string()
+        ]
+    );
+    assert_eq!(parsed.descendant_count(), 5);
+}
+
+#[test]
+fn test_parse_errors() {
+    for (text, message) in [
+        ("", "no root node"),
+        ("root\nchild\n", "line 2: expected a connector"),
+        (
+            "root\n+-- a\n|   |   '-- b\n",
+            "line 3: node is more than one level below its parent",
+        ),
+        (
+            "root\n'-- bad \\x\n",
+            "line 2: invalid escape sequence in label",
+        ),
+    ] {
+        let error = roundtrip::parse(text).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), message);
+    }
+}
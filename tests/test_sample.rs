@@ -0,0 +1,87 @@
+use pretty_assertions::assert_eq;
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        (1..=3).map(|i| {
+            StringTreeNode::with_children(
+                format!("Parent {}", i),
+                (1..=10).map(|j| format!("Child {}.{}", i, j)),
+            )
+        }),
+    )
+}
+
+#[test]
+fn test_sample_within_budget() {
+    let tree = make_tree();
+    assert_eq!(tree.sample(100), tree);
+    assert_eq!(tree.sample(34), tree);
+}
+
+#[test]
+fn test_sample() {
+    let tree = make_tree();
+
+    let result = tree.sample(14).to_string();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
++-- Parent 1
+|   +-- Child 1.1
+|   +-- Child 1.2
+|   +-- … 7 omitted
+|   '-- Child 1.10
++-- Parent 2
+|   +-- Child 2.1
+|   +-- … 8 omitted
+|   '-- Child 2.10
+'-- Parent 3
+    +-- Child 3.1
+    +-- … 8 omitted
+    '-- Child 3.10
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_sample_with() {
+    let tree = make_tree();
+
+    let sample = tree.sample_with(3, |omitted| format!("({} more)", omitted));
+    let labels: Vec<String> = sample.children().map(|child| child.label()).collect();
+    assert_eq!(
+        labels,
+        vec!["Parent 1".to_string(), "(22 more)".to_string()]
+    );
+    assert_eq!(
+        sample.children().next().unwrap().to_string(),
+        "Parent 1\n'-- (10 more)\n".to_string()
+    );
+
+    let sample = tree.sample_with(0, |omitted| format!("({} more)", omitted));
+    assert_eq!(sample.to_string(), "root\n'-- (33 more)\n".to_string());
+}
+
+#[test]
+fn test_sample_more_parents_than_budget() {
+    let tree = make_tree();
+
+    let result = tree.sample(6).to_string();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
++-- Parent 1
+|   '-- … 10 omitted
++-- Parent 2
+|   '-- … 10 omitted
+'-- Parent 3
+    '-- … 10 omitted
+"#
+        .to_string()
+    );
+}
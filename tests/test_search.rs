@@ -0,0 +1,260 @@
+use std::ops::ControlFlow;
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            "Uncle".into(),
+            StringTreeNode::with_child_nodes(
+                "Parent".to_string(),
+                vec![
+                    StringTreeNode::with_children(
+                        "Child 1".to_string(),
+                        vec!["Grand Child 1".to_string()].into_iter(),
+                    ),
+                    "Child 2".into(),
+                ]
+                .into_iter(),
+            ),
+            "Aunt".into(),
+        ]
+        .into_iter(),
+    )
+}
+
+#[test]
+fn test_find() {
+    let tree = make_tree();
+
+    let (path, node) = tree.find(|label| label.starts_with("Child")).unwrap();
+    assert_eq!(path, vec![1, 0]);
+    assert_eq!(node.data(), "Child 1");
+
+    let (path, node) = tree.find(|label| label == "root").unwrap();
+    assert!(path.is_empty());
+    assert_eq!(node.data(), "root");
+
+    assert!(tree.find(|label| label == "Cousin").is_none());
+}
+
+#[test]
+fn test_find_all() {
+    let tree = make_tree();
+
+    let result: Vec<(Vec<usize>, String)> = tree
+        .find_all(|label| label.contains("Child"))
+        .into_iter()
+        .map(|(path, node)| (path, node.label()))
+        .collect();
+    assert_eq!(
+        result,
+        vec![
+            (vec![1, 0], "Child 1".to_string()),
+            (vec![1, 0, 0], "Grand Child 1".to_string()),
+            (vec![1, 1], "Child 2".to_string()),
+        ]
+    );
+
+    assert!(tree.find_all(|label| label.is_empty()).is_empty());
+}
+
+#[test]
+fn test_find_iter() {
+    let tree = make_tree();
+
+    let mut matches = tree.find_iter(|label| label.len() == 4);
+    assert_eq!(matches.next().map(|(path, _)| path), Some(vec![]));
+    assert_eq!(matches.next().map(|(path, _)| path), Some(vec![2]));
+    assert!(matches.next().is_none());
+}
+
+#[test]
+fn test_stats() {
+    let stats = make_tree().stats();
+    assert_eq!(
+        stats,
+        TreeStats {
+            nodes: 7,
+            leaves: 4,
+            height: 3,
+            widest_level: 3,
+            max_children: 3,
+            average_branching: 2.0,
+        }
+    );
+
+    let stats = StringTreeNode::new("root".to_string()).stats();
+    assert_eq!(stats.nodes, 1);
+    assert_eq!(stats.leaves, 1);
+    assert_eq!(stats.height, 0);
+    assert_eq!(stats.widest_level, 1);
+    assert_eq!(stats.average_branching, 0.0);
+}
+
+#[test]
+fn test_iter_with_paths() {
+    let tree = make_tree();
+
+    let result: Vec<(Vec<usize>, String)> = tree
+        .iter_with_paths()
+        .map(|(path, node)| (path, node.label()))
+        .collect();
+    assert_eq!(
+        result,
+        vec![
+            (vec![], "root".to_string()),
+            (vec![0], "Uncle".to_string()),
+            (vec![1], "Parent".to_string()),
+            (vec![1, 0], "Child 1".to_string()),
+            (vec![1, 0, 0], "Grand Child 1".to_string()),
+            (vec![1, 1], "Child 2".to_string()),
+            (vec![2], "Aunt".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_iter_with_depth() {
+    let tree = make_tree();
+
+    let result: Vec<(usize, &str)> = tree
+        .iter_with_depth()
+        .map(|(depth, label)| (depth, label.as_str()))
+        .collect();
+    assert_eq!(
+        result,
+        vec![
+            (0, "root"),
+            (1, "Uncle"),
+            (1, "Parent"),
+            (2, "Child 1"),
+            (3, "Grand Child 1"),
+            (2, "Child 2"),
+            (1, "Aunt"),
+        ]
+    );
+}
+
+#[test]
+fn test_levels() {
+    let tree = make_tree();
+
+    let result: Vec<Vec<String>> = tree
+        .levels()
+        .into_iter()
+        .map(|level| level.into_iter().map(|node| node.label()).collect())
+        .collect();
+    assert_eq!(
+        result,
+        vec![
+            vec!["root".to_string()],
+            vec![
+                "Uncle".to_string(),
+                "Parent".to_string(),
+                "Aunt".to_string()
+            ],
+            vec!["Child 1".to_string(), "Child 2".to_string()],
+            vec!["Grand Child 1".to_string()],
+        ]
+    );
+}
+
+#[test]
+fn test_deepest_leaf() {
+    let mut tree = make_tree();
+
+    let (path, node) = tree.deepest_leaf();
+    assert_eq!(path, vec![1, 0, 0]);
+    assert_eq!(node.data(), "Grand Child 1");
+    assert_eq!(tree.longest_paths(), vec![vec![1, 0, 0]]);
+
+    tree.push_node(StringTreeNode::with_child_nodes(
+        "Cousin".to_string(),
+        vec![StringTreeNode::with_children(
+            "Child 3".to_string(),
+            vec!["Grand Child 2".to_string()].into_iter(),
+        )]
+        .into_iter(),
+    ));
+    assert_eq!(tree.deepest_leaf().0, vec![1, 0, 0]);
+    assert_eq!(tree.longest_paths(), vec![vec![1, 0, 0], vec![3, 0, 0]]);
+
+    let leaf = StringTreeNode::new("leaf".to_string());
+    assert!(leaf.deepest_leaf().0.is_empty());
+    assert_eq!(leaf.longest_paths(), vec![Vec::<usize>::new()]);
+}
+
+#[test]
+fn test_lca() {
+    let tree = make_tree();
+
+    assert_eq!(tree.lca(&[1, 0, 0], &[1, 1]), vec![1]);
+    assert_eq!(tree.lca(&[1, 0, 0], &[1, 0]), vec![1, 0]);
+    assert_eq!(tree.lca(&[0], &[2]), Vec::<usize>::new());
+    assert_eq!(tree.lca(&[1, 0, 0], &[1, 0, 0]), vec![1, 0, 0]);
+    assert_eq!(tree.lca(&[1, 5, 0], &[1, 5, 1]), vec![1]);
+
+    let (path, node) = tree.lca_node(&[1, 0, 0], &[1, 1]);
+    assert_eq!(path, vec![1]);
+    assert_eq!(node.data(), "Parent");
+}
+
+#[test]
+fn test_subtree_at() {
+    let mut tree = make_tree();
+
+    assert_eq!(tree.subtree_at(&[]).unwrap().data(), "root");
+    assert_eq!(tree.subtree_at(&[1, 0, 0]).unwrap().data(), "Grand Child 1");
+    assert!(tree.subtree_at(&[1, 2]).is_none());
+    assert!(tree.subtree_at(&[0, 0]).is_none());
+
+    let (path, _) = tree.find(|label| label == "Child 2").unwrap();
+    tree.subtree_at_mut(&path)
+        .unwrap()
+        .push("Grand Child 2".to_string());
+    assert_eq!(tree.subtree_at(&[1, 1, 0]).unwrap().data(), "Grand Child 2");
+    assert!(tree.subtree_at_mut(&[3]).is_none());
+}
+
+#[test]
+fn test_try_visit() {
+    let tree = make_tree();
+
+    let mut visited: Vec<(usize, String)> = Vec::new();
+    let result: Option<()> = tree.try_visit(|node, depth| {
+        visited.push((depth, node.data().clone()));
+        if node.data() == "Child 1" {
+            ControlFlow::Continue(Visit::SkipChildren)
+        } else {
+            ControlFlow::Continue(Visit::Children)
+        }
+    });
+    assert_eq!(result, None);
+    assert_eq!(
+        visited
+            .iter()
+            .map(|(depth, label)| format!("{} {}", depth, label))
+            .collect::<Vec<String>>(),
+        vec![
+            "0 root",
+            "1 Uncle",
+            "1 Parent",
+            "2 Child 1",
+            "2 Child 2",
+            "1 Aunt"
+        ]
+    );
+
+    let mut count = 0;
+    let result = tree.try_visit(|node, _| {
+        count += 1;
+        if node.data().starts_with("Child") {
+            ControlFlow::Break(node.data().clone())
+        } else {
+            ControlFlow::Continue(Visit::Children)
+        }
+    });
+    assert_eq!(result, Some("Child 1".to_string()));
+    assert_eq!(count, 4);
+}
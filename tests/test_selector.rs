@@ -0,0 +1,84 @@
+use pretty_assertions::assert_eq;
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            "Uncle".into(),
+            StringTreeNode::with_child_nodes(
+                "Parent".to_string(),
+                vec![
+                    StringTreeNode::with_children(
+                        "Child 1".to_string(),
+                        vec!["leaf".to_string()].into_iter(),
+                    ),
+                    "Child 2".into(),
+                    "Sibling".into(),
+                ]
+                .into_iter(),
+            ),
+            StringTreeNode::with_children("Aunt".to_string(), vec!["leaf".to_string()].into_iter()),
+        ]
+        .into_iter(),
+    )
+}
+
+#[test]
+fn test_select() {
+    let tree = make_tree();
+
+    let paths: Vec<Vec<usize>> = tree
+        .select(&Selector::new("root/*/Child*"))
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    assert_eq!(paths, vec![vec![1, 0], vec![1, 1]]);
+
+    let paths: Vec<Vec<usize>> = tree
+        .select(&"**/leaf".into())
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect();
+    assert_eq!(paths, vec![vec![1, 0, 0], vec![2, 0]]);
+
+    assert!(tree.select(&Selector::new("other/**")).is_empty());
+}
+
+#[test]
+fn test_write_with_selector() {
+    let tree = make_tree();
+
+    let result =
+        tree.to_string_with_selector(&TreeFormatting::default(), &Selector::new("**/leaf"));
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
++-- Parent
+|   '-- Child 1
+|       '-- leaf
+'-- Aunt
+    '-- leaf
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_write_with_selector_nothing_selected() {
+    let tree = make_tree();
+    let mut buffer: Vec<u8> = Vec::new();
+
+    let result = tree.write_with_selector(
+        &mut buffer,
+        &TreeFormatting::default(),
+        &Selector::new("root/Cousin"),
+    );
+    assert!(result.is_ok());
+    let result = String::from_utf8(buffer).unwrap();
+    println!("{}", result);
+    assert_eq!(result, "root\n".to_string());
+}
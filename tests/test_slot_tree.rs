@@ -0,0 +1,73 @@
+use pretty_assertions::assert_eq;
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    let mut parent = StringTreeNode::with_children(
+        "Parent".to_string(),
+        vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+    );
+    parent.set_collapsed(true);
+    let mut hidden = StringTreeNode::new("Hidden".to_string());
+    hidden.set_hidden(true);
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![parent, hidden, "Uncle".into()].into_iter(),
+    )
+}
+
+#[test]
+fn test_round_trip() {
+    let tree = make_tree();
+    let slots = SlotTree::from(tree.clone());
+    assert_eq!(slots.node_count(), 6);
+    assert_eq!(StringTreeNode::from(slots), tree);
+}
+
+#[test]
+fn test_handles_survive_removal() {
+    let mut slots = SlotTree::from(make_tree());
+    let root = slots.root();
+    let parent = slots.children(root)[0];
+    let uncle = slots.children(root)[2];
+    let child = slots.children(parent)[1];
+
+    let removed = slots.remove(parent).unwrap();
+    assert_eq!(removed.descendant_count(), 2);
+    assert_eq!(slots.node_count(), 3);
+    assert!(!slots.contains(parent));
+    assert!(!slots.contains(child));
+    assert_eq!(slots.get(uncle), Some(&"Uncle".to_string()));
+    assert_eq!(slots.parent(uncle), Some(root));
+
+    // The new nodes reuse the removed slots, but not the removed handles.
+    let aunt = slots.insert(root, 0, "Aunt".to_string()).unwrap();
+    let cousin = slots.push(aunt, "Cousin".to_string()).unwrap();
+    assert_ne!(aunt, parent);
+    assert_ne!(cousin, child);
+    assert_eq!(slots.get(child), None);
+    assert_eq!(slots.push(parent, "Orphan".to_string()), None);
+    assert_eq!(
+        slots.children(root),
+        &[aunt, slots.children(root)[1], uncle]
+    );
+
+    let result = StringTreeNode::from(slots).to_string();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
++-- Aunt
+|   '-- Cousin
+'-- Uncle
+"#
+    );
+}
+
+#[test]
+fn test_root_cannot_be_removed() {
+    let mut slots = SlotTree::new("root".to_string());
+    let root = slots.root();
+    assert!(slots.remove(root).is_none());
+    assert_eq!(slots.insert(root, 1, "a".to_string()), None);
+    assert_eq!(slots.node_count(), 1);
+}
@@ -0,0 +1,70 @@
+use pretty_assertions::assert_eq;
+use text_trees::*;
+
+fn make_tree(labels: &[&str]) -> StringTreeNode {
+    StringTreeNode::with_children(
+        "root".to_string(),
+        labels.iter().map(|label| label.to_string()),
+    )
+}
+
+fn child_labels(tree: &StringTreeNode) -> Vec<String> {
+    tree.children().map(|child| child.label()).collect()
+}
+
+#[test]
+fn test_natural_order() {
+    let mut tree = make_tree(&[
+        "file10", "file2", "file02", "file1", "File3", "file", "a10b2",
+    ]);
+    tree.sort_children_by(natural_order);
+    assert_eq!(
+        child_labels(&tree),
+        vec!["File3", "a10b2", "file", "file1", "file2", "file02", "file10"]
+    );
+}
+
+#[test]
+fn test_case_insensitive_order() {
+    let mut tree = make_tree(&["banana", "Cherry", "apple", "Apple"]);
+    tree.sort_children_by(case_insensitive_order);
+    assert_eq!(
+        child_labels(&tree),
+        vec!["Apple", "apple", "banana", "Cherry"]
+    );
+}
+
+#[test]
+fn test_by_value_descending() {
+    let mut tree = TreeNode::with_children(0.0, vec![2.0, f64::NAN, 30.0, 4.0].into_iter());
+    tree.sort_children_by(by_value_descending(|n: &f64| *n));
+    assert_eq!(tree.to_string(), "0\n+-- 30\n+-- 4\n+-- 2\n'-- NaN\n");
+}
+
+#[test]
+fn test_sort_tree_by() {
+    let mut tree = StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            StringTreeNode::with_children(
+                "dir10".to_string(),
+                vec!["b".to_string(), "a".to_string()].into_iter(),
+            ),
+            "dir9".into(),
+        ]
+        .into_iter(),
+    );
+
+    let mut unsorted = tree.clone();
+    unsorted.sort_children_by(natural_order);
+    assert_eq!(
+        unsorted.to_string(),
+        "root\n+-- dir9\n'-- dir10\n    +-- b\n    '-- a\n"
+    );
+
+    tree.sort_tree_by(natural_order);
+    assert_eq!(
+        tree.to_string(),
+        "root\n+-- dir9\n'-- dir10\n    +-- a\n    '-- b\n"
+    );
+}
@@ -0,0 +1,157 @@
+use text_trees::{
+    ColumnAlignment, ColumnFormat, FormatCharacters, StringTreeNode, TreeFormatting,
+    TreeOrientation, TreeTable,
+};
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            "Uncle".into(),
+            StringTreeNode::with_children(
+                "Parent".to_string(),
+                vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+            ),
+        ]
+        .into_iter(),
+    )
+}
+
+fn make_table<'a>() -> TreeTable<'a, String> {
+    TreeTable::new("Name")
+        .with_column(("Length", |label: &String| label.len().to_string()))
+        .with_column(("Upper", |label: &String| label.to_uppercase()))
+}
+
+#[test]
+fn test_table() {
+    let tree = make_tree();
+
+    let result = tree.to_string_with_table(&TreeFormatting::default(), &make_table());
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"Name             Length  Upper
+root             4       ROOT
++-- Uncle        5       UNCLE
+'-- Parent       6       PARENT
+    +-- Child 1  7       CHILD 1
+    '-- Child 2  7       CHILD 2
+"#
+    );
+}
+
+#[test]
+fn test_table_with_rule() {
+    let tree = make_tree();
+    let mut table = make_table();
+    table.rule = Some('─');
+    table.separator = " │ ".to_string();
+
+    let result = tree.to_string_with_table(
+        &TreeFormatting::dir_tree(FormatCharacters::box_chars()),
+        &table,
+    );
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"Name            │ Length │ Upper
+─────────────── │ ────── │ ───────
+root            │ 4      │ ROOT
+├── Uncle       │ 5      │ UNCLE
+└── Parent      │ 6      │ PARENT
+    ├── Child 1 │ 7      │ CHILD 1
+    └── Child 2 │ 7      │ CHILD 2
+"#
+    );
+}
+
+#[test]
+fn test_table_wide_characters() {
+    let tree = StringTreeNode::with_children(
+        "根".to_string(),
+        vec!["葉っぱ".to_string(), "leaf".to_string()].into_iter(),
+    );
+    let table = TreeTable::new("Name")
+        .with_column(("Chars", |label: &String| label.chars().count().to_string()));
+
+    let result = tree.to_string_with_table(&TreeFormatting::default(), &table);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"Name        Chars
+根          1
++-- 葉っぱ  3
+'-- leaf    4
+"#
+    );
+}
+
+#[test]
+fn test_table_classic() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        orientation: TreeOrientation::Classic,
+        ..Default::default()
+    };
+
+    let result = tree.to_string_with_table(&format, &make_table());
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"               root  4  ROOT
+              /              \
+Uncle  5  UNCLE             Parent  6  PARENT
+                               /          \
+                 Child 1  7  CHILD 1  Child 2  7  CHILD 2
+"#
+    );
+}
+
+#[test]
+fn test_table_headers() {
+    assert_eq!(make_table().headers(), vec!["Length", "Upper"]);
+}
+
+#[test]
+fn test_table_column_formats() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        column_formats: vec![
+            ColumnFormat {
+                alignment: ColumnAlignment::Right,
+                min_width: Some(8),
+                ..Default::default()
+            },
+            ColumnFormat {
+                alignment: ColumnAlignment::Center,
+                max_width: Some(5),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let result = tree.to_string_with_table(&format, &make_table());
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"Name               Length  Upper
+root                    4  ROOT
++-- Uncle               5  UNCLE
+'-- Parent              6  PARE…
+    +-- Child 1         7  CHIL…
+    '-- Child 2         7  CHIL…
+"#
+    );
+}
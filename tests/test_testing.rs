@@ -0,0 +1,141 @@
+use pretty_assertions::assert_eq;
+use text_trees::testing::structural_diff;
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            "Uncle".into(),
+            StringTreeNode::with_children(
+                "Parent".to_string(),
+                vec!["Child 1".to_string(), "Child 2".to_string()].into_iter(),
+            ),
+        ]
+        .into_iter(),
+    )
+}
+
+fn make_other_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            "Aunt".into(),
+            StringTreeNode::with_children(
+                "Parent".to_string(),
+                vec!["Child 1".to_string()].into_iter(),
+            ),
+            "Cousin".into(),
+        ]
+        .into_iter(),
+    )
+}
+
+#[test]
+fn test_structural_diff() {
+    assert!(structural_diff(&make_tree(), &make_tree()).is_empty());
+    assert_eq!(
+        structural_diff(&make_tree(), &make_other_tree()),
+        vec![
+            r#"at [0]: "Uncle" != "Aunt""#.to_string(),
+            r#"at [1, 1]: only in actual "Child 2""#.to_string(),
+            r#"at [2]: only in expected "Cousin""#.to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_structural_diff_collapsed_and_visibility() {
+    let mut other = make_tree();
+    other.children_vec_mut()[0].set_hidden(true);
+    other.children_vec_mut()[1].set_collapsed(true);
+    assert_eq!(
+        structural_diff(&make_tree(), &other),
+        vec![
+            "at [0]: visibility Visible != Hidden".to_string(),
+            "at [1]: collapsed false != true".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_assert_tree_eq() {
+    assert_tree_eq!(make_tree(), make_tree());
+    assert_tree_eq!(make_tree(), make_tree(), "trees for {}", "test");
+}
+
+#[test]
+#[should_panic(expected = r#"at [2]: only in expected "Cousin""#)]
+fn test_assert_tree_eq_fails() {
+    assert_tree_eq!(make_tree(), make_other_tree());
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `(actual == expected)`: trees for test")]
+fn test_assert_tree_eq_fails_with_message() {
+    assert_tree_eq!(make_tree(), make_other_tree(), "trees for {}", "test");
+}
+
+#[test]
+fn test_normalize() {
+    assert_eq!(
+        testing::normalize("\nroot  \n'-- Child\t\n\n\n"),
+        "root\n'-- Child\n".to_string()
+    );
+    assert_eq!(testing::normalize("root"), "root\n".to_string());
+    assert_eq!(testing::normalize("\n\n"), String::new());
+}
+
+#[test]
+fn test_assert_renders_to() {
+    assert_renders_to!(
+        make_tree(),
+        TreeFormatting::default(),
+        r#"
+root
++-- Uncle
+'-- Parent
+    +-- Child 1
+    '-- Child 2
+"#
+    );
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `(actual == expected)`: for Uncle")]
+fn test_assert_renders_to_fails() {
+    assert_renders_to!(
+        make_tree(),
+        TreeFormatting::default(),
+        "root",
+        "for {}",
+        "Uncle"
+    );
+}
+
+#[test]
+fn test_assert_renders_to_snapshot() {
+    let path = std::env::temp_dir()
+        .join(format!("text_trees_snapshot_{}", std::process::id()))
+        .join("tree.txt");
+    let _ = std::fs::remove_file(&path);
+
+    let result = std::panic::catch_unwind(|| {
+        assert_renders_to_snapshot!(make_tree(), TreeFormatting::default(), &path)
+    });
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("does not exist, set TEXT_TREES_UPDATE_SNAPSHOTS=1 to create it"));
+    assert!(message.ends_with(&make_tree().to_string()));
+    assert!(!path.exists());
+
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, make_tree().to_string()).unwrap();
+    assert_renders_to_snapshot!(make_tree(), TreeFormatting::default(), &path);
+
+    let result = std::panic::catch_unwind(|| {
+        assert_renders_to_snapshot!(make_other_tree(), TreeFormatting::default(), &path)
+    });
+    assert!(result.is_err());
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
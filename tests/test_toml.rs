@@ -0,0 +1,51 @@
+#![cfg(feature = "toml")]
+
+use text_trees::*;
+
+#[test]
+fn test_toml_document_with_type_badges() {
+    let document: toml::Value = toml::from_str(
+        r#"
+[package]
+name = "text_trees"
+edition = "2018"
+
+[dependencies]
+unicode-width = "0.2"
+
+[[bin]]
+name = "ttree"
+"#,
+    )
+    .unwrap();
+    let options = ValueTreeOptions {
+        root_label: "Cargo.toml".to_string(),
+        type_badges: true,
+        ..Default::default()
+    };
+    let mut buffer: Vec<u8> = Vec::new();
+
+    let result = write_toml_value(
+        &document,
+        &mut buffer,
+        &TreeFormatting::dir_tree(FormatCharacters::box_chars()),
+        &options,
+    );
+    assert!(result.is_ok());
+    let result = String::from_utf8(buffer).unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"Cargo.toml                   (table)
+├── bin                      (array)
+│   └── [0]                  (table)
+│       └── name: "ttree"    (string)
+├── dependencies             (table)
+│   └── unicode-width: "0.2" (string)
+└── package                  (table)
+    ├── edition: "2018"      (string)
+    └── name: "text_trees"   (string)
+"#
+        .to_string()
+    );
+}
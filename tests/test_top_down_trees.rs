@@ -171,6 +171,99 @@ fn test_box_char_side_tree() {
     );
 }
 
+#[test]
+fn test_continuation_indent() {
+    let tree = make_tree();
+    let mut chars = FormatCharacters::ascii();
+    chars.horizontal_line_count = 4;
+    chars.label_space_count = 0;
+    let format = TreeFormatting {
+        continuation_indent: Some(3),
+        ..TreeFormatting::dir_tree(chars)
+    };
+
+    let result = tree.to_string_with_format(&format);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
++----Uncle
++----Parent
+|  +----Child 1
+|  |  '----Grand Child 1
+|  '----Child 2
+|     '----Grand Child 2
+|        '----Great Grand Child 2
+|           '----Great Great Grand Child 2
+'----Aunt
+   '----Child 3
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_label_guide_offset() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        label_guide_offset: Some(2),
+        ..Default::default()
+    };
+
+    let result = tree.to_string_with_format(&format);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
+  +-- Uncle
+  +-- Parent
+  |     +-- Child 1
+  |     |     '-- Grand Child 1
+  |     '-- Child 2
+  |           '-- Grand Child 2
+  |                 '-- Great Grand Child 2
+  |                       '-- Great Great Grand Child 2
+  '-- Aunt
+        '-- Child 3
+"#
+        .to_string()
+    );
+
+    let mut chars = FormatCharacters::box_chars();
+    chars.label_space_count = 2;
+    let format = TreeFormatting {
+        label_guide_offset: Some(0),
+        root_connector: Some('│'),
+        ..TreeFormatting::dir_tree(chars)
+    };
+
+    let result = tree.to_string_with_format(&format);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
+│
+├──  Uncle
+├──  Parent
+│    ├──  Child 1
+│    │    └──  Grand Child 1
+│    └──  Child 2
+│         └──  Grand Child 2
+│              └──  Great Grand Child 2
+│                   └──  Great Great Grand Child 2
+└──  Aunt
+     └──  Child 3
+"#
+        .to_string()
+    );
+}
+
 #[test]
 fn test_spacing_in_tree() {
     let tree = make_tree();
@@ -190,7 +283,9 @@ fn test_spacing_in_tree() {
             right_facing_angle: '└',
             label_space_char: '.',
             label_space_count: 2,
+            ..Default::default()
         },
+        ..Default::default()
     };
 
     let result = tree.to_string_with_format(&format);
@@ -214,3 +309,1164 @@ fn test_spacing_in_tree() {
         .to_string()
     );
 }
+
+#[test]
+fn test_quoted_labels() {
+    let tree = StringTreeNode::with_children(
+        "root".to_string(),
+        vec![
+            "My Documents".to_string(),
+            "notes.txt".to_string(),
+            "a/b".to_string(),
+        ]
+        .into_iter(),
+    );
+    let format = TreeFormatting {
+        label_quoting: Some(LabelQuoting::with_delimiters(QuoteStyle::Double, vec!['/'])),
+        ..Default::default()
+    };
+
+    let result = tree.to_string_with_format(&format);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
++-- "My Documents"
++-- notes.txt
+'-- "a/b"
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_align_depths_with_wide_chars() {
+    let tree = StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            "a".into(),
+            StringTreeNode::with_children(
+                "b".to_string(),
+                vec!["c".to_string(), "d".to_string()].into_iter(),
+            ),
+        ]
+        .into_iter(),
+    );
+    let mut format = TreeFormatting::dir_tree(FormatCharacters {
+        right_facing_tee: '＋',
+        ..FormatCharacters::ascii()
+    });
+    format.align_depths = true;
+
+    let result = tree.to_string_with_format(&format);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
+＋-- a
+'--- b
+    ＋-- c
+    '--- d
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_collapsed_node() {
+    let mut parent = StringTreeNode::with_child_nodes(
+        "Parent".to_string(),
+        vec![
+            StringTreeNode::with_children(
+                "Child 1".to_string(),
+                vec!["Grand Child 1".to_string()].into_iter(),
+            ),
+            "Child 2".into(),
+        ]
+        .into_iter(),
+    );
+    parent.set_collapsed(true);
+    let tree = StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec!["Uncle".into(), parent, "Aunt".into()].into_iter(),
+    );
+
+    let result =
+        tree.to_string_with_format(&TreeFormatting::dir_tree_left(FormatCharacters::ascii()));
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"+ root
++--- Uncle
++--- Parent [+3]
+'--- Aunt
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_deleted_nodes() {
+    let tree = make_tree();
+
+    let result = tree.to_string_with_deleted(&TreeFormatting::default(), |label: &String| {
+        label.ends_with("Child 2") || label == "Uncle"
+    });
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
++-- ~~Uncle~~
++-- Parent
+|   +-- Child 1
+|   |   '-- Grand Child 1
+|   '-- ~~Child 2~~
+|       '-- ~~Grand Child 2~~
+|           '-- ~~Great Grand Child 2~~
+|               '-- ~~Great Great Grand Child 2~~
+'-- Aunt
+    '-- Child 3
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_annotated_nodes() {
+    let tree = StringTreeNode::with_children(
+        "src".to_string(),
+        vec!["lib.rs".to_string(), "classic.rs".to_string()].into_iter(),
+    );
+    let mut format = TreeFormatting::dir_tree(FormatCharacters::box_chars());
+    format.annotation_separator = " : ".to_string();
+
+    let result = tree.to_string_with_annotations(&format, |label: &String| {
+        if label.ends_with(".rs") {
+            Some(format!("{} bytes", label.len() * 100))
+        } else {
+            None
+        }
+    });
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"src
+├── lib.rs : 600 bytes
+└── classic.rs : 1000 bytes
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_label_transform_before_quoting() {
+    let tree = StringTreeNode::with_children(
+        "root".to_string(),
+        vec!["a long child label".to_string(), "short".to_string()].into_iter(),
+    );
+    let format = TreeFormatting {
+        label_transform: Some(LabelTransform::new(|label| {
+            if label.chars().count() > 8 {
+                format!("{}…", label.chars().take(7).collect::<String>())
+            } else {
+                label
+            }
+        })),
+        label_quoting: Some(LabelQuoting::double_quotes()),
+        ..Default::default()
+    };
+
+    let result = tree.to_string_with_format(&format);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
++-- "a long …"
+'-- short
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_write_with_format_fmt() {
+    let tree = make_tree();
+    let format = TreeFormatting::dir_tree(FormatCharacters::box_chars());
+
+    let mut result = String::from("tree:\n");
+    assert!(tree.write_with_format_fmt(&mut result, &format).is_ok());
+    println!("{}", result);
+    assert_eq!(
+        result,
+        format!("tree:\n{}", tree.to_string_with_format(&format).unwrap())
+    );
+
+    let mut bytes: Vec<u8> = Vec::new();
+    assert!(tree.write_with_format(&mut bytes, &format).is_ok());
+    assert_eq!(String::from_utf8(bytes).unwrap(), result[6..].to_string());
+}
+
+#[test]
+fn test_display_propagates_errors() {
+    use std::fmt::Write;
+
+    struct LimitedWriter(usize);
+
+    impl Write for LimitedWriter {
+        fn write_str(&mut self, s: &str) -> std::fmt::Result {
+            if s.len() > self.0 {
+                return Err(std::fmt::Error);
+            }
+            self.0 -= s.len();
+            Ok(())
+        }
+    }
+
+    let tree = make_tree();
+    assert!(write!(LimitedWriter(20), "{}", tree).is_err());
+    assert!(write!(LimitedWriter(1024), "{}", tree).is_ok());
+}
+
+#[test]
+fn test_very_deep_tree() {
+    const DEPTH: usize = 5_000;
+    const STACK: usize = 256 * 1024;
+
+    // A small stack ensures that neither rendering, nor dropping, the tree recurses per level.
+    let handle = std::thread::Builder::new()
+        .stack_size(STACK)
+        .spawn(|| {
+            let mut tree = StringTreeNode::new(DEPTH.to_string());
+            for depth in (0..DEPTH).rev() {
+                tree = StringTreeNode::with_child_nodes(depth.to_string(), vec![tree].into_iter());
+            }
+            let format = TreeFormatting::dir_tree(FormatCharacters {
+                horizontal_line_count: 0,
+                label_space_count: 0,
+                ..FormatCharacters::ascii()
+            });
+            let result = tree.to_string_with_format(&format).unwrap();
+            assert_eq!(tree.descendant_count(), DEPTH);
+            result
+        })
+        .unwrap();
+    let result = handle.join().unwrap();
+
+    let lines: Vec<&str> = result.lines().collect();
+    assert_eq!(lines.len(), DEPTH + 1);
+    assert_eq!(lines[0], "0");
+    assert_eq!(lines[2], " '2");
+    assert_eq!(lines[DEPTH], format!("{}'{}", " ".repeat(DEPTH - 1), DEPTH));
+}
+
+#[test]
+fn test_lines_with_format() {
+    let tree = make_tree();
+    let format = TreeFormatting::dir_tree(FormatCharacters::box_chars());
+
+    let lines: Vec<String> = tree.lines_with_format(&format).take(3).collect();
+    assert_eq!(lines, vec!["root", "├── Uncle", "├── Parent"]);
+
+    let lines: Vec<String> = tree.lines_with_format(&format).collect();
+    assert_eq!(
+        lines,
+        tree.to_string_with_format(&format)
+            .unwrap()
+            .lines()
+            .collect::<Vec<&str>>()
+    );
+
+    let format = TreeFormatting {
+        orientation: TreeOrientation::Classic,
+        ..Default::default()
+    };
+    assert_eq!(
+        tree.lines_with_format(&format).count(),
+        tree.to_string_with_format(&format).unwrap().lines().count()
+    );
+}
+
+#[test]
+fn test_measure_with_format() {
+    let tree = make_tree();
+    for format in [
+        TreeFormatting::dir_tree(FormatCharacters::ascii()),
+        TreeFormatting::dir_tree_left(FormatCharacters::box_chars()),
+        TreeFormatting {
+            orientation: TreeOrientation::Classic,
+            ..Default::default()
+        },
+    ] {
+        let result = tree.to_string_with_format(&format).unwrap();
+        let metrics = tree.measure_with_format(&format).unwrap();
+        assert_eq!(metrics.lines, result.lines().count());
+        assert_eq!(metrics.bytes, result.len());
+        assert_eq!(
+            metrics.max_width,
+            result.lines().map(|l| l.chars().count()).max().unwrap()
+        );
+    }
+
+    let format = TreeFormatting {
+        max_nodes: Some(2),
+        ..Default::default()
+    };
+    assert!(tree.measure_with_format(&format).is_err());
+    assert!(tree.widest_line_with_format(&format).is_err());
+}
+
+#[test]
+fn test_widest_line_with_format() {
+    let tree = make_tree();
+    let format = TreeFormatting::dir_tree(FormatCharacters::box_chars());
+    let result = tree.to_string_with_format(&format).unwrap();
+    assert_eq!(
+        tree.widest_line_with_format(&format).unwrap(),
+        result.lines().map(|l| l.chars().count()).max().unwrap()
+    );
+
+    let wide =
+        StringTreeNode::with_children("root".to_string(), vec!["日本語".to_string()].into_iter());
+    assert_eq!(wide.widest_line_with_format(&format).unwrap(), 10);
+}
+
+#[test]
+fn test_write_with_format_stats() {
+    let mut tree = make_tree();
+    let mut parent = StringTreeNode::with_children(
+        "Collapsed".to_string(),
+        vec!["Hidden 1".to_string(), "Hidden 2".to_string()].into_iter(),
+    );
+    parent.set_collapsed(true);
+    tree.push_node(parent);
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let stats = tree
+        .write_with_format_stats(
+            &mut buffer,
+            &TreeFormatting::dir_tree(FormatCharacters::ascii()),
+        )
+        .unwrap();
+    let result = String::from_utf8(buffer).unwrap();
+    println!("{}", result);
+    assert_eq!(
+        stats,
+        RenderStats {
+            lines: 12,
+            nodes_rendered: 12,
+            nodes_elided: 2,
+            max_width: "|               '-- Great Great Grand Child 2".len(),
+        }
+    );
+    assert_eq!(stats.lines, result.lines().count());
+}
+
+#[test]
+fn test_write_to_file() {
+    let tree = make_tree();
+    let format = TreeFormatting::dir_tree(FormatCharacters::box_chars());
+    let path = std::env::temp_dir().join(format!("text_trees_write_{}.txt", std::process::id()));
+
+    tree.write_to_file(&path, &format).unwrap();
+    let result = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(result, tree.to_string_with_format(&format).unwrap());
+}
+
+#[test]
+fn test_root_connector() {
+    let format = TreeFormatting {
+        root_connector: Some('|'),
+        ..TreeFormatting::dir_tree_with_prefix(FormatCharacters::ascii(), "> ".to_string())
+    };
+    let tree = make_tree();
+    let result = tree.to_string_with_format(&format).unwrap();
+    println!("{}", result);
+    assert!(result.starts_with("> root\n> |\n> +-- "));
+    assert_eq!(result.lines().count(), tree.to_string().lines().count() + 1);
+    assert_eq!(
+        tree.lines_with_format(&format).collect::<Vec<String>>(),
+        result.lines().map(str::to_string).collect::<Vec<String>>()
+    );
+    assert_eq!(
+        tree.measure_with_format(&format).unwrap().lines,
+        result.lines().count()
+    );
+
+    let leaf = StringTreeNode::new("leaf".to_string());
+    assert_eq!(leaf.to_string_with_format(&format).unwrap(), "> leaf\n");
+
+    let left = TreeFormatting {
+        root_connector: Some('|'),
+        ..TreeFormatting::dir_tree_left(FormatCharacters::ascii())
+    };
+    assert_eq!(
+        tree.to_string_with_format(&left).unwrap(),
+        tree.to_string_with_format(&TreeFormatting::dir_tree_left(FormatCharacters::ascii()))
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_branch_connectors() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        branch_connectors: Some(BranchConnectors::box_chars()),
+        ..TreeFormatting::dir_tree(FormatCharacters::box_chars())
+    };
+
+    let result = tree.to_string_with_format(&format);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
+├── Uncle
+┝── Parent
+│   ┝── Child 1
+│   │   └── Grand Child 1
+│   ┕── Child 2
+│       ┕── Grand Child 2
+│           ┕── Great Grand Child 2
+│               └── Great Great Grand Child 2
+┕── Aunt
+    └── Child 3
+"#
+        .to_string()
+    );
+
+    let left = TreeFormatting {
+        branch_connectors: Some(BranchConnectors::box_chars()),
+        ..TreeFormatting::dir_tree_left(FormatCharacters::box_chars())
+    };
+    assert_eq!(
+        tree.to_string_with_format(&left).unwrap(),
+        tree.to_string_with_format(&TreeFormatting::dir_tree_left(FormatCharacters::box_chars()))
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_max_line_width() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        max_line_width: Some(16),
+        ..TreeFormatting::dir_tree(FormatCharacters::box_chars())
+    };
+
+    let result = tree.to_string_with_format(&format);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
+├── Uncle
+├── Parent
+│   ├── Child 1
+│   │   └── Gra…
+│   └── Child 2
+│       └── Gra…
+│           └──…
+│              …
+└── Aunt
+    └── Child 3
+"#
+        .to_string()
+    );
+
+    let lines: Vec<String> = tree.lines_with_format(&format).collect();
+    assert_eq!(lines.join("\n") + "\n", result);
+}
+
+#[test]
+fn test_collapse_repeated_leaves() {
+    let mut tree = StringTreeNode::new("log".to_string());
+    tree.push("connect".to_string());
+    for _ in 0..12 {
+        tree.push("retry".to_string());
+    }
+    let mut retry = StringTreeNode::new("retry".to_string());
+    retry.push("timeout".to_string());
+    retry.push("timeout".to_string());
+    tree.push_node(retry);
+    tree.push("done".to_string());
+    tree.push("done".to_string());
+
+    let format = TreeFormatting {
+        collapse_repeated_leaves: true,
+        ..TreeFormatting::dir_tree(FormatCharacters::ascii())
+    };
+
+    let result = tree.to_string_with_format(&format);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"log
++-- connect
++-- retry (×12)
++-- retry
+|   '-- timeout (×2)
+'-- done (×2)
+"#
+        .to_string()
+    );
+
+    let lines: Vec<String> = tree.lines_with_format(&format).collect();
+    assert_eq!(lines.join("\n") + "\n", result);
+}
+
+#[test]
+fn test_visibility() {
+    let mut tree = make_tree();
+    tree.subtree_at_mut(&[1])
+        .unwrap()
+        .set_visibility(Visibility::Flattened);
+    tree.subtree_at_mut(&[1, 0]).unwrap().set_hidden(true);
+    tree.subtree_at_mut(&[2]).unwrap().set_hidden(true);
+    assert_eq!(tree, make_tree());
+
+    let result = tree.to_string_with_format(&TreeFormatting::default());
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
++-- Uncle
+'-- Child 2
+    '-- Grand Child 2
+        '-- Great Grand Child 2
+            '-- Great Great Grand Child 2
+"#
+        .to_string()
+    );
+
+    let stats = tree
+        .write_with_format_stats(&mut Vec::new(), &TreeFormatting::default())
+        .unwrap();
+    assert_eq!(stats.nodes_rendered, 6);
+    assert_eq!(stats.nodes_elided, 5);
+
+    tree.subtree_at_mut(&[2]).unwrap().set_hidden(false);
+    assert_eq!(
+        tree.subtree_at(&[2]).unwrap().visibility(),
+        Visibility::Visible
+    );
+    assert!(tree.to_string().ends_with("'-- Aunt\n    '-- Child 3\n"));
+}
+
+#[test]
+fn test_default_and_with_capacity() {
+    let tree: StringTreeNode = Default::default();
+    assert_eq!(tree, StringTreeNode::new(String::new()));
+    assert!(!tree.has_children());
+
+    let mut tree = TreeNode::with_capacity(0, 3);
+    for child in 1..=3 {
+        tree.push(child);
+    }
+    assert_eq!(tree, TreeNode::with_children(0, 1..=3));
+}
+
+#[test]
+fn test_children_slice_and_vec() {
+    let mut tree = TreeNode::with_children(0, vec![5, 1, 3].into_iter());
+    tree.children_vec_mut()
+        .sort_unstable_by_key(|child| *child.data());
+    tree.children_vec_mut().insert(0, TreeNode::new(9));
+    let _ = tree.children_vec_mut().remove(0);
+
+    let children = tree.children_slice();
+    assert_eq!(children.len(), 3);
+    assert_eq!(
+        children.binary_search_by_key(&3, |child| *child.data()),
+        Ok(1)
+    );
+    assert_eq!(tree.to_string(), "0\n+-- 1\n+-- 3\n'-- 5\n");
+}
+
+#[test]
+fn test_borrow_node_data() {
+    use std::borrow::Borrow;
+    use std::collections::HashSet;
+
+    fn labels<B: Borrow<String>>(items: &[B]) -> Vec<&str> {
+        items.iter().map(|item| item.borrow().as_str()).collect()
+    }
+
+    let mut tree = make_tree();
+    tree.children_vec_mut()
+        .sort_by(|a, b| a.as_ref().cmp(b.as_ref()));
+    assert_eq!(
+        labels(tree.children_slice()),
+        vec!["Aunt", "Parent", "Uncle"]
+    );
+
+    let wanted: HashSet<String> = vec!["Uncle".to_string()].into_iter().collect();
+    assert_eq!(
+        tree.children()
+            .filter(|child| wanted.contains(child.as_ref()))
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_with_cached_labels() {
+    let mut tree = make_tree();
+    tree.subtree_at_mut(&[1, 1]).unwrap().set_collapsed(true);
+    tree.subtree_at_mut(&[2]).unwrap().set_hidden(true);
+    let expected = tree.to_string();
+
+    let tree = tree.with_cached_labels();
+    assert_eq!(tree.to_string(), expected);
+    let child = tree.subtree_at(&[1, 1]).unwrap();
+    assert!(child.is_collapsed());
+    assert_eq!(child.data().label(), "Child 2");
+    assert_eq!(child.data().clone().into_data(), "Child 2".to_string());
+}
+
+#[test]
+fn test_labeled_data() {
+    #[derive(Debug)]
+    struct Task {
+        id: u32,
+        done: bool,
+    }
+
+    let label = |task: &Task| format!("#{}{}", task.id, if task.done { " ✔" } else { "" });
+    let mut root = Labeled::new(Task { id: 1, done: false }, label);
+    assert_eq!(root.to_string(), "#1");
+    root.data_mut().done = true;
+
+    let tree = TreeNode::with_children(
+        root,
+        vec![Labeled::new(Task { id: 2, done: false }, label)].into_iter(),
+    );
+    assert_eq!(tree.to_string(), "#1 ✔\n'-- #2\n");
+    assert_eq!(
+        format!("{:?}", tree.data()),
+        "Labeled { data: Task { id: 1, done: true }, .. }"
+    );
+}
+
+#[test]
+fn test_display_with() {
+    let tree = make_tree();
+    let format = TreeFormatting::dir_tree(FormatCharacters::box_chars());
+    assert_eq!(
+        format!("{}", tree.display_with(&format)),
+        tree.to_string_with_format(&format).unwrap()
+    );
+}
+
+#[test]
+fn test_display_with_is_copy() {
+    // Neither `Clone`, `Copy`, nor `Debug`.
+    struct Label(&'static str);
+
+    impl std::fmt::Display for Label {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    let tree = TreeNode::with_children(Label("root"), vec![Label("child")].into_iter());
+    let format = TreeFormatting::default();
+    let display = tree.display_with(&format);
+    let copy = display;
+    assert_eq!(
+        format!("{}{}", display, copy),
+        "root\n'-- child\n".repeat(2)
+    );
+    assert!(format!("{:?}", display).starts_with("TreeDisplay { format: "));
+}
+
+#[test]
+fn test_to_string_tree() {
+    let tree = TreeNode::with_child_nodes(
+        1,
+        vec![TreeNode::with_children(2, vec![3, 4].into_iter()), 5.into()].into_iter(),
+    );
+    let mut expected = StringTreeNode::with_child_nodes(
+        "1".to_string(),
+        vec![
+            StringTreeNode::with_children(
+                "2".to_string(),
+                vec!["3".into(), "4".into()].into_iter(),
+            ),
+            "5".into(),
+        ]
+        .into_iter(),
+    );
+    assert_eq!(tree.to_string_tree(), expected);
+
+    let mut tree = tree;
+    tree.subtree_at_mut(&[0]).unwrap().set_collapsed(true);
+    expected.subtree_at_mut(&[0]).unwrap().set_collapsed(true);
+    assert!(tree
+        .to_string_tree()
+        .subtree_at(&[0])
+        .unwrap()
+        .is_collapsed());
+    assert_eq!(tree.to_string_tree().to_string(), expected.to_string());
+}
+
+#[test]
+fn test_elastic_tabs() {
+    let tree = StringTreeNode::with_child_nodes(
+        "src".to_string(),
+        vec![
+            StringTreeNode::with_children(
+                "bin".to_string(),
+                vec!["main.rs".to_string()].into_iter(),
+            ),
+            "lib.rs".into(),
+        ]
+        .into_iter(),
+    );
+    let format = TreeFormatting {
+        annotation_separator: "\t".to_string(),
+        elastic_tabs: Some(2),
+        ..TreeFormatting::dir_tree(FormatCharacters::ascii())
+    };
+    let annotations = |label: &String| match label.as_str() {
+        "src" => None,
+        "bin" => Some("dir\t2020-01-01".to_string()),
+        _ => Some(format!("{} bytes\t2021-06-30", label.len() * 100)),
+    };
+
+    let result = tree.to_string_with_annotations(&format, annotations);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"src
++-- bin          dir        2020-01-01
+|   '-- main.rs  700 bytes  2021-06-30
+'-- lib.rs       600 bytes  2021-06-30
+"#
+        .to_string()
+    );
+
+    let tree = StringTreeNode::with_children(
+        "name\tsize".to_string(),
+        vec!["main.rs\t700".to_string(), "lib.rs\t600".to_string()].into_iter(),
+    );
+    let result = tree.to_string_with_format(&format).unwrap();
+    assert_eq!(
+        result,
+        "name         size\n+-- main.rs  700\n'-- lib.rs   600\n"
+    );
+
+    let lines: Vec<String> = tree.lines_with_format(&format).collect();
+    assert_eq!(lines.join("\n") + "\n", result);
+}
+
+#[test]
+fn test_elastic_tabs_column_formats() {
+    let tree = StringTreeNode::with_children(
+        "name\tsize\ttype".to_string(),
+        vec![
+            "main.rs\t700\tsource".to_string(),
+            "image.png\t12800\tbinary image".to_string(),
+        ]
+        .into_iter(),
+    );
+    let format = TreeFormatting {
+        elastic_tabs: Some(1),
+        column_formats: vec![
+            ColumnFormat {
+                alignment: ColumnAlignment::Right,
+                ..Default::default()
+            },
+            ColumnFormat {
+                max_width: Some(8),
+                ..Default::default()
+            },
+        ],
+        ..TreeFormatting::dir_tree(FormatCharacters::ascii())
+    };
+
+    let result = tree.to_string_with_format(&format);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"name           size type
++-- main.rs     700 source
+'-- image.png 12800 binary …
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_max_label_widths() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        max_label_widths: vec![20, 20, 8],
+        ..TreeFormatting::dir_tree(FormatCharacters::box_chars())
+    };
+
+    let result = tree.to_string_with_format(&format);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
+├── Uncle
+├── Parent
+│   ├── Child 1
+│   │   └── Grand C…
+│   └── Child 2
+│       └── Grand C…
+│           └── Great G…
+│               └── Great G…
+└── Aunt
+    └── Child 3
+"#
+        .to_string()
+    );
+
+    let lines: Vec<String> = tree.lines_with_format(&format).collect();
+    assert_eq!(lines.join("\n") + "\n", result);
+}
+
+#[test]
+fn test_line_transforms() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        max_line_width: Some(16),
+        line_transforms: vec![
+            LineTransform::new(|line| line.replace("Child", "Kid")),
+            LineTransform::new(|line| format!("{:02} {}", line.chars().count(), line)),
+        ],
+        ..TreeFormatting::dir_tree(FormatCharacters::ascii())
+    };
+
+    let result = tree.to_string_with_format(&format);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"04 root
+09 +-- Uncle
+10 +-- Parent
+13 |   +-- Kid 1
+16 |   |   '-- Gra…
+13 |   '-- Kid 2
+16 |       '-- Gra…
+16 |           '--…
+16 |              …
+08 '-- Aunt
+13     '-- Kid 3
+"#
+        .to_string()
+    );
+
+    let lines: Vec<String> = tree.lines_with_format(&format).collect();
+    assert_eq!(lines.join("\n") + "\n", result);
+}
+
+#[test]
+fn test_limits() {
+    let mut tree = make_tree();
+    let limited = |max_nodes, max_depth| TreeFormatting {
+        max_nodes,
+        max_depth,
+        ..Default::default()
+    };
+
+    assert!(tree.check_limits(&limited(Some(11), Some(5))).is_ok());
+    assert!(tree
+        .to_string_with_format(&limited(Some(11), Some(5)))
+        .is_ok());
+
+    let error = tree
+        .to_string_with_format(&limited(Some(10), None))
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(
+        error.to_string(),
+        "the tree has more than the maximum of 10 nodes"
+    );
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let error = tree
+        .write_with_badges(&mut buffer, &limited(None, Some(4)), &|_: &String| None)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "the tree is deeper than the maximum depth of 4"
+    );
+    assert!(buffer.is_empty());
+
+    // Only the nodes written are counted.
+    tree.children_vec_mut()[1].set_collapsed(true);
+    assert!(tree.check_limits(&limited(Some(5), Some(2))).is_ok());
+}
+
+#[derive(Debug, Default)]
+struct FlushRecorder {
+    data: Vec<u8>,
+    flushed_at: Vec<usize>,
+}
+
+impl std::io::Write for FlushRecorder {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.flushed_at.push(self.data.len());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_flush_policy() {
+    let tree = make_tree();
+    let with_policy = |flush_policy| TreeFormatting {
+        flush_policy,
+        ..TreeFormatting::dir_tree(FormatCharacters::ascii())
+    };
+
+    let mut writer = FlushRecorder::default();
+    tree.write_with_format(&mut writer, &with_policy(FlushPolicy::OnFinish))
+        .unwrap();
+    assert_eq!(writer.flushed_at, vec![writer.data.len()]);
+
+    let mut writer = FlushRecorder::default();
+    tree.write_with_format(&mut writer, &with_policy(FlushPolicy::EveryLines(4)))
+        .unwrap();
+    let text = String::from_utf8(writer.data.clone()).unwrap();
+    let line_ends: Vec<usize> = text
+        .match_indices('\n')
+        .map(|(index, _)| index + 1)
+        .collect();
+    assert_eq!(
+        writer.flushed_at,
+        vec![line_ends[3], line_ends[7], text.len()]
+    );
+
+    let mut writer = FlushRecorder::default();
+    tree.write_with_format(&mut writer, &with_policy(FlushPolicy::EveryBytes(64)))
+        .unwrap();
+    assert!(writer.flushed_at.len() > 2);
+    assert!(writer
+        .flushed_at
+        .windows(2)
+        .all(|pair| pair[1] - pair[0] <= 64 + 32));
+    assert_eq!(writer.flushed_at.last(), Some(&writer.data.len()));
+}
+
+#[test]
+fn test_shared_between_threads() {
+    let tree = std::sync::Arc::new(make_tree());
+    let formats = [
+        TreeFormatting::dir_tree(FormatCharacters::ascii()),
+        TreeFormatting::dir_tree_left(FormatCharacters::box_chars()),
+        TreeFormatting {
+            orientation: TreeOrientation::Classic,
+            ..Default::default()
+        },
+    ];
+
+    let expected: Vec<String> = formats
+        .iter()
+        .map(|format| tree.to_string_with_format(format).unwrap())
+        .collect();
+    let results: Vec<String> = std::thread::scope(|scope| {
+        let handles: Vec<_> = formats
+            .iter()
+            .map(|format| {
+                let tree = std::sync::Arc::clone(&tree);
+                scope.spawn(move || tree.to_string_with_format(format).unwrap())
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_debug_structure() {
+    let mut tree = TreeNode::with_child_nodes(
+        1,
+        vec![TreeNode::with_children(2, vec![3, 4].into_iter()), 5.into()].into_iter(),
+    );
+    tree.children_vec_mut()[0].set_collapsed(true);
+    tree.children_vec_mut()[1].set_visibility(Visibility::Flattened);
+
+    assert_eq!(
+        format!("{:?}", TreeNode::new(1)),
+        "TreeNode { data: 1, children: [], collapsed: false, visibility: Visible }"
+    );
+    let result = format!("{:#?}", tree);
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"1
++-- 2 [Collapsed]
+|   +-- 3
+|   '-- 4
+'-- 5 [Flattened]"#
+    );
+}
+
+#[test]
+fn test_windows_tree() {
+    let tree = make_tree();
+    let result = tree
+        .to_string_with_format(&TreeFormatting::dir_tree(FormatCharacters::windows_ascii()))
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
++---Uncle
++---Parent
+|   +---Child 1
+|   |   \---Grand Child 1
+|   \---Child 2
+|       \---Grand Child 2
+|           \---Great Grand Child 2
+|               \---Great Great Grand Child 2
+\---Aunt
+    \---Child 3
+"#
+    );
+
+    let result = tree
+        .to_string_with_format(&TreeFormatting::windows_tree())
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
+├───Uncle
+├───Parent
+│   ├───Child 1
+│   │   └───Grand Child 1
+│   └───Child 2
+│       └───Grand Child 2
+│           └───Great Grand Child 2
+│               └───Great Great Grand Child 2
+└───Aunt
+    └───Child 3
+"#
+    );
+}
+
+#[test]
+fn test_annotation_footnotes() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        annotation_footnotes: true,
+        ..TreeFormatting::dir_tree(FormatCharacters::box_chars())
+    };
+    let annotations = |label: &String| {
+        if label.starts_with("Child") {
+            Some("https://example.com/a/very/long/address/for/children".to_string())
+        } else if label.starts_with("Grand") {
+            Some(format!("{} has a long description", label))
+        } else {
+            None
+        }
+    };
+
+    let result = tree
+        .to_string_with_annotations(&format, annotations)
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"root
+├── Uncle
+├── Parent
+│   ├── Child 1 [1]
+│   │   └── Grand Child 1 [2]
+│   └── Child 2 [1]
+│       └── Grand Child 2 [3]
+│           └── Great Grand Child 2
+│               └── Great Great Grand Child 2
+└── Aunt
+    └── Child 3 [1]
+
+[1] https://example.com/a/very/long/address/for/children
+[2] Grand Child 1 has a long description
+[3] Grand Child 2 has a long description
+"#
+    );
+}
+
+#[test]
+fn test_annotation_footnotes_line_transforms() {
+    let tree = StringTreeNode::with_children(
+        "root".to_string(),
+        vec!["a".to_string(), "b".to_string()].into_iter(),
+    );
+    let format = TreeFormatting {
+        annotation_footnotes: true,
+        line_transforms: vec![LineTransform::new(|line| format!(">{}", line))],
+        ..Default::default()
+    };
+    let annotations = |label: &String| Some(format!("about {}", label));
+
+    let result = tree
+        .to_string_with_annotations(&format, annotations)
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#">root [1]
+>+-- a [2]
+>'-- b [3]
+>
+>[1] about root
+>[2] about a
+>[3] about b
+"#
+    );
+
+    let format = TreeFormatting {
+        orientation: TreeOrientation::Classic,
+        annotation_footnotes: true,
+        ..Default::default()
+    };
+    let inline = TreeFormatting {
+        orientation: TreeOrientation::Classic,
+        ..Default::default()
+    };
+    assert_eq!(
+        tree.to_string_with_annotations(&format, annotations)
+            .unwrap(),
+        tree.to_string_with_annotations(&inline, annotations)
+            .unwrap()
+    );
+}
@@ -0,0 +1,335 @@
+use pretty_assertions::assert_eq;
+use std::fmt::{Display, Formatter, Result};
+use text_trees::*;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Entry(&'static str, f64);
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn make_tree() -> TreeNode<Entry> {
+    TreeNode::with_child_nodes(
+        Entry("/", 0.0),
+        vec![
+            TreeNode::with_children(
+                Entry("bin", 4.0),
+                vec![Entry("ls", 140.0), Entry("cat", 36.0)].into_iter(),
+            ),
+            TreeNode::with_child_nodes(
+                Entry("usr", 4.0),
+                vec![TreeNode::with_children(
+                    Entry("lib", 4.0),
+                    vec![Entry("libc.so", 2048.0)].into_iter(),
+                )]
+                .into_iter(),
+            ),
+            Entry("swapfile", 1024.5).into(),
+        ]
+        .into_iter(),
+    )
+}
+
+fn size(entry: &Entry) -> f64 {
+    entry.1
+}
+
+#[test]
+fn test_total() {
+    let tree = make_tree();
+    assert_eq!(tree.total(&size), 3260.5);
+    assert_eq!(tree.children().next().unwrap().total(&size), 180.0);
+}
+
+#[test]
+fn test_totals_column() {
+    let tree = make_tree();
+
+    let result =
+        tree.to_string_with_totals(&TreeFormatting::default(), &TotalsOptions::default(), &size);
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"/                   3260
++-- bin              180
+|   +-- ls           140
+|   '-- cat           36
++-- usr             2056
+|   '-- lib         2052
+|       '-- libc.so 2048
+'-- swapfile        1024
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_totals_include_collapsed_nodes() {
+    let mut tree = make_tree();
+    tree.subtree_at_mut(&[1]).unwrap().set_collapsed(true);
+    let options = TotalsOptions {
+        precision: 1,
+        separator: " | ".to_string(),
+        ..Default::default()
+    };
+
+    let mut buffer: Vec<u8> = Vec::new();
+    tree.write_with_totals(&mut buffer, &TreeFormatting::default(), &options, &size)
+        .unwrap();
+    let result = String::from_utf8(buffer).unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"/            | 3260.5
++-- bin      |  180.0
+|   +-- ls   |  140.0
+|   '-- cat  |   36.0
++-- usr [+2] | 2056.0
+'-- swapfile | 1024.5
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_totals_with_percentages() {
+    let mut tree = make_tree();
+    tree.push(Entry("empty", 0.0));
+    tree.push_node(TreeNode::with_children(
+        Entry("tmp", 0.0),
+        vec![Entry("x", 0.0)].into_iter(),
+    ));
+    let options = TotalsOptions {
+        percentages: true,
+        ..Default::default()
+    };
+
+    let result = tree
+        .to_string_with_totals(&TreeFormatting::default(), &options, &size)
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"/                   3260 100.0%
++-- bin              180   5.5%
+|   +-- ls           140  77.8%
+|   '-- cat           36  20.0%
++-- usr             2056  63.1%
+|   '-- lib         2052  99.8%
+|       '-- libc.so 2048  99.8%
++-- swapfile        1024  31.4%
++-- empty              0   0.0%
+'-- tmp                0   0.0%
+    '-- x              0      -
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_totals_with_column_formats() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        column_formats: vec![
+            ColumnFormat {
+                alignment: ColumnAlignment::Left,
+                min_width: Some(6),
+                ..Default::default()
+            },
+            ColumnFormat {
+                alignment: ColumnAlignment::Right,
+                max_width: Some(4),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let options = TotalsOptions {
+        percentages: true,
+        ..Default::default()
+    };
+
+    let result = tree
+        .to_string_with_totals(&format, &options, &size)
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"/                   3260   100…
++-- bin             180    5.5%
+|   +-- ls          140    77.…
+|   '-- cat         36     20.…
++-- usr             2056   63.…
+|   '-- lib         2052   99.…
+|       '-- libc.so 2048   99.…
+'-- swapfile        1024   31.…
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_totals_with_bar() {
+    let tree = make_tree();
+    let options = TotalsOptions {
+        bar: Some(BarOptions::default()),
+        ..Default::default()
+    };
+
+    let result = tree
+        .to_string_with_totals(&TreeFormatting::default(), &options, &size)
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"/                   3260 ▇▇▇▇▇▇▇▇▇▇
++-- bin              180 ▇▁▁▁▁▁▁▁▁▁
+|   +-- ls           140 ▁▁▁▁▁▁▁▁▁▁
+|   '-- cat           36 ▁▁▁▁▁▁▁▁▁▁
++-- usr             2056 ▇▇▇▇▇▇▁▁▁▁
+|   '-- lib         2052 ▇▇▇▇▇▇▁▁▁▁
+|       '-- libc.so 2048 ▇▇▇▇▇▇▁▁▁▁
+'-- swapfile        1024 ▇▇▇▁▁▁▁▁▁▁
+"#
+        .to_string()
+    );
+
+    let options = TotalsOptions {
+        percentages: true,
+        bar: Some(BarOptions {
+            width: 4,
+            scale: BarScale::PerLevel,
+            filled: '#',
+            empty: '.',
+        }),
+        ..Default::default()
+    };
+    let result = tree
+        .to_string_with_totals(&TreeFormatting::default(), &options, &size)
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"/                   3260 100.0% ####
++-- bin              180   5.5% ....
+|   +-- ls           140  77.8% ....
+|   '-- cat           36  20.0% ....
++-- usr             2056  63.1% ####
+|   '-- lib         2052  99.8% ####
+|       '-- libc.so 2048  99.8% ####
+'-- swapfile        1024  31.4% ##..
+"#
+        .to_string()
+    );
+}
+
+#[test]
+fn test_color_scale() {
+    let scale = ColorScale::green_to_red(ColorTarget::Labels);
+    assert_eq!(scale.color(0.0), Some((0, 200, 0)));
+    assert_eq!(scale.color(0.5), Some((230, 210, 0)));
+    assert_eq!(scale.color(1.0), Some((220, 0, 0)));
+    assert_eq!(scale.color(2.0), Some((220, 0, 0)));
+    assert_eq!(scale.color(0.25), Some((115, 205, 0)));
+    assert_eq!(scale.paint("x", 0.0), "\x1b[38;2;0;200;0mx\x1b[0m");
+
+    let empty = ColorScale {
+        target: ColorTarget::Labels,
+        stops: Vec::new(),
+    };
+    assert_eq!(empty.color(0.5), None);
+    assert_eq!(empty.paint("x", 0.5), "x");
+}
+
+#[test]
+fn test_totals_with_colored_labels() {
+    let tree = TreeNode::with_children(
+        Entry("/", 0.0),
+        vec![Entry("a", 10.0), Entry("bb", 0.0)].into_iter(),
+    );
+    let scale = ColorScale {
+        target: ColorTarget::Labels,
+        stops: vec![(0, 0, 0), (100, 100, 100)],
+    };
+    let options = TotalsOptions {
+        color: Some(scale.clone()),
+        ..Default::default()
+    };
+
+    let result = tree
+        .to_string_with_totals(&TreeFormatting::default(), &options, &size)
+        .unwrap();
+    assert_eq!(
+        result,
+        format!(
+            "{}      10\n+-- {}  10\n'-- {}  0\n",
+            scale.paint("/", 1.0),
+            scale.paint("a", 1.0),
+            scale.paint("bb", 0.0)
+        )
+    );
+
+    let options = TotalsOptions {
+        bar: Some(BarOptions {
+            width: 2,
+            ..Default::default()
+        }),
+        color: Some(ColorScale {
+            target: ColorTarget::Bars,
+            ..scale.clone()
+        }),
+        ..Default::default()
+    };
+    let result = tree
+        .to_string_with_totals(&TreeFormatting::default(), &options, &size)
+        .unwrap();
+    assert_eq!(
+        result.lines().last().unwrap(),
+        format!("'-- bb  0 {}", scale.paint("▁▁", 0.0))
+    );
+}
+
+#[test]
+fn test_totals_with_repeated_leaves() {
+    let tree = TreeNode::with_children(
+        Entry("/", 0.0),
+        vec![
+            Entry("x", 5.0),
+            Entry("x", 5.0),
+            Entry("x", 5.0),
+            Entry("y", 30.0),
+        ]
+        .into_iter(),
+    );
+    let format = TreeFormatting {
+        collapse_repeated_leaves: true,
+        ..Default::default()
+    };
+    let options = TotalsOptions {
+        percentages: true,
+        bar: Some(BarOptions {
+            width: 4,
+            scale: BarScale::PerLevel,
+            filled: '#',
+            empty: '.',
+        }),
+        ..Default::default()
+    };
+
+    let result = tree
+        .to_string_with_totals(&format, &options, &size)
+        .unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"/          45 100.0% ####
++-- x (×3) 15  33.3% ##..
+'-- y      30  66.7% ####
+"#
+    );
+}
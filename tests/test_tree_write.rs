@@ -0,0 +1,122 @@
+use pretty_assertions::assert_eq;
+use std::fmt::{self, Write as _};
+use std::io;
+use text_trees::*;
+
+struct FailingWriter;
+
+impl io::Write for FailingWriter {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct FlushRecorder {
+    data: Vec<u8>,
+    flushed_at: Vec<usize>,
+}
+
+impl io::Write for FlushRecorder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushed_at.push(self.data.len());
+        Ok(())
+    }
+}
+
+struct Upper(String);
+
+impl fmt::Write for Upper {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.push_str(&s.to_uppercase());
+        Ok(())
+    }
+}
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_children(
+        "root".to_string(),
+        vec!["a".to_string(), "b".to_string()].into_iter(),
+    )
+}
+
+#[test]
+fn test_write_to_io_and_fmt() {
+    let tree = make_tree();
+    let format = TreeFormatting::dir_tree(FormatCharacters::box_chars());
+
+    let mut bytes: Vec<u8> = Vec::new();
+    tree.write_to(&mut IoWrite(&mut bytes), &format).unwrap();
+    assert_eq!(String::from_utf8(bytes).unwrap(), "root\n├── a\n└── b\n");
+
+    let mut text = String::new();
+    write!(text, "> ").unwrap();
+    tree.write_to(&mut text, &format).unwrap();
+    assert_eq!(text, "> root\n├── a\n└── b\n");
+
+    let mut upper = FmtWrite(Upper(String::new()));
+    tree.write_to(&mut upper, &format).unwrap();
+    assert_eq!(upper.0 .0, "ROOT\n├── A\n└── B\n");
+}
+
+#[test]
+fn test_write_to_returns_io_error() {
+    let error = make_tree()
+        .write_to(&mut IoWrite(FailingWriter), &TreeFormatting::default())
+        .unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+}
+
+#[test]
+fn test_write_to_io_flush_policy() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        flush_policy: FlushPolicy::EveryLines(1),
+        ..Default::default()
+    };
+
+    let mut writer = FlushRecorder::default();
+    tree.write_to(&mut IoWrite(&mut writer), &format).unwrap();
+    let text = String::from_utf8(writer.data.clone()).unwrap();
+    let line_ends: Vec<usize> = text
+        .match_indices('\n')
+        .map(|(index, _)| index + 1)
+        .collect();
+    assert_eq!(line_ends.len(), 3);
+    assert_eq!(
+        writer.flushed_at,
+        vec![line_ends[0], line_ends[1], line_ends[2], text.len()]
+    );
+}
+
+#[test]
+fn test_write_to_fmt_keeps_limit_message() {
+    let tree = make_tree();
+    let format = TreeFormatting {
+        max_nodes: Some(2),
+        ..Default::default()
+    };
+
+    let mut text = String::new();
+    assert!(tree.write_with_format_fmt(&mut text, &format).is_err());
+    assert!(text.is_empty());
+
+    let error = tree
+        .write_to(&mut FmtWrite(&mut text), &format)
+        .unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(
+        error.to_string(),
+        "the tree has more than the maximum of 2 nodes"
+    );
+    assert!(text.is_empty());
+}
@@ -0,0 +1,203 @@
+use pretty_assertions::assert_eq;
+use std::cell::RefCell;
+use std::io::{ErrorKind, Write};
+use std::rc::Rc;
+use text_trees::*;
+
+fn make_tree() -> StringTreeNode {
+    StringTreeNode::with_child_nodes(
+        "root".to_string(),
+        vec![
+            "Uncle".into(),
+            StringTreeNode::with_child_nodes(
+                "Parent".to_string(),
+                vec![
+                    StringTreeNode::with_children(
+                        "Child 1".to_string(),
+                        vec!["Grand Child 1".into()].into_iter(),
+                    ),
+                    StringTreeNode::with_children(
+                        "Child 2".to_string(),
+                        vec!["Grand Child 2".into()].into_iter(),
+                    ),
+                ]
+                .into_iter(),
+            ),
+            StringTreeNode::with_children(
+                "Aunt".to_string(),
+                vec!["Child 3".to_string()].into_iter(),
+            ),
+        ]
+        .into_iter(),
+    )
+}
+
+fn replay<W: Write>(node: &StringTreeNode, writer: &mut TreeWriter<W>) {
+    writer.start_node(node.data()).unwrap();
+    for child in node.children() {
+        replay(child, writer);
+    }
+    writer.end_node().unwrap();
+}
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl SharedBuffer {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.borrow().clone()).unwrap()
+    }
+}
+
+#[test]
+fn test_events_match_tree() {
+    let tree = make_tree();
+    for format in [
+        TreeFormatting::dir_tree(FormatCharacters::ascii()),
+        TreeFormatting::dir_tree_with_prefix(FormatCharacters::box_chars(), "# ".to_string()),
+        TreeFormatting::dir_tree_left(FormatCharacters::ascii()),
+        TreeFormatting::dir_tree_left_with_prefix(FormatCharacters::box_chars(), "> ".to_string()),
+        TreeFormatting {
+            root_connector: Some('|'),
+            ..TreeFormatting::dir_tree(FormatCharacters::ascii())
+        },
+        TreeFormatting {
+            root_connector: Some('|'),
+            max_line_width: Some(12),
+            ..TreeFormatting::dir_tree(FormatCharacters::ascii())
+        },
+        TreeFormatting {
+            root_connector: Some('|'),
+            line_transforms: vec![LineTransform::new(|line| format!("[{}]", line))],
+            ..TreeFormatting::dir_tree(FormatCharacters::ascii())
+        },
+    ] {
+        let mut writer = TreeWriter::new(Vec::new(), &format);
+        replay(&tree, &mut writer);
+        let result = String::from_utf8(writer.finish().unwrap()).unwrap();
+        println!("{}", result);
+        assert_eq!(result, tree.to_string_with_format(&format).unwrap());
+    }
+}
+
+#[test]
+fn test_output_is_incremental() {
+    let buffer = SharedBuffer::default();
+    let mut writer = TreeWriter::new(buffer.clone(), &Default::default());
+
+    writer.start_node("root").unwrap();
+    assert_eq!(buffer.contents(), "");
+    writer.start_node("one").unwrap();
+    assert_eq!(buffer.contents(), "root\n");
+    writer.end_node().unwrap();
+    assert_eq!(buffer.contents(), "root\n");
+    writer.start_node("two").unwrap();
+    assert_eq!(buffer.contents(), "root\n+-- one\n");
+    writer.end_node().unwrap();
+    assert_eq!(buffer.contents(), "root\n+-- one\n");
+    writer.start_last_node("three").unwrap();
+    writer.start_last_node("four").unwrap();
+    writer.end_node().unwrap();
+    assert_eq!(
+        buffer.contents(),
+        "root\n+-- one\n+-- two\n'-- three\n    '-- four\n"
+    );
+    let _ = writer.finish().unwrap();
+}
+
+#[test]
+fn test_invalid_events() {
+    let mut writer = TreeWriter::new(Vec::new(), &Default::default());
+    assert_eq!(
+        writer.end_node().unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+    writer.start_node("root").unwrap();
+    writer.start_last_node("last").unwrap();
+    writer.end_node().unwrap();
+    assert_eq!(
+        writer.start_node("after last").unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+    writer.end_node().unwrap();
+    assert_eq!(
+        writer.start_node("second root").unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+fn test_limits() {
+    let format = TreeFormatting {
+        max_nodes: Some(3),
+        max_depth: Some(1),
+        ..Default::default()
+    };
+    let mut writer = TreeWriter::new(Vec::new(), &format);
+    writer.start_node("root").unwrap();
+    writer.start_node("one").unwrap();
+    let error = writer.start_node("too deep").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert_eq!(
+        error.to_string(),
+        "the tree is deeper than the maximum depth of 1"
+    );
+    writer.end_node().unwrap();
+    writer.start_node("two").unwrap();
+    writer.end_node().unwrap();
+    let error = writer.start_node("three").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "the tree has more than the maximum of 3 nodes"
+    );
+    let output = writer.finish().unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "root\n+-- one\n'-- two\n"
+    );
+}
+
+#[derive(Debug, Default)]
+struct FlushCounter {
+    lines: usize,
+    flushed_at: Vec<usize>,
+}
+
+impl Write for FlushCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.lines += buf.iter().filter(|b| **b == b'\n').count();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.flushed_at.push(self.lines);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_flush_policy() {
+    let format = TreeFormatting {
+        flush_policy: FlushPolicy::EveryLines(2),
+        ..Default::default()
+    };
+    let mut writer = TreeWriter::new(FlushCounter::default(), &format);
+    writer.start_node("root").unwrap();
+    for child in ["one", "two", "three", "four"] {
+        writer.start_node(child).unwrap();
+        writer.end_node().unwrap();
+    }
+    let counter = writer.finish().unwrap();
+    assert_eq!(counter.lines, 5);
+    assert_eq!(counter.flushed_at, vec![2, 4, 5]);
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "roxmltree")]
+
+use pretty_assertions::assert_eq;
+use text_trees::*;
+
+#[test]
+fn test_xml_tree() {
+    let document = roxmltree::Document::parse(
+        r#"<?xml version="1.0"?>
+<!-- a feed -->
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:media="http://search.yahoo.com/mrss/">
+  <title>Example</title>
+  <entry xml:lang="en" class='a "b"'>
+    <media:thumbnail media:url="x.png"/>
+    Some text
+  </entry>
+  <entry/>
+</feed>"#,
+    )
+    .unwrap();
+
+    let tree = xml_tree(&document);
+    assert_eq!(tree.descendant_count(), 4);
+    let entry = tree.children().nth(1).unwrap().data();
+    assert_eq!(entry.name(), "entry");
+    assert_eq!(
+        entry.attributes(),
+        &[
+            ("xml:lang".to_string(), "en".to_string()),
+            ("class".to_string(), "a \"b\"".to_string())
+        ]
+    );
+
+    let mut buffer: Vec<u8> = Vec::new();
+    write_xml_document(
+        &document,
+        &mut buffer,
+        &TreeFormatting::dir_tree(FormatCharacters::box_chars()),
+    )
+    .unwrap();
+    let result = String::from_utf8(buffer).unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"feed
+├── title
+├── entry xml:lang="en" class="a &quot;b&quot;"
+│   └── media:thumbnail media:url="x.png"
+└── entry
+"#
+    );
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "serde_yaml")]
+
+use pretty_assertions::assert_eq;
+use text_trees::*;
+
+const DOCUMENT: &str = r#"
+services:
+  web:
+    image: "nginx:1.25"
+    ports: [80, 443]
+    healthy: true
+  cache: ~
+1: one
+color: !Rgb [255, 0, 0]
+mode: !Fast
+"#;
+
+#[test]
+fn test_from_yaml_str() {
+    let tree = from_yaml_str(DOCUMENT, &ValueTreeOptions::default()).unwrap();
+    println!("{}", tree);
+    assert_eq!(
+        tree.to_string(),
+        r#"$
++-- services
+|   +-- web
+|   |   +-- image: "nginx:1.25"
+|   |   +-- ports
+|   |   |   +-- [0]: 80
+|   |   |   '-- [1]: 443
+|   |   '-- healthy: true
+|   '-- cache: null
++-- 1: "one"
++-- color
+|   +-- [0]: 255
+|   +-- [1]: 0
+|   '-- [2]: 0
+'-- mode: !Fast null
+"#
+    );
+}
+
+#[test]
+fn test_yaml_value_with_type_badges() {
+    let value: serde_yaml::Value = serde_yaml::from_str(DOCUMENT).unwrap();
+    let options = ValueTreeOptions {
+        root_label: "compose.yaml".to_string(),
+        index_labels: false,
+        type_badges: true,
+        ..Default::default()
+    };
+    let mut buffer: Vec<u8> = Vec::new();
+    write_yaml_value(&value, &mut buffer, &TreeFormatting::default(), &options).unwrap();
+    let result = String::from_utf8(buffer).unwrap();
+    println!("{}", result);
+    assert_eq!(
+        result,
+        r#"compose.yaml                    (mapping)
++-- services                    (mapping)
+|   +-- web                     (mapping)
+|   |   +-- image: "nginx:1.25" (string)
+|   |   +-- ports               (sequence)
+|   |   |   +-- 80              (number)
+|   |   |   '-- 443             (number)
+|   |   '-- healthy: true       (boolean)
+|   '-- cache: null             (null)
++-- 1: "one"                    (string)
++-- color                       (sequence)
+|   +-- 255                     (number)
+|   +-- 0                       (number)
+|   '-- 0                       (number)
+'-- mode: !Fast null            (null)
+"#
+    );
+}
+
+#[test]
+fn test_from_yaml_str_error() {
+    let error = from_yaml_str("a: [1, 2", &ValueTreeOptions::default()).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,227 @@
+/*!
+Provides the `DisplayTree` derive macro for the [text_trees](https://docs.rs/text_trees) crate,
+this crate is re-exported by `text_trees` when the `derive` feature is enabled.
+
+The macro implements the `text_trees::ToTreeNode` trait for structs and enums, the resulting
+node is labeled with the name of the struct, or enum variant, and has a child node for each
+field. Named fields are labeled `name: value` where the field's value is a leaf, or `name: Label`
+where the field's value has children; unnamed fields use the field's own node.
+
+The following attributes may be added to fields.
+
+* `#[tree(skip)]` — the field is not included in the tree.
+* `#[tree(display)]` — the field is written as a leaf using its `Display` implementation, rather
+  than requiring it to implement `ToTreeNode`.
+
+# Example
+
+```rust,ignore
+use text_trees::{DisplayTree, ToTreeNode};
+
+#[derive(DisplayTree)]
+enum Expr {
+    Number(i64),
+    Add(Box<Expr>, Box<Expr>),
+}
+
+let expr = Expr::Add(Box::new(Expr::Number(1)), Box::new(Expr::Number(2)));
+println!("{}", expr.to_tree_node());
+```
+
+```text
+Add
++-- Number
+|   '-- 1
+'-- Number
+    '-- 2
+```
+*/
+
+#![warn(
+    // ---------- Stylistic
+    future_incompatible,
+    nonstandard_style,
+    rust_2018_idioms,
+    trivial_casts,
+    trivial_numeric_casts,
+    // ---------- Public
+    missing_debug_implementations,
+    missing_docs,
+    unreachable_pub,
+    // ---------- Unsafe
+    unsafe_code,
+    // ---------- Unused
+    unused_extern_crates,
+    unused_import_braces,
+    unused_qualifications,
+    unused_results,
+)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Fields, Ident, Result,
+};
+
+// ------------------------------------------------------------------------------------------------
+// Public Macros
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Derive an implementation of `text_trees::ToTreeNode` for a struct or enum.
+///
+#[proc_macro_derive(DisplayTree, attributes(tree))]
+pub fn derive_display_tree(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FieldMode {
+    Node,
+    Display,
+    Skip,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream2> {
+    let type_name = &input.ident;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let bindings = field_bindings(&data.fields);
+            let pattern = fields_pattern(quote!(Self), &data.fields, &bindings);
+            let node = fields_node(&type_name.to_string(), &data.fields, &bindings)?;
+            quote! {
+                let #pattern = self;
+                #node
+            }
+        }
+        Data::Enum(data) => {
+            let mut arms: Vec<TokenStream2> = Default::default();
+            for variant in &data.variants {
+                let variant_name = &variant.ident;
+                let bindings = field_bindings(&variant.fields);
+                let pattern =
+                    fields_pattern(quote!(Self::#variant_name), &variant.fields, &bindings);
+                let node = fields_node(&variant_name.to_string(), &variant.fields, &bindings)?;
+                arms.push(quote! { #pattern => { #node } });
+            }
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "DisplayTree cannot be derived for unions",
+            ))
+        }
+    };
+
+    let type_params: Vec<Ident> = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = input.generics.make_where_clause();
+    for param in type_params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: ::text_trees::ToTreeNode));
+    }
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::text_trees::ToTreeNode for #type_name #type_generics #where_clause {
+            fn to_tree_node(&self) -> ::text_trees::StringTreeNode {
+                #body
+            }
+        }
+    })
+}
+
+fn field_bindings(fields: &Fields) -> Vec<Ident> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(name) => format_ident!("__field_{}", name),
+            None => format_ident!("__field_{}", index),
+        })
+        .collect()
+}
+
+fn fields_pattern(path: TokenStream2, fields: &Fields, bindings: &[Ident]) -> TokenStream2 {
+    match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote!(#path { #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!(#path ( #(#bindings),* )),
+        Fields::Unit => path,
+    }
+}
+
+fn fields_node(label: &str, fields: &Fields, bindings: &[Ident]) -> Result<TokenStream2> {
+    let mut children: Vec<TokenStream2> = Default::default();
+    for (field, binding) in fields.iter().zip(bindings) {
+        let mode = field_mode(&field.attrs)?;
+        let child = match (mode, &field.ident) {
+            (FieldMode::Skip, _) => continue,
+            (FieldMode::Node, Some(name)) => {
+                let name = name.to_string();
+                quote!(::text_trees::ToTreeNode::to_tree_node_named(#binding, #name))
+            }
+            (FieldMode::Node, None) => quote!(::text_trees::ToTreeNode::to_tree_node(#binding)),
+            (FieldMode::Display, Some(name)) => {
+                let name = name.to_string();
+                quote!(::text_trees::StringTreeNode::new(
+                    format!("{}: {}", #name, #binding)
+                ))
+            }
+            (FieldMode::Display, None) => {
+                quote!(::text_trees::StringTreeNode::new(#binding.to_string()))
+            }
+        };
+        children.push(child);
+    }
+    // silence unused variable warnings for skipped fields.
+    let unused = bindings.iter();
+    Ok(quote! {
+        #(let _ = &#unused;)*
+        ::text_trees::StringTreeNode::with_child_nodes(
+            #label.to_string(),
+            ::std::vec![#(#children),*].into_iter(),
+        )
+    })
+}
+
+fn field_mode(attrs: &[Attribute]) -> Result<FieldMode> {
+    let mut mode = FieldMode::Node;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("tree")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                mode = FieldMode::Skip;
+                Ok(())
+            } else if meta.path.is_ident("display") {
+                mode = FieldMode::Display;
+                Ok(())
+            } else {
+                Err(meta.error("expected `skip` or `display`"))
+            }
+        })?;
+    }
+    Ok(mode)
+}